@@ -24,7 +24,9 @@ use crate::{
         AbilityPositiveEffect, AbilityReach, AbilityRollType, AbilityTarget, ApplyEffect,
         AreaEffect, AreaShape, AreaTargetAcquisition, AttackEnhancement, AttackEnhancementEffect,
         AttackEnhancementOnHitEffect, BaseAction, Character, Condition, DefenseType, HandType,
-        OnAttackedReaction, OnHitReaction, OnHitReactionEffect, Range, Shield, Weapon,
+        MovementEnhancement, OnAttackedReaction, OnAttackedReactionId, OnHitReaction,
+        OnHitReactionEffect, Range, Shield, SpellGrazeBehavior, Weapon, COUNTERSPELL_AP_COST,
+        COUNTERSPELL_MANA_COST, GUARDIAN_AP_COST, GUARDING_EVASION_PER_AP,
     },
     data::PassiveSkill,
     drawing::{draw_dashed_rectangle_lines, draw_rounded_rectangle_lines},
@@ -36,11 +38,21 @@ pub const EVASION_STR: &str = "  |<shield>| |<stat>Evasion|";
 const WILL_STR: &str = "  |<shield>| |<stat>Will|";
 const TOUGHNESS_STR: &str = "  |<shield>| |<stat>Toughness|";
 
-fn defense_str(defense_type: DefenseType) -> &'static str {
+fn defense_str(defense_type: DefenseType) -> String {
     match defense_type {
-        DefenseType::Will => WILL_STR,
-        DefenseType::Evasion => EVASION_STR,
-        DefenseType::Toughness => TOUGHNESS_STR,
+        DefenseType::Will => WILL_STR.to_string(),
+        DefenseType::Evasion => EVASION_STR.to_string(),
+        DefenseType::Toughness => TOUGHNESS_STR.to_string(),
+        DefenseType::LowerOf(a, b) => format!(
+            "  |<shield>| lower of |<stat>{}| / |<stat>{}|",
+            a.label(),
+            b.label()
+        ),
+        DefenseType::HigherOf(a, b) => format!(
+            "  |<shield>| higher of |<stat>{}| / |<stat>{}|",
+            a.label(),
+            b.label()
+        ),
     }
 }
 
@@ -127,6 +139,7 @@ pub fn button_action_tooltip(action: &ButtonAction) -> Tooltip {
         ButtonAction::Action(base_action) => base_action_tooltip(base_action),
         ButtonAction::AttackEnhancement(enhancement) => attack_enhancement_tooltip(enhancement),
         ButtonAction::AbilityEnhancement(enhancement) => ability_enhancement_tooltip(enhancement),
+        ButtonAction::MovementEnhancement(enhancement) => movement_enhancement_tooltip(enhancement),
         ButtonAction::OnAttackedReaction(reaction) => on_attacked_reaction_tooltip(reaction),
         ButtonAction::OnHitReaction(reaction) => on_hit_reaction_tooltip(reaction),
         ButtonAction::Proceed => Tooltip {
@@ -137,6 +150,24 @@ pub fn button_action_tooltip(action: &ButtonAction) -> Tooltip {
             header: "THIS SHOULD NOT BE SHOWN".to_string(), // This is replaced on-the-fly if needed
             ..Default::default()
         },
+        ButtonAction::OverwatchShot => Tooltip {
+            header: "Overwatch shot".to_string(),
+            description: Some("Shoot the enemy that moved into view, using the reserved AP"),
+            ..Default::default()
+        },
+        ButtonAction::Intercept => Tooltip {
+            header: format!("Intercept ({} AP)", GUARDIAN_AP_COST),
+            description: Some("Take the attack instead of your ally"),
+            ..Default::default()
+        },
+        ButtonAction::Counterspell => Tooltip {
+            header: format!(
+                "Counterspell ({} AP, {} mana)",
+                COUNTERSPELL_AP_COST, COUNTERSPELL_MANA_COST
+            ),
+            description: Some("Interrupt the spell if your spell roll beats the caster's"),
+            ..Default::default()
+        },
         ButtonAction::Passive(skill) => passive_skill_tooltip(skill),
     }
 }
@@ -150,6 +181,22 @@ fn passive_skill_tooltip(skill: &PassiveSkill) -> Tooltip {
     }
 }
 
+fn movement_enhancement_tooltip(enhancement: &MovementEnhancement) -> Tooltip {
+    Tooltip {
+        header: format!(
+            "{} {}",
+            enhancement.name,
+            cost_string(enhancement.action_point_cost, enhancement.stamina_cost, 0)
+        ),
+        description: Some(enhancement.description),
+        technical_description: vec![format!(
+            "|<boot>||<stat>Move| + {} x Agility",
+            enhancement.bonus_distance_per_agility
+        )],
+        ..Default::default()
+    }
+}
+
 fn on_attacked_reaction_tooltip(reaction: &OnAttackedReaction) -> Tooltip {
     let mut technical_description = vec![];
 
@@ -158,10 +205,20 @@ fn on_attacked_reaction_tooltip(reaction: &OnAttackedReaction) -> Tooltip {
             "|<value>+{}| |<shield>||<stat>Evasion|",
             reaction.effect.bonus_evasion
         ));
+        if reaction.id == OnAttackedReactionId::Parry {
+            technical_description
+                .push("Less against heavier weapons, more against lighter".to_string());
+        }
     }
     if reaction.effect.bonus_armor > 0 {
         technical_description.push(format!("|<value>+{}| armor", reaction.effect.bonus_armor));
     }
+    if reaction.effect.counterattack {
+        technical_description.push("On graze: free attack against the attacker".to_string());
+    }
+    if reaction.only_from_front {
+        technical_description.push("|<faded>Only against attacks from the front|".to_string());
+    }
 
     Tooltip {
         header: format!(
@@ -185,11 +242,21 @@ fn on_hit_reaction_tooltip(reaction: &OnHitReaction) -> Tooltip {
         technical_description.push("  |<shield>| = toughness".to_string());
         technical_description.push("  Dazed (2+)".to_string());
     }
+    if let OnHitReactionEffect::SpellReflect(percentage) = reaction.effect {
+        technical_description.push("|<dice>| Spell".to_string());
+        technical_description.push("Targets the attacker".to_string());
+        technical_description.push("  |<shield>| = will".to_string());
+        technical_description.push(format!("  {}% of the damage taken", percentage));
+    }
     Tooltip {
         header: format!(
             "{} {}",
             reaction.name,
-            cost_string(reaction.action_point_cost, reaction.stamina_cost, 0)
+            cost_string(
+                reaction.action_point_cost,
+                reaction.stamina_cost,
+                reaction.mana_cost
+            )
         ),
         description: Some(reaction.description),
         technical_description,
@@ -303,11 +370,16 @@ fn describe_attack_enhancement_effect(effect: &AttackEnhancementEffect, t: &mut
             AttackEnhancementOnHitEffect::Target(defense_type, apply_effect) => {
                 t.technical_description.push("|<faded>Target:|".to_string());
                 if let Some(defense_type) = defense_type {
-                    t.technical_description
-                        .push(defense_str(defense_type).to_string());
+                    t.technical_description.push(defense_str(defense_type));
                 }
                 describe_apply_effect(apply_effect, t);
             }
+            AttackEnhancementOnHitEffect::Disarm(defense_type) => {
+                t.technical_description.push("|<faded>Target:|".to_string());
+                t.technical_description.push(defense_str(defense_type));
+                t.technical_description
+                    .push("Knock away main-hand weapon".to_string());
+            }
         }
     }
 }
@@ -347,6 +419,11 @@ fn ability_enhancement_tooltip(enhancement: &AbilityEnhancement) -> Tooltip {
             ));
         }
 
+        if effect.splash {
+            t.technical_description
+                .push("Also hits enemies next to the target".to_string());
+        }
+
         if effect.bonus_area_damage > 0 {
             t.technical_description.push(format!(
                 "|<value>+{}| |<sword>| (area)",
@@ -376,6 +453,11 @@ fn ability_enhancement_tooltip(enhancement: &AbilityEnhancement) -> Tooltip {
                 effect.increased_radius_tenths as f32 * 0.1
             ));
         }
+
+        if effect.spare_allies {
+            t.technical_description
+                .push("Allies are not hit".to_string());
+        }
     }
 
     if let Some(effect) = enhancement.attack_effect {
@@ -423,6 +505,20 @@ fn base_action_tooltip(base_action: &BaseAction) -> Tooltip {
             description: Some("Use a consumable from your inventory (e.g. a potion)."),
             ..Default::default()
         },
+        BaseAction::DelayTurn => Tooltip {
+            header: "Delay turn".to_string(),
+            description: Some("Let the next character in the turn order act before you."),
+            ..Default::default()
+        },
+        BaseAction::GuardStance => Tooltip {
+            header: "Guard".to_string(),
+            description: Some("End your turn, and gain evasion for each AP left unspent."),
+            technical_description: vec![format!(
+                "|<value>+{}| |<shield>|<stat>Evasion| per AP, until your next turn",
+                GUARDING_EVASION_PER_AP
+            )],
+            ..Default::default()
+        },
     }
 }
 
@@ -431,6 +527,9 @@ pub fn describe_apply_effect(effect: ApplyEffect, t: &mut Tooltip) {
         ApplyEffect::RemoveActionPoints(n) => t
             .technical_description
             .push(format!("  Loses |<value>{}| AP", n)),
+        ApplyEffect::GainActionPoints(n) => t
+            .technical_description
+            .push(format!("  Gains |<value>{}| AP", n)),
         ApplyEffect::GainStamina(n) => t
             .technical_description
             .push(format!("  |<stamina>| |<value>{}| stamina", n)),
@@ -468,6 +567,30 @@ pub fn describe_apply_effect(effect: ApplyEffect, t: &mut Tooltip) {
                 .push(format!("  |<keyword>Pushed| ({})", amount));
             t.keywords.push(Keyword::Pushed);
         }
+        ApplyEffect::DrainMana {
+            amount,
+            damage_per_point,
+        } => {
+            let mut line = format!("  Drains |<value>{}| mana", amount);
+            if damage_per_point > 0 {
+                line.push_str(&format!(
+                    ", |<value>{}| damage per point drained",
+                    damage_per_point
+                ));
+            }
+            t.technical_description.push(line);
+        }
+        ApplyEffect::Dispel(dispel) => {
+            let kind = if dispel.positive {
+                "beneficial"
+            } else {
+                "harmful"
+            };
+            t.technical_description.push(format!(
+                "  Removes up to |<value>{}| {} conditions",
+                dispel.max_count, kind
+            ));
+        }
     }
 }
 
@@ -507,6 +630,14 @@ fn ability_tooltip(ability: &Ability) -> Tooltip {
         t.technical_description.push(s);
     }
 
+    if let Some(backlash) = ability.backlash {
+        t.technical_description.push(format!(
+            "|<faded>Backlash on a roll of {} or less:|",
+            backlash.dice_roll_at_most
+        ));
+        describe_apply_effect(backlash.effect, &mut t);
+    }
+
     t.technical_description.push("".to_string());
 
     match ability.target {
@@ -542,21 +673,37 @@ fn ability_tooltip(ability: &Ability) -> Tooltip {
             }
         }
 
-        AbilityTarget::Ally { range, effect } => {
+        AbilityTarget::Ally {
+            range,
+            effect,
+            pull_toward_caster,
+        } => {
             t.technical_description
                 .push(format!("|<faded>Target ally (range {})|", range));
             describe_ability_ally_effect(effect, &mut t);
+            if let Some(distance) = pull_toward_caster {
+                t.technical_description
+                    .push(format!("Pull target up to {} cells toward you", distance));
+            }
         }
 
         AbilityTarget::None {
             self_area,
             self_effect,
+            summon,
         } => {
             if let Some(effect) = self_effect {
                 t.technical_description.push("|<faded>Self|".to_string());
                 describe_ability_ally_effect(effect, &mut t);
             }
 
+            if let Some(summon) = summon {
+                t.technical_description.push(format!(
+                    "Summon a {} for |<value>{}| rounds",
+                    summon.name, summon.duration_rounds
+                ));
+            }
+
             if let Some(AreaEffect {
                 shape,
                 acquisition,
@@ -589,11 +736,58 @@ fn ability_tooltip(ability: &Ability) -> Tooltip {
             }
         }
 
-        AbilityTarget::Area { range, area_effect } => {
-            describe_area_effect(Some(range), area_effect, &mut t)
+        AbilityTarget::Teleport { range } => {
+            t.technical_description
+                .push(format!("|<faded>Teleport (range {})|", range));
+        }
+
+        AbilityTarget::Trap { range, effect } => {
+            t.technical_description
+                .push(format!("|<faded>Trap (range {})|", range));
+            t.technical_description
+                .push("|<faded>When an enemy steps on it:|".to_string());
+            describe_ability_negative_effect(AbilityNegativeEffect::Spell(effect), &mut t);
+        }
+
+        AbilityTarget::Area {
+            range,
+            area_effect,
+            ground_effect,
+        } => {
+            describe_area_effect(Some(range), area_effect, &mut t);
+            if let Some(ground_effect) = ground_effect {
+                t.technical_description.push(format!(
+                    "|<faded>Lingers for {} rounds|",
+                    ground_effect.remaining_rounds
+                ));
+                if let Some(effect) = ground_effect.on_enter {
+                    t.technical_description
+                        .push("|<faded>On entering:|".to_string());
+                    describe_apply_effect(effect, &mut t);
+                }
+                if let Some(effect) = ground_effect.on_end_turn {
+                    t.technical_description
+                        .push("|<faded>On ending turn there:|".to_string());
+                    describe_apply_effect(effect, &mut t);
+                }
+            }
         }
     };
 
+    if ability.cooldown_rounds > 0 {
+        t.technical_description.push("".to_string());
+        t.technical_description.push(format!(
+            "|<faded>Cooldown: {} rounds|",
+            ability.cooldown_rounds
+        ));
+    }
+
+    if ability.is_once_per_fight() {
+        t.technical_description.push("".to_string());
+        t.technical_description
+            .push("|<faded>Once per fight|".to_string());
+    }
+
     t
 }
 
@@ -653,8 +847,7 @@ fn describe_ability_negative_effect(effect: AbilityNegativeEffect, t: &mut Toolt
     match effect {
         AbilityNegativeEffect::Spell(effect) => {
             if let Some(defense_type) = effect.defense_type {
-                t.technical_description
-                    .push(defense_str(defense_type).to_string())
+                t.technical_description.push(defense_str(defense_type))
             };
 
             match effect.damage {
@@ -664,23 +857,45 @@ fn describe_ability_negative_effect(effect: AbilityNegativeEffect, t: &mut Toolt
                 Some(AbilityDamage::AtLeast(n)) => t
                     .technical_description
                     .push(format!("  |<sword>| |<value>{}|", n)),
+                Some(AbilityDamage::Scaling {
+                    base,
+                    per_modifier_tenths,
+                }) => t.technical_description.push(format!(
+                    "  |<sword>| |<value>{}| +{}% of |<stat>Spell| mod",
+                    base,
+                    per_modifier_tenths * 10
+                )),
                 None => {}
             }
 
             for apply_effect in effect.on_hit.unwrap_or_default().iter().flatten() {
                 describe_apply_effect(*apply_effect, t);
             }
+
+            match effect.graze_behavior {
+                SpellGrazeBehavior::Reduced => {}
+                SpellGrazeBehavior::DamageOnly => t
+                    .technical_description
+                    .push("  Graze: full damage, no effects".to_string()),
+                SpellGrazeBehavior::EffectsOnly => t
+                    .technical_description
+                    .push("  Graze: no damage".to_string()),
+            }
         }
 
         AbilityNegativeEffect::PerformAttack(ability_attack_effect) => {
             t.technical_description
-                .push(defense_str(DefenseType::Evasion).to_string());
+                .push(defense_str(DefenseType::Evasion));
             let dmg_str = ability_attack_effect
                 .override_damage
                 .map(|dmg| dmg.to_string())
                 .unwrap_or("weapon".to_string());
             t.technical_description
                 .push(format!("  |<sword>| |<value>{dmg_str}|"));
+            if ability_attack_effect.flurry {
+                t.technical_description
+                    .push("  Main-hand, then off-hand".to_string());
+            }
             if let Some(apply_effect) = ability_attack_effect.on_hit {
                 describe_apply_effect(apply_effect, t);
             }
@@ -697,6 +912,14 @@ fn describe_ability_ally_effect(effect: AbilityPositiveEffect, t: &mut Tooltip)
     for apply in effect.apply.iter().flatten().flatten().copied() {
         describe_apply_effect(apply, t);
     }
+
+    if let Some(backfire) = effect.backfire {
+        t.technical_description.push(format!(
+            "|<faded>Backfires on a roll below {}:|",
+            backfire.roll_below
+        ));
+        describe_apply_effect(backfire.effect, t);
+    }
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -737,6 +960,7 @@ pub struct ActionButton {
     ap_color: Rc<Cell<Color>>,
     parent_bg_color: Option<Color>,
     bottom_resource_text: Option<Element>,
+    font: Font,
 }
 
 pub const REGULAR_ACTION_BUTTON_SIZE: (f32, f32) = (64.0, 64.0);
@@ -858,6 +1082,16 @@ impl ActionButton {
             context: None,
             ap_color,
             parent_bg_color: None,
+            font: font.clone(),
+        }
+    }
+
+    fn cooldown_remaining_rounds(&self) -> u32 {
+        match (self.action, &self.character) {
+            (ButtonAction::Action(BaseAction::UseAbility(ability)), Some(character)) => {
+                character.ability_cooldown_remaining_rounds(ability.id)
+            }
+            _ => 0,
         }
     }
 
@@ -899,6 +1133,38 @@ impl ActionButton {
                         self.tooltip.borrow_mut().error = Some("Requires melee weapon!");
                     }
 
+                    self.tooltip_is_based_on_equipped_weapon
+                        .set(equipped_weapon);
+                }
+            } else if ability.requires_dual_wield() {
+                let equipped_weapon = match &self.character {
+                    Some(ch) => ch.weapon(HandType::OffHand),
+                    None => None,
+                };
+
+                if self.tooltip_is_based_on_equipped_weapon.get() != equipped_weapon {
+                    if self.character.as_ref().unwrap().is_dual_wielding() {
+                        self.tooltip.borrow_mut().error = None;
+                    } else {
+                        self.tooltip.borrow_mut().error = Some("Requires a weapon in each hand");
+                    }
+
+                    self.tooltip_is_based_on_equipped_weapon
+                        .set(equipped_weapon);
+                }
+            } else if ability.requires_reach_weapon() {
+                let equipped_weapon = match &self.character {
+                    Some(ch) => ch.weapon(HandType::MainHand),
+                    None => None,
+                };
+
+                if self.tooltip_is_based_on_equipped_weapon.get() != equipped_weapon {
+                    if self.character.as_ref().unwrap().has_reach_weapon() {
+                        self.tooltip.borrow_mut().error = None;
+                    } else {
+                        self.tooltip.borrow_mut().error = Some("Requires reach weapon");
+                    }
+
                     self.tooltip_is_based_on_equipped_weapon
                         .set(equipped_weapon);
                 }
@@ -920,6 +1186,17 @@ impl ActionButton {
                         .set(equipped_shield);
                 }
             }
+
+            if ability.is_once_per_fight() {
+                let used = self
+                    .character
+                    .as_ref()
+                    .is_some_and(|ch| ch.has_used_ability_this_fight(ability.id));
+                self.tooltip.borrow_mut().error = used.then_some("Already used this fight");
+            } else if ability.cooldown_rounds > 0 {
+                let on_cooldown = self.cooldown_remaining_rounds() > 0;
+                self.tooltip.borrow_mut().error = on_cooldown.then_some("On cooldown");
+            }
         }
 
         if let ButtonAction::Action(BaseAction::Attack(attack)) = self.action {
@@ -1084,6 +1361,25 @@ impl Drawable for ActionButton {
 
         draw_texture_ex(icon, x + 2.0, y + 2.0, WHITE, params);
 
+        let cooldown = self.cooldown_remaining_rounds();
+        if cooldown > 0 {
+            draw_rectangle(x, y, w, h, Color::new(0.0, 0.0, 0.0, 0.6));
+            let text = cooldown.to_string();
+            let font_size = 32;
+            let text_dim = measure_text(&text, Some(&self.font), font_size, 1.0);
+            draw_text_rounded(
+                &text,
+                x + (w - text_dim.width) / 2.0,
+                y + (h - text_dim.height) / 2.0 + text_dim.offset_y,
+                TextParams {
+                    font: Some(&self.font),
+                    font_size,
+                    color: WHITE,
+                    ..Default::default()
+                },
+            );
+        }
+
         if let Some((keycode, font)) = self.hotkey.borrow().as_ref() {
             let text = hotkey_string(keycode);
             let margin = 3.0;
@@ -1242,7 +1538,11 @@ pub enum ButtonAction {
     OnHitReaction(OnHitReaction),
     AttackEnhancement(AttackEnhancement),
     AbilityEnhancement(AbilityEnhancement),
+    MovementEnhancement(MovementEnhancement),
     OpportunityAttack,
+    OverwatchShot,
+    Intercept,
+    Counterspell,
     Proceed,
     Passive(PassiveSkill),
 }
@@ -1256,12 +1556,18 @@ impl ButtonAction {
                 BaseAction::Move => "Move",
                 BaseAction::ChangeEquipment => "Change equipment",
                 BaseAction::UseConsumable => "Use consumable",
+                BaseAction::DelayTurn => "Delay turn",
+                BaseAction::GuardStance => "Guard",
             },
             ButtonAction::OnAttackedReaction(reaction) => reaction.name,
             ButtonAction::OnHitReaction(reaction) => reaction.name,
             ButtonAction::AttackEnhancement(enhancement) => enhancement.name,
             ButtonAction::AbilityEnhancement(enhancement) => enhancement.name,
+            ButtonAction::MovementEnhancement(enhancement) => enhancement.name,
             ButtonAction::OpportunityAttack => "Opportunity attack",
+            ButtonAction::OverwatchShot => "Overwatch shot",
+            ButtonAction::Intercept => "Intercept",
+            ButtonAction::Counterspell => "Counterspell",
             ButtonAction::Proceed => "Proceed",
             ButtonAction::Passive(skill) => skill.name(),
         }
@@ -1291,13 +1597,19 @@ impl ButtonAction {
                 BaseAction::Move => IconId::Move,
                 BaseAction::ChangeEquipment => IconId::Equip,
                 BaseAction::UseConsumable => IconId::UseConsumable,
+                BaseAction::DelayTurn => IconId::EndTurn,
+                BaseAction::GuardStance => IconId::Block,
             },
             ButtonAction::AttackEnhancement(enhancement) => enhancement.icon,
             ButtonAction::AbilityEnhancement(enhancement) => enhancement.icon,
+            ButtonAction::MovementEnhancement(enhancement) => enhancement.icon,
             ButtonAction::OnAttackedReaction(reaction) => reaction.icon,
             ButtonAction::OnHitReaction(reaction) => reaction.icon,
             ButtonAction::Proceed => IconId::Go,
             ButtonAction::OpportunityAttack => IconId::MeleeAttack,
+            ButtonAction::OverwatchShot => IconId::RangedAttack,
+            ButtonAction::Intercept => IconId::Block,
+            ButtonAction::Counterspell => IconId::Mindblast,
             ButtonAction::Passive(skill) => skill.icon(),
         }
     }
@@ -1309,8 +1621,13 @@ impl ButtonAction {
             ButtonAction::OnHitReaction(reaction) => reaction.action_point_cost as i32,
             ButtonAction::AttackEnhancement(enhancement) => enhancement.action_point_cost as i32,
             ButtonAction::AbilityEnhancement(enhancement) => enhancement.action_point_cost as i32,
+            ButtonAction::MovementEnhancement(enhancement) => enhancement.action_point_cost as i32,
             ButtonAction::Proceed => 0,
             ButtonAction::OpportunityAttack => 1,
+            // Paid for by the AP that was reserved when entering the stance
+            ButtonAction::OverwatchShot => 0,
+            ButtonAction::Intercept => GUARDIAN_AP_COST as i32,
+            ButtonAction::Counterspell => COUNTERSPELL_AP_COST as i32,
             ButtonAction::Passive(..) => 0,
         }
     }
@@ -1340,8 +1657,12 @@ impl ButtonAction {
             ButtonAction::OnHitReaction(..) => 0,
             ButtonAction::AttackEnhancement(enhancement) => enhancement.mana_cost,
             ButtonAction::AbilityEnhancement(enhancement) => enhancement.mana_cost,
+            ButtonAction::MovementEnhancement(..) => 0,
             ButtonAction::Proceed => 0,
             ButtonAction::OpportunityAttack => 0,
+            ButtonAction::OverwatchShot => 0,
+            ButtonAction::Intercept => 0,
+            ButtonAction::Counterspell => COUNTERSPELL_MANA_COST,
             ButtonAction::Passive(..) => 0,
         }
     }
@@ -1353,8 +1674,12 @@ impl ButtonAction {
             ButtonAction::OnHitReaction(reaction) => reaction.stamina_cost,
             ButtonAction::AttackEnhancement(enhancement) => enhancement.stamina_cost,
             ButtonAction::AbilityEnhancement(enhancement) => enhancement.stamina_cost,
+            ButtonAction::MovementEnhancement(enhancement) => enhancement.stamina_cost,
             ButtonAction::Proceed => 0,
             ButtonAction::OpportunityAttack => 0,
+            ButtonAction::OverwatchShot => 0,
+            ButtonAction::Intercept => 0,
+            ButtonAction::Counterspell => 0,
             ButtonAction::Passive(..) => 0,
         }
     }
@@ -1380,6 +1705,13 @@ impl ButtonAction {
         }
     }
 
+    pub fn unwrap_movement_enhancement(&self) -> MovementEnhancement {
+        match self {
+            ButtonAction::MovementEnhancement(enhancement) => *enhancement,
+            _ => panic!(),
+        }
+    }
+
     pub fn unwrap_on_attacked_reaction(&self) -> OnAttackedReaction {
         match self {
             ButtonAction::OnAttackedReaction(reaction) => *reaction,
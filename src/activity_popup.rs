@@ -64,6 +64,10 @@ pub struct ActivityPopup {
 }
 
 impl ActivityPopup {
+    pub fn add_character(&mut self, character: Rc<Character>, after: CharacterId) {
+        self.characters.insert_after(character, after);
+    }
+
     pub fn new(
         font: Font,
         state: Rc<RefCell<UiState>>,
@@ -366,6 +370,7 @@ impl ActivityPopup {
         let mut changed_on_attacked_reaction = false;
         let mut changed_ability_enhancements = false;
         let mut changed_attack_enhancements = false;
+        let mut changed_movement_enhancements = false;
         for event in self.choice_button_events.borrow_mut().drain(..) {
             match event {
                 InternalUiEvent::ButtonHovered(ButtonHovered {
@@ -425,6 +430,20 @@ impl ActivityPopup {
 
                                 changed_ability_enhancements = true;
                             }
+                            ConfiguredAction::Move {
+                                selected_enhancements,
+                                selected_movement_path,
+                                cost,
+                            } => {
+                                *selected_enhancements = selected_button_actions
+                                    .iter()
+                                    .map(|action| action.unwrap_movement_enhancement())
+                                    .collect();
+                                // The reachable area changed, so the destination must be picked again
+                                selected_movement_path.clear();
+                                *cost = 0;
+                                changed_movement_enhancements = true;
+                            }
                             _ => unreachable!(),
                         },
                         UiState::ReactingToAttack { selected, .. } => {
@@ -446,6 +465,15 @@ impl ActivityPopup {
                             // It's a binary choice of 'use opportunity attack or not'
                             *selected = !selected_button_actions.is_empty();
                         }
+                        UiState::ReactingToOverwatch { selected, .. } => {
+                            *selected = !selected_button_actions.is_empty();
+                        }
+                        UiState::ReactingToIntercept { selected, .. } => {
+                            *selected = !selected_button_actions.is_empty();
+                        }
+                        UiState::ReactingToCounterspell { selected, .. } => {
+                            *selected = !selected_button_actions.is_empty();
+                        }
                         UiState::ChoosingAction | UiState::Idle => unreachable!(),
                     }
 
@@ -479,6 +507,9 @@ impl ActivityPopup {
         if changed_attack_enhancements {
             return Some(ActivityPopupOutcome::ChangedAttackEnhancements);
         }
+        if changed_movement_enhancements {
+            return Some(ActivityPopupOutcome::ChangedMovementEnhancements);
+        }
         if let Some(slider) = &self.movement_cost_slider {
             if slider.has_changed.take() {
                 return Some(ActivityPopupOutcome::ChangedMovementSprint(
@@ -548,10 +579,15 @@ impl ActivityPopup {
             _ => None,
         };
 
-        let reserved_from_action = base_action
-            .as_ref()
-            .map(|action| action.base_action_point_cost())
-            .unwrap_or(0);
+        let reserved_from_action = match base_action {
+            Some(ConfiguredAction::ChangeEquipment { .. }) => {
+                self.characters
+                    .get(self.relevant_character_id)
+                    .change_equipment_ap_cost() as i32
+            }
+            Some(action) => action.base_action_point_cost(),
+            None => 0,
+        };
         let mut reserved_from_choices: i32 = 0;
         for action in self.selected_choices() {
             reserved_from_choices += action.action_point_cost();
@@ -763,10 +799,26 @@ impl ActivityPopup {
                         }
                     }
 
-                    ConfiguredAction::Move { .. } => {
+                    ConfiguredAction::Move {
+                        selected_enhancements,
+                        ..
+                    } => {
                         let active_char = self.characters.get(active_character_id);
                         //let speed = active_char.move_speed();
                         //lines.push(format!("Speed: {:.1}", speed));
+
+                        for enhancement in active_char.known_movement_enhancements() {
+                            let btn =
+                                self.new_button(ButtonAction::MovementEnhancement(enhancement));
+                            btn.enabled
+                                .set(active_char.can_use_movement_enhancement(&enhancement));
+                            if selected_enhancements.contains(&enhancement) {
+                                self.selected_choice_button_ids.push(btn.id);
+                                btn.selected.set(ButtonSelected::Yes);
+                            }
+                            popup_buttons.push(btn);
+                        }
+
                         let stamina = &active_char.stamina;
                         if stamina.max() > 0 {
                             let max_stamina_spend = stamina.current();
@@ -777,8 +829,15 @@ impl ActivityPopup {
                         }
                     }
 
-                    ConfiguredAction::ChangeEquipment { .. } => {}
+                    ConfiguredAction::ChangeEquipment { .. } => {
+                        let character = self.characters.get(active_character_id);
+                        if character.has_free_equipment_change() {
+                            lines.push("Free this turn (Quickdraw)".to_string());
+                        }
+                    }
                     ConfiguredAction::UseConsumable { .. } => {}
+                    ConfiguredAction::DelayTurn => {}
+                    ConfiguredAction::GuardStance => {}
                 }
             }
 
@@ -808,6 +867,9 @@ impl ActivityPopup {
                 {
                     let btn_action = ButtonAction::OnAttackedReaction(reaction);
                     let btn = self.new_button(btn_action);
+                    // e.g. a shield doesn't help against an attack from behind
+                    btn.enabled
+                        .set(reactor.is_reaction_effective_against(reaction, attacker.pos()));
                     popup_buttons.push(btn);
                 }
             }
@@ -869,6 +931,54 @@ impl ActivityPopup {
                 popup_buttons.push(btn);
             }
 
+            UiState::ReactingToOverwatch {
+                reactor, target, ..
+            } => {
+                self.relevant_character_id = *reactor;
+                lines.push("React (overwatch)".to_string());
+                lines.push(format!(
+                    "{} spotted {}",
+                    self.characters.get(*reactor).name,
+                    self.characters.get(*target).name
+                ));
+
+                let btn = self.new_button(ButtonAction::OverwatchShot);
+                popup_buttons.push(btn);
+            }
+
+            UiState::ReactingToIntercept {
+                reactor, victim, ..
+            } => {
+                self.relevant_character_id = *reactor;
+                lines.push("React (guardian)".to_string());
+                lines.push(format!(
+                    "{} can take the attack against {}",
+                    self.characters.get(*reactor).name,
+                    self.characters.get(*victim).name
+                ));
+
+                let btn = self.new_button(ButtonAction::Intercept);
+                popup_buttons.push(btn);
+            }
+
+            UiState::ReactingToCounterspell {
+                reactor,
+                caster,
+                ability,
+                ..
+            } => {
+                self.relevant_character_id = *reactor;
+                lines.push("React (counterspell)".to_string());
+                lines.push(format!(
+                    "{} is casting {}",
+                    self.characters.get(*caster).name,
+                    ability.name
+                ));
+
+                let btn = self.new_button(ButtonAction::Counterspell);
+                popup_buttons.push(btn);
+            }
+
             UiState::ChoosingAction | UiState::Idle => {}
         }
 
@@ -928,6 +1038,7 @@ pub enum ActivityPopupOutcome {
     ChangedAbilityEnhancements,
     ChangedAttackEnhancements,
     ChangedMovementSprint(u32),
+    ChangedMovementEnhancements,
     ChangedReaction,
 }
 
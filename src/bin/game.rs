@@ -28,6 +28,7 @@ use rpg::map_data::{make_high_alice, make_high_bob, make_low_level_party, make_m
 use rpg::map_scene::{MapChoice, MapScene};
 use rpg::resources::{init_core_game, GameResources, UiResources};
 use rpg::rest_scene::run_rest_loop;
+use rpg::save_file::{load_party, save_party};
 use rpg::shop_scene::run_shop_loop;
 use rpg::sounds::SoundPlayer;
 use rpg::textures::{
@@ -80,9 +81,10 @@ async fn main() {
             .run_map_loop(resources.simple_font.clone(), &player_characters[..])
             .await;
         match map_choice {
-            MapChoice::Rest => {
+            MapChoice::Rest(rest_type) => {
                 player_characters = run_rest_loop(
                     player_characters,
+                    *rest_type,
                     resources.simple_font.clone(),
                     &ui_resources.equipment_icons,
                     ui_resources.icons.clone(),
@@ -138,13 +140,26 @@ async fn main() {
      */
 }
 
+const SAVE_FILE_PATH: &str = "savegame.json";
+
+fn new_or_saved_party() -> (Rc<Party>, Vec<Character>) {
+    // The party is saved after every won fight, and can be picked up again with --continue
+    if std::env::args().any(|arg| arg == "--continue") {
+        match load_party(SAVE_FILE_PATH) {
+            Ok(saved) => return saved,
+            Err(e) => println!("Starting with a new party. {}", e),
+        }
+    }
+    make_low_level_party()
+}
+
 async fn run_demo(
     resources: &GameResources,
     ui_resources: &UiResources,
     sound_player: SoundPlayer,
 ) {
     loop {
-        let (party, player_characters) = make_low_level_party();
+        let (party, player_characters) = new_or_saved_party();
         let mut player_characters: Vec<Rc<Character>> = player_characters
             .into_iter()
             .map(|ch| Rc::new(ch))
@@ -242,6 +257,10 @@ async fn run_demo(
                 sound_player.clone(),
             )
             .await;
+
+            if let Err(e) = save_party(SAVE_FILE_PATH, &player_characters, &party) {
+                println!("{}", e);
+            }
         }
     }
 }
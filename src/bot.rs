@@ -2,17 +2,17 @@ use core::f32;
 use std::{cell::Cell, iter, rc::Rc};
 
 use macroquad::rand::ChooseRandom;
-use rand::{random_bool, random_range, Rng};
+use rand::{random_bool, Rng};
 
 use crate::{
     core::{
-        distance_between, sq_distance_between, Ability, AbilityId, AbilityTarget, Action,
-        ActionReach, ActionTarget, AttackEnhancement, BaseAction, Character, CharacterId,
+        distance_between, predict_attack, sq_distance_between, Ability, AbilityId, AbilityTarget,
+        Action, ActionReach, ActionTarget, AttackEnhancement, BaseAction, Character, CharacterId,
         Condition, CoreGame, HandType, OnAttackedReaction, OnHitReaction, Position, Range,
-        CENTER_MELEE_RANGE_SQUARED,
+        CENTER_MELEE_RANGE_SQUARED, COUNTERSPELL_MANA_COST,
     },
-    data::{HULDRA_HEAL, HULDRA_INFLICT_HORRORS, HULDRA_INFLICT_WOUNDS, INFLICT_WOUNDS},
-    pathfind::{Occupation, Path, PathfindGrid},
+    data::{HULDRA_HEAL, HULDRA_INFLICT_HORRORS, HULDRA_INFLICT_WOUNDS, INFLICT_WOUNDS, STAND_UP},
+    pathfind::{build_path_from_chart, Occupation, Path, PathfindGrid},
     util::{adjacent_cells, are_entities_within_melee, line_visitor, CustomShuffle},
 };
 
@@ -41,7 +41,9 @@ impl HuldraBehaviour {
             .enemies()
             .any(|char| char.health.current() < char.health.max() - 5);
 
-        let are_all_players_bleeding = game.player_characters().all(|ch| ch.is_bleeding());
+        let are_all_players_bleeding = visible_player_characters(game, bot)
+            .iter()
+            .all(|ch| ch.is_bleeding());
 
         let heal = BotAction::SingleFriendlyTarget(HULDRA_HEAL);
         let inflict_wounds = BotAction::SingleEnemyTarget(HULDRA_INFLICT_WOUNDS);
@@ -68,10 +70,11 @@ impl HuldraBehaviour {
             && !are_all_players_bleeding
             && rng.random_bool(0.8)
         {
-            let mut non_bleeding_player_chars: Vec<&Rc<Character>> = game
-                .player_characters()
-                .filter(|ch| !ch.is_bleeding())
-                .collect();
+            let mut non_bleeding_player_chars: Vec<&Rc<Character>> =
+                visible_player_characters(game, bot)
+                    .into_iter()
+                    .filter(|ch| !ch.is_bleeding())
+                    .collect();
 
             non_bleeding_player_chars.sort_by_key(|ch| {
                 let range = HULDRA_INFLICT_WOUNDS.target.range(&[]).unwrap();
@@ -89,7 +92,7 @@ impl HuldraBehaviour {
 
             action = (inflict_wounds, Some(Rc::clone(&target)));
         } else {
-            let player_chars: Vec<&Rc<Character>> = game.player_characters().collect();
+            let player_chars = visible_player_characters(game, bot);
             let target = player_chars[rng.random_range(0..player_chars.len())];
 
             dbg!("NEW Huldra HORROR GOAL: {:?}", target.id());
@@ -133,9 +136,32 @@ pub struct GenericBehaviour {
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct FighterBehaviour {
     target_selection: EnemyTargetSelection,
+    // Cowardly fighters try to get away from the player characters once they're near death
+    flees_when_near_death: bool,
 }
 
 impl FighterBehaviour {
+    pub fn cowardly() -> Self {
+        Self {
+            flees_when_near_death: true,
+            ..Default::default()
+        }
+    }
+
+    fn run(&self, game: &CoreGame) -> Option<Action> {
+        let bot = game.active_character();
+        if self.flees_when_near_death && bot.conditions.borrow().has(&Condition::NearDeath) {
+            match flee(game, bot) {
+                Flight::Move(action) => return Some(action),
+                // Nowhere to run, so it might as well fight
+                Flight::Cornered => {}
+                Flight::OutOfMovement => return None,
+            }
+        }
+
+        pursue_goal(game, self.get_goal(game))
+    }
+
     fn get_goal(&self, game: &CoreGame) -> BotGoal {
         let (player_chars, target_id) = self.target_selection.run(game);
         let bot = game.characters.get_rc(game.active_character_id);
@@ -163,96 +189,145 @@ impl FighterBehaviour {
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct EnemyTargetSelection {
     current_target: Cell<Option<CharacterId>>,
-    chance_of_switching_target: Cell<f32>,
 }
 
+// Score bonus for the current target, so that the bot doesn't switch back and forth between
+// targets over small differences
+const TARGET_STICKINESS: f32 = 2.0;
+
+const DOWNED_TARGET_SCORE: f32 = -10.0;
+
 impl EnemyTargetSelection {
     fn run<'a>(&self, game: &'a CoreGame) -> (Vec<&'a Rc<Character>>, CharacterId) {
-        let bot = game.active_character();
-
-        let mut player_chars: Vec<&Rc<Character>> = game.player_characters().collect();
+        let bot = game.characters.get_rc(game.active_character_id);
 
-        if let Some(target_id) = self.current_target.get() {
-            if !player_chars.iter().any(|ch| ch.id() == target_id) {
-                // Player char must have died. Force a target switch.
-                self.current_target.set(None);
+        let player_chars = visible_player_characters(game, bot);
+
+        // Pick the most threatening target among those that can be reached
+        let mut best: Option<(CharacterId, f32)> = None;
+        for ch in &player_chars {
+            let Some(path) = find_path_to_attack_target(game, bot, ch) else {
+                continue;
+            };
+            let mut score = threat_score(game, bot, ch, path.total_distance);
+            if self.current_target.get() == Some(ch.id()) {
+                score += TARGET_STICKINESS;
+            }
+            if best.is_none_or(|(_, best_score)| score > best_score) {
+                best = Some((ch.id(), score));
             }
         }
 
-        if random_bool(0.5) {
-            println!("Sort player chars by proximity");
-            player_chars.sort_by_key(|ch| {
-                let distance_to = find_path_to_attack_target(game, bot, ch)
-                    .map(|p| p.total_distance)
-                    .unwrap_or(f32::MAX);
+        // TODO: this panics if all player chars have died
+        let mut target_id = best
+            .map(|(id, _score)| id)
+            .unwrap_or_else(|| player_chars[0].id());
 
-                // convert from f32 to make it sortable
-                (distance_to * 10.0) as u32
-            });
-        } else {
-            println!("Shuffle player chars");
-            CustomShuffle::shuffle(&mut player_chars);
+        if let Some(taunter) = bot.taunter() {
+            if player_chars.iter().any(|ch| ch.id() == taunter) {
+                println!("bot is taunted by {}", taunter);
+                target_id = taunter;
+            }
         }
 
-        if self.current_target.get().is_none() {
-            // TODO: this panics if all player chars have died
-            self.current_target.set(Some(player_chars[0].id()));
-        }
+        self.current_target.set(Some(target_id));
 
-        let mut target_id = self.current_target.get().unwrap();
-
-        let chance_switch_target = self.chance_of_switching_target.get();
-        dbg!(chance_switch_target);
-        let should_switch_target = random_range(0.0..1.0) < chance_switch_target;
-        dbg!(should_switch_target);
-        if should_switch_target {
-            self.chance_of_switching_target.set(0.0);
-        } else if self.chance_of_switching_target.get() == 0.0 {
-            // Make it very rare to switch target immediately after acquiring it
-            self.chance_of_switching_target.set(0.01);
-        } else {
-            // After that, the chance increases steadily
-            self.chance_of_switching_target
-                .set(chance_switch_target + 0.1);
-        }
+        (player_chars, target_id)
+    }
+}
 
-        if should_switch_target {
-            println!("bot should try switching target");
-            if let Some(new_target) = player_chars.iter().find(|ch| ch.id() != target_id) {
-                println!("switching to new target?: {}", new_target.id());
+/// How worthwhile it is for the bot to go after the target. Considers how much damage the bot is
+/// expected to deal, whether the target can be finished off, whether it's a healer, and how far
+/// away it is.
+fn threat_score(game: &CoreGame, bot: &Rc<Character>, target: &Character, distance: f32) -> f32 {
+    if target.is_downed() {
+        // Already out of the fight, so anyone still standing is more worth going after
+        return DOWNED_TARGET_SCORE - distance * 0.2;
+    }
 
-                if find_path_to_attack_target(game, bot, new_target).is_some() {
-                    println!("Yes, there's a path to it ");
-                    self.current_target.set(Some(new_target.id()));
-                    target_id = new_target.id();
-                }
-            }
-        } else {
-            println!("bot sticks with current target: {:?}", self.current_target);
+    let mut score = 0.0;
+
+    // A disarmed bot has no attack to weigh
+    if let Some(attack) = bot.attack_action() {
+        let prediction = predict_attack(&game.characters, bot, attack.hand, &[], target, None, 0);
+        score += prediction.avg_damage;
+        if prediction.max_damage >= target.health.current() {
+            // Could be finished off with a single blow
+            score += prediction.percentage_chance_deal_damage as f32 * 0.05;
         }
+    }
 
-        (player_chars, target_id)
+    score += (1.0 - target.health.ratio()) * 5.0;
+
+    if target.knows_ability(AbilityId::Heal) {
+        score += 3.0;
     }
+
+    score - distance * 0.2
 }
 
 const EXPLORATION_RANGE: f32 = 60.0;
 
+/// Hidden player characters can only be noticed from up close
+fn visible_player_characters<'a>(game: &'a CoreGame, bot: &Character) -> Vec<&'a Rc<Character>> {
+    game.player_characters()
+        .filter(|ch| !ch.is_hidden() || are_entities_within_melee(bot.pos(), ch.pos()))
+        .collect()
+}
+
 pub fn bot_choose_action(game: &CoreGame) -> Option<Action> {
     let character = game.active_character();
     assert!(!character.player_controlled());
 
     dbg!("BOT CHOOSING ACTION ...");
 
+    if character.conditions.borrow().has(&Condition::Confused) {
+        return confused_action(game);
+    }
+
+    // Whatever the plan is, it starts with getting back on its feet
+    if character.can_use_ability(STAND_UP) {
+        return Some(Action::UseAbility {
+            ability: STAND_UP,
+            enhancements: vec![],
+            target: ActionTarget::None,
+        });
+    }
+
+    if visible_player_characters(game, character).is_empty() {
+        println!("Bot can't see any player characters");
+        return None;
+    }
+
+    if character.conditions.borrow().has(&Condition::Panicked) {
+        match flee(game, character) {
+            Flight::Move(action) => return Some(action),
+            // Nowhere to run, so it might as well fight
+            Flight::Cornered => {}
+            Flight::OutOfMovement => return guard_or_end_turn(character),
+        }
+    }
+
     let result = match character.kind.unwrap_bot_behaviour() {
         BotBehaviour::Normal => run_normal_behaviour(game),
         BotBehaviour::Huldra(huldra) => huldra.run(game),
-        BotBehaviour::Fighter(fighter) => pursue_goal(game, fighter.get_goal(game)),
+        BotBehaviour::Fighter(fighter) => fighter.run(game),
     };
+    // Any movement the behaviour came up with is off the table while rooted or prone
+    let result = result
+        .filter(|action| !(character.is_immobilized() && matches!(action, Action::Move { .. })));
+    let result = result.or_else(|| guard_or_end_turn(character));
     println!("Bot chose: {:?}", result);
 
     result
 }
 
+/// Rather than letting its remaining AP go to waste, the bot guards
+fn guard_or_end_turn(bot: &Character) -> Option<Action> {
+    bot.can_use_action(BaseAction::GuardStance)
+        .then_some(Action::GuardStance)
+}
+
 #[derive(Clone)]
 struct BotGoal {
     action: (BotAction, Option<Rc<Character>>),
@@ -289,14 +364,21 @@ fn pursue_goal(game: &CoreGame, goal: BotGoal) -> Option<Action> {
     match goal.action {
         (BotAction::Attack, goal_target) => {
             let goal_target = goal_target.as_ref().unwrap();
-            if bot.can_attack(bot.attack_action().unwrap())
-                && attack_reaches(bot, goal_target, &game.pathfind_grid)
-            {
-                println!("bot attacks target");
-                return Some(attack_action(bot, goal_target));
+            match bot.attack_action() {
+                Some(attack) => {
+                    if bot.can_attack(attack)
+                        && attack_reaches(bot, goal_target, &game.pathfind_grid)
+                    {
+                        println!("bot attacks target");
+                        return Some(attack_action(bot, goal_target));
+                    }
+                    path_to_goal = find_path_to_attack_target(game, bot, &goal_target);
+                }
+                None => {
+                    println!("bot has no weapon to attack with");
+                    path_to_goal = None;
+                }
             }
-            let weapon_range = bot.attack_weapon_range().unwrap().into_range();
-            path_to_goal = find_path(game, bot, &goal_target, weapon_range);
         }
         (BotAction::SingleEnemyTarget(ability), goal_target) => {
             let goal_target = goal_target.as_ref().unwrap();
@@ -357,7 +439,7 @@ fn pursue_goal(game: &CoreGame, goal: BotGoal) -> Option<Action> {
         println!("bot's goal didn't involve movement");
     }
 
-    let mut player_chars: Vec<&Rc<Character>> = game.player_characters().collect();
+    let mut player_chars = visible_player_characters(game, bot);
     player_chars.shuffle();
 
     let mut bot_chars: Vec<&Rc<Character>> = game.enemies().collect();
@@ -366,7 +448,10 @@ fn pursue_goal(game: &CoreGame, goal: BotGoal) -> Option<Action> {
     for action in goal.fallback_actions {
         match action {
             BotAction::Attack => {
-                if bot.can_attack(bot.attack_action().unwrap()) {
+                if bot
+                    .attack_action()
+                    .is_some_and(|attack| bot.can_attack(attack))
+                {
                     for player_char in &player_chars {
                         if attack_reaches(bot, player_char, &game.pathfind_grid) {
                             println!("bot attacks someone before moving to target");
@@ -449,8 +534,7 @@ fn find_path_to_attack_target(
     bot: &Character,
     target: &&Rc<Character>,
 ) -> Option<Path> {
-    let attack = bot.attack_action().unwrap();
-    let weapon_range = bot.weapon(attack.hand).unwrap().range;
+    let weapon_range = bot.attack_weapon_range()?;
 
     find_path(game, bot, target, weapon_range.into_range())
 }
@@ -509,21 +593,50 @@ impl std::fmt::Debug for BotAction {
 }
 
 fn attack_action(bot: &Character, target: &Character) -> Action {
+    // A disarmed bot may be left with only its off-hand weapon
+    let hand = bot
+        .attack_action()
+        .map(|attack| attack.hand)
+        .unwrap_or(HandType::MainHand);
     let mut enhancements = vec![];
 
     if random_bool(0.5) {
         if let Some(e) = bot.known_attack_enhancements.borrow().first() {
-            enhancements.push(*e);
+            if bot.can_use_attack_enhancement(hand, e) {
+                enhancements.push(*e);
+            }
         }
     }
 
     Action::Attack {
-        hand: HandType::MainHand,
+        hand,
         enhancements,
         target: target.id(),
     }
 }
 
+/// A confused bot can't tell friend from foe, and lashes out at a random character within reach
+fn confused_action(game: &CoreGame) -> Option<Action> {
+    let bot = game.active_character();
+    if !bot.can_attack(bot.attack_action()?) {
+        return None;
+    }
+
+    let candidates: Vec<&Rc<Character>> = game
+        .characters
+        .iter()
+        .filter(|other| {
+            other.id() != bot.id()
+                && !other.is_dead()
+                && attack_reaches(bot, other, &game.pathfind_grid)
+        })
+        .collect();
+
+    let target = ChooseRandom::choose(&candidates[..])?;
+    println!("Confused bot attacks {} #{}", target.name, target.id());
+    Some(attack_action(bot, target))
+}
+
 fn simple_targetted_ability_action(ability: Ability, target: &Character) -> Action {
     Action::UseAbility {
         ability,
@@ -534,14 +647,17 @@ fn simple_targetted_ability_action(ability: Ability, target: &Character) -> Acti
 
 fn attack_reaches(bot: &Character, target: &Character, pathfind_grid: &PathfindGrid) -> bool {
     println!("bot::attack_reaches()...");
+    let Some(attack) = bot.attack_action() else {
+        return false;
+    };
     let action_reach = bot
-        .reaches_with_attack(HandType::MainHand, target.pos(), iter::empty())
+        .reaches_with_attack(attack.hand, target.pos(), iter::empty())
         .1;
     if action_reach == ActionReach::No {
         return false;
     }
 
-    !pathfind_grid.obstructed_line_of_sight(bot.pos(), target.pos())
+    !pathfind_grid.obstructed_line_of_sight_for(Some(bot.id()), bot.pos(), target.pos())
 }
 
 fn run_normal_behaviour(game: &CoreGame) -> Option<Action> {
@@ -555,7 +671,7 @@ fn run_normal_behaviour(game: &CoreGame) -> Option<Action> {
         .map(|weapon| !weapon.is_melee())
         .unwrap_or(false);
 
-    let mut player_chars: Vec<&Rc<Character>> = game.player_characters().collect();
+    let mut player_chars = visible_player_characters(game, bot);
 
     let bot_pos = bot.position.get();
 
@@ -588,9 +704,13 @@ fn run_normal_behaviour(game: &CoreGame) -> Option<Action> {
     }
 
     if let Some(attack) = bot.attack_action() {
-        attack_range = Some(bot.weapon(attack.hand).unwrap().range);
+        attack_range = bot.attack_weapon_range();
         CustomShuffle::shuffle(&mut player_chars);
-        for player_char in player_chars {
+        if let Some(taunter) = bot.taunter() {
+            // Go for the taunter if at all possible
+            player_chars.sort_by_key(|ch| ch.id() != taunter);
+        }
+        for player_char in &player_chars {
             if attack_reaches(bot, player_char, &game.pathfind_grid) {
                 if bot.can_attack(attack) {
                     return Some(attack_action(bot, player_char));
@@ -604,7 +724,8 @@ fn run_normal_behaviour(game: &CoreGame) -> Option<Action> {
 
     let mut shortest_path_to_some_player: Option<Path> = None;
 
-    for player_pos in &game.player_positions() {
+    let player_positions: Vec<Position> = player_chars.iter().map(|ch| ch.pos()).collect();
+    for player_pos in &player_positions {
         let maybe_path = if let Some(range) = attack_range {
             game.pathfind_grid.find_shortest_path_to_proximity(
                 bot.id(),
@@ -642,7 +763,64 @@ fn run_normal_behaviour(game: &CoreGame) -> Option<Action> {
     None
 }
 
+enum Flight {
+    Move(Action),
+    Cornered,
+    OutOfMovement,
+}
+
+/// Move as far away from the player characters as the bot's remaining movement allows. Opportunity
+/// attacks are accepted as the price of getting away.
+fn flee(game: &CoreGame, bot: &Character) -> Flight {
+    if bot.is_immobilized() {
+        // Rooted or prone, so there's no getting away this turn
+        return Flight::OutOfMovement;
+    }
+
+    let player_chars = visible_player_characters(game, bot);
+    let closest_player_sq_distance = |pos: Position| {
+        player_chars
+            .iter()
+            .map(|ch| sq_distance_between(ch.pos(), pos))
+            .fold(f32::MAX, f32::min)
+    };
+
+    let bot_pos = bot.pos();
+    let current = closest_player_sq_distance(bot_pos);
+
+    let chart =
+        game.pathfind_grid
+            .explore_outward(bot.id(), bot_pos, bot.remaining_movement.get(), None);
+    let best = chart
+        .keys()
+        .copied()
+        .filter(|pos| *pos != bot_pos && game.pathfind_grid.is_free(Some(bot.id()), *pos))
+        .map(|pos| (pos, closest_player_sq_distance(pos)))
+        .filter(|(_pos, sq_distance)| *sq_distance > current)
+        .max_by(|a, b| a.1.total_cmp(&b.1));
+
+    if let Some((destination, _)) = best {
+        let path = build_path_from_chart(&chart, bot_pos, destination);
+        println!("Bot flees to {:?}: {:?}", destination, path);
+        if let Some(action) = convert_path_to_move_action(bot, path) {
+            return Flight::Move(action);
+        }
+    }
+
+    let is_engaged = player_chars
+        .iter()
+        .any(|ch| are_entities_within_melee(bot_pos, ch.pos()));
+    if is_engaged || bot.remaining_movement.get() > 0.0 {
+        Flight::Cornered
+    } else {
+        Flight::OutOfMovement
+    }
+}
+
 pub fn convert_path_to_move_action(character: &Character, path: Path) -> Option<Action> {
+    if character.is_immobilized() {
+        return None;
+    }
     let remaining_free_movement = character.remaining_movement.get();
     dbg!(remaining_free_movement);
     //let max_sprint_usage = character.stamina.current();
@@ -662,6 +840,7 @@ pub fn convert_path_to_move_action(character: &Character, path: Path) -> Option<
             total_distance,
             positions,
             extra_cost,
+            enhancements: vec![],
         })
     } else {
         println!("---");
@@ -700,6 +879,23 @@ pub fn bot_choose_attack_reaction(
     None
 }
 
+pub fn bot_choose_intercept(
+    game: &CoreGame,
+    reactor_id: CharacterId,
+    victim_id: CharacterId,
+) -> bool {
+    let reactor = game.characters.get(reactor_id);
+    let victim = game.characters.get(victim_id);
+    // Only take the hit for an ally that is worse off
+    reactor.health.ratio() > victim.health.ratio()
+}
+
+pub fn bot_choose_counterspell(game: &CoreGame, reactor_id: CharacterId, ability: Ability) -> bool {
+    let reactor = game.characters.get(reactor_id);
+    // Don't gamble the mana on cheap spells
+    ability.mana_cost >= COUNTERSPELL_MANA_COST || reactor.mana.ratio() > 0.5
+}
+
 pub fn bot_choose_hit_reaction(
     game: &CoreGame,
     reactor_id: CharacterId,
@@ -718,3 +914,111 @@ pub fn bot_choose_hit_reaction(
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+    use crate::core::{Attributes, Bot, CharacterKind, Party, PlayerId};
+    use crate::d20::FixedDice;
+    use crate::data::{HEAL, WAR_HAMMER};
+    use crate::game_ui_connection::GameUserInterfaceConnection;
+    use crate::init_fight_map::GameInitState;
+    use crate::textures::{PortraitId, SpriteId};
+
+    fn make_bot() -> Rc<Character> {
+        let bot = Character::new(
+            CharacterKind::Bot(Bot {
+                behaviour: BotBehaviour::Fighter(Default::default()),
+                base_movement: 12.0,
+                morale: None,
+            }),
+            "Enslaved",
+            PortraitId::Ghoul,
+            SpriteId::Skeleton2,
+            Attributes::new(5, 5, 2, 1),
+            (5, 10),
+        );
+        bot.set_weapon(HandType::MainHand, WAR_HAMMER);
+        bot.set_id(0);
+        Rc::new(bot)
+    }
+
+    fn make_player(id: CharacterId, pos: Position) -> Rc<Character> {
+        let party = Rc::new(Party {
+            money: Cell::new(0),
+            stash: Default::default(),
+        });
+        let character = Character::new(
+            CharacterKind::Player(party, PlayerId::Bob),
+            "Bob",
+            PortraitId::Bob,
+            SpriteId::Bob,
+            Attributes::new(3, 3, 3, 3),
+            pos,
+        );
+        character.set_id(id);
+        Rc::new(character)
+    }
+
+    fn make_game(characters: Vec<Rc<Character>>) -> CoreGame {
+        let pathfind_grid = Rc::new(PathfindGrid::new((30, 30)));
+        for character in &characters {
+            pathfind_grid
+                .set_occupied(character.pos(), Some(Occupation::Character(character.id())));
+        }
+        let init_state = GameInitState {
+            characters,
+            active_character_id: 0,
+            pathfind_grid,
+            background: Default::default(),
+            terrain_objects: Default::default(),
+            decorations: Default::default(),
+        };
+        CoreGame::new(
+            GameUserInterfaceConnection::uninitialized(),
+            &init_state,
+            Box::new(FixedDice::expected()),
+        )
+    }
+
+    fn chosen_target(game: &CoreGame) -> CharacterId {
+        EnemyTargetSelection::default().run(game).1
+    }
+
+    #[test]
+    fn bot_goes_after_a_wounded_target_rather_than_the_closest_one() {
+        let closest = make_player(1, (9, 10));
+        let wounded = make_player(2, (17, 10));
+        wounded.health.lose(wounded.health.max() - 2);
+        let game = make_game(vec![make_bot(), closest, wounded]);
+
+        assert_eq!(2, chosen_target(&game));
+    }
+
+    #[test]
+    fn bot_goes_after_a_healer_rather_than_the_closest_target() {
+        let closest = make_player(1, (9, 10));
+        let healer = make_player(2, (17, 10));
+        healer.learn_ability(HEAL);
+        let game = make_game(vec![make_bot(), closest, healer]);
+
+        assert_eq!(2, chosen_target(&game));
+    }
+
+    #[test]
+    fn bot_leaves_downed_targets_alone_while_others_are_standing() {
+        let downed = make_player(1, (9, 10));
+        let standing = make_player(2, (17, 10));
+        downed.health.lose(downed.health.max());
+        downed
+            .conditions
+            .borrow_mut()
+            .set_stacks(Condition::Downed, 3);
+        assert!(downed.is_downed());
+        let game = make_game(vec![make_bot(), downed, standing]);
+
+        assert_eq!(2, chosen_target(&game));
+    }
+}
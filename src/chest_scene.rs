@@ -18,8 +18,8 @@ use crate::{
     base_ui::{draw_text_rounded, Drawable},
     core::{ArrowStack, Character, EquipmentEntry},
     data::{
-        ADRENALIN_POTION, ARCANE_POTION, BARBED_ARROWS, CHAIN_MAIL, ENERGY_POTION, MEDIUM_SHIELD,
-        PENETRATING_ARROWS,
+        ADRENALIN_POTION, ALCHEMISTS_FIRE, ARCANE_POTION, BARBED_ARROWS, CHAIN_MAIL, ENERGY_POTION,
+        MEDIUM_SHIELD, PENETRATING_ARROWS,
     },
     equipment_ui::equipment_tooltip,
     non_combat_ui::NonCombatPartyUi,
@@ -219,6 +219,7 @@ pub fn generate_chest_content() -> Vec<ChestEntry> {
         EquipmentEntry::Consumable(ARCANE_POTION),
         EquipmentEntry::Consumable(ENERGY_POTION),
         EquipmentEntry::Consumable(ADRENALIN_POTION),
+        EquipmentEntry::Consumable(ALCHEMISTS_FIRE),
     ];
     let mut rng = rand::rng();
     vec![ChestEntry {
@@ -10,13 +10,16 @@ use indexmap::IndexMap;
 use macroquad::color::Color;
 
 use crate::bot::BotBehaviour;
-use crate::d20::{probability_of_d20_reaching, roll_d20_with_advantage, DiceRollBonus};
+use crate::d20::{
+    outcome_distribution, probability_of_d20_reaching, roll_d20_with_advantage_using,
+    DiceRollBonus, DiceRoller,
+};
 
-use crate::data::PassiveSkill;
+use crate::data::{PassiveSkill, SPRINT, STAND_UP};
 use crate::game_ui_connection::{ActionOrSwitchTo, GameUserInterfaceConnection, QuitEvent};
 use crate::grid::ParticleShape;
 use crate::init_fight_map::GameInitState;
-use crate::pathfind::{Collision, Occupation, PathfindGrid};
+use crate::pathfind::{Collision, Occupation, Path, PathfindGrid};
 use crate::sounds::SoundId;
 use crate::textures::{EquipmentIconId, IconId, PortraitId, SpriteId, StatusId};
 use crate::util::{are_entities_within_melee, line_visitor, CustomShuffle};
@@ -27,6 +30,21 @@ pub const MAX_ACTION_POINTS: u32 = 6;
 pub const ACTION_POINTS_PER_TURN: u32 = 4;
 
 pub const MOVE_DISTANCE_PER_STAMINA: u32 = 4;
+// Every attack or ability after the first one in a turn costs this much extra stamina
+pub const FATIGUE_STAMINA_COST: u32 = 1;
+
+// The median of a d20 (rounded down)
+const DRY_RUN_DICE_ROLL: u32 = 10;
+
+pub const EXPERIENCE_PER_KILL: u32 = 10;
+// Rounds that a downed party member can hold on before dying
+pub const DOWNED_ROUNDS: u32 = 3;
+// The share of max health that a revived character comes back with
+const REVIVE_HEALTH_DIVISOR: u32 = 4;
+
+const SECOND_WIND_MISSING_HEALTH_DIVISOR: u32 = 3;
+// Multiplied by the current level, to get the experience needed for the next level
+pub const EXPERIENCE_PER_LEVEL: u32 = 20;
 
 #[derive(Debug)]
 enum ActionOutcome {
@@ -42,33 +60,82 @@ pub struct CoreGame {
     pub pathfind_grid: Rc<PathfindGrid>,
     round_index: u32,
     round_length: u32,
+    // Used for summoned characters
+    next_character_id: CharacterId,
+    dice: Box<dyn DiceRoller>,
+    // Loot that didn't fit in the party stash; picked up by walking over it
+    ground_loot: RefCell<HashMap<Position, Vec<EquipmentEntry>>>,
+    // Lingering effects on the battlefield, e.g. from a wall of fire
+    ground_effects: RefCell<HashMap<Position, GroundEffect>>,
+    // Hidden from the opposing side (and not known to bot pathfinding). Several may share a cell.
+    traps: RefCell<Vec<Trap>>,
+    // Used to tell how decimated the enemy side is, for morale checks
+    enemy_starting_count: u32,
+    // How many of MORALE_THRESHOLDS the enemy side has dropped below
+    morale_thresholds_passed: u32,
 }
 
 impl CoreGame {
-    pub fn new(user_interface: GameUserInterfaceConnection, init_state: &GameInitState) -> Self {
+    pub fn new(
+        user_interface: GameUserInterfaceConnection,
+        init_state: &GameInitState,
+        dice: Box<dyn DiceRoller>,
+    ) -> Self {
+        for character in &init_state.characters {
+            character
+                .initiative
+                .set(character.agility() + dice.roll_d20());
+            character.set_terrain(&init_state.pathfind_grid);
+        }
         let characters = Characters::new(init_state.characters.clone());
 
         let round_length = characters.iter().count() as u32;
+        let enemy_starting_count = characters
+            .iter()
+            .filter(|ch| !ch.player_controlled())
+            .count() as u32;
+        let next_character_id = characters.iter().map(|ch| ch.id()).max().unwrap_or(0) + 1;
+        let active_character_id = characters
+            .iter()
+            .next()
+            .map(|ch| ch.id())
+            .unwrap_or(init_state.active_character_id);
         Self {
             characters,
-            active_character_id: init_state.active_character_id,
+            active_character_id,
             user_interface,
             pathfind_grid: init_state.pathfind_grid.clone(),
             round_index: 0,
             round_length,
+            next_character_id,
+            dice,
+            ground_loot: Default::default(),
+            ground_effects: Default::default(),
+            traps: Default::default(),
+            enemy_starting_count,
+            morale_thresholds_passed: 0,
         }
     }
 
+    fn roll_d20_with_advantage(&self, advantage_level: i32) -> u32 {
+        roll_d20_with_advantage_using(self.dice.as_ref(), advantage_level)
+    }
+
     pub async fn run(mut self) -> Result<(), QuitEvent> {
         self.log("The battle begins").await;
+        let initiative_line = self
+            .characters
+            .iter()
+            .map(|ch| format!("|{}| ({})", ch.name, ch.initiative()))
+            .collect::<Vec<_>>()
+            .join(", ");
+        self.log(format!("Initiative: {}", initiative_line)).await;
         self.log("Round 1").await;
 
         for character in self.characters.iter() {
             character.on_battle_start();
         }
-        for player_char in self.player_characters() {
-            player_char.is_part_of_active_group.set(true);
-        }
+        self.start_active_group();
         self.on_character_positions_changed();
 
         loop {
@@ -85,15 +152,82 @@ impl CoreGame {
 
             if enemy_count == 0 {
                 println!("No enemies remaining. Exiting game loop");
+                self.end_fight_cleanup().await;
                 self.ui_handle_event(GameEvent::GameOver("Victory")).await;
                 return Ok(());
             }
-            if enemy_count == self.characters.0.len() {
+            if self
+                .player_characters()
+                .all(|ch| ch.is_summon() || ch.is_downed())
+            {
                 self.ui_handle_event(GameEvent::GameOver("Defeat")).await;
                 return Ok(());
             }
 
-            let action_or_character_change = self.user_interface.select_action(&self).await?;
+            let is_frozen = self
+                .active_character()
+                .conditions
+                .borrow()
+                .has(&Condition::Frozen);
+            if is_frozen {
+                // The frozen character still takes its turn (for round-completion purposes), but
+                // doesn't get to act
+                let character = self.active_character();
+                {
+                    let mut conditions = character.conditions.borrow_mut();
+                    if conditions
+                        .get(&Condition::Frozen)
+                        .is_some_and(|state| state.stacks.is_some())
+                    {
+                        conditions.lose_stacks(&Condition::Frozen, 1);
+                    } else {
+                        conditions.remove(&Condition::Frozen);
+                    }
+                }
+                self.log(format!(
+                    "|{}| is frozen and skips their turn",
+                    character.name
+                ))
+                .await;
+            }
+
+            // Bots lash out at random characters while confused (see bot.rs), but the player
+            // shouldn't be asked to make that choice on behalf of a character that can't think
+            // straight
+            let is_confused_player = self.active_character().player_controlled()
+                && self.active_character().has_condition(&Condition::Confused);
+            if is_confused_player {
+                self.log(format!(
+                    "|{}| is confused and stumbles around aimlessly",
+                    self.active_character().name
+                ))
+                .await;
+            }
+
+            let is_petrified = self.active_character().has_condition(&Condition::Petrified);
+            if is_petrified {
+                self.log(format!(
+                    "|{}| is petrified and skips their turn",
+                    self.active_character().name
+                ))
+                .await;
+            }
+
+            let is_downed = self.active_character().is_downed();
+            if is_downed {
+                self.log(format!(
+                    "|{}| is downed and skips their turn",
+                    self.active_character().name
+                ))
+                .await;
+            }
+
+            let action_or_character_change =
+                if is_frozen || is_petrified || is_confused_player || is_downed {
+                    ActionOrSwitchTo::Action(None)
+                } else {
+                    self.user_interface.select_action(&self).await?
+                };
 
             let action = match action_or_character_change {
                 ActionOrSwitchTo::Action(action) => action,
@@ -104,6 +238,11 @@ impl CoreGame {
                         .get(character_id)
                         .has_taken_a_turn_this_round
                         .get());
+                    assert!(self
+                        .characters
+                        .get(character_id)
+                        .is_part_of_active_group
+                        .get());
                     self.active_character_id = character_id;
                     self.notify_ui_of_new_active_char().await;
                     continue;
@@ -111,10 +250,12 @@ impl CoreGame {
             };
 
             let mut turn_ended = false;
+            let ends_turn = matches!(action, None | Some(Action::GuardStance));
 
             if let Some(action) = action {
                 let mut killed_by_action = HashSet::new();
                 let action_outcome = self.perform_action(action).await?;
+                self.report_depleted_arrows().await;
 
                 if let ActionOutcome::AttackHit { victim_id, damage } = action_outcome {
                     let victim = self.characters.get(victim_id);
@@ -139,7 +280,8 @@ impl CoreGame {
                             )
                             .await?
                         {
-                            self.perform_on_hit_reaction(victim_id, reaction).await;
+                            self.perform_on_hit_reaction(victim_id, reaction, damage)
+                                .await;
                         }
                     }
                 } else if let ActionOutcome::AbilityHitEnemies { victim_ids } = action_outcome {
@@ -151,6 +293,20 @@ impl CoreGame {
                     }
                 }
 
+                let num_enemies_killed = killed_by_action
+                    .iter()
+                    .map(|id| self.characters.get(*id))
+                    .filter(|victim| !victim.player_controlled() && !victim.is_summon())
+                    .count() as u32;
+                if num_enemies_killed > 0 {
+                    let amount = num_enemies_killed * EXPERIENCE_PER_KILL;
+                    for character in self.player_characters().filter(|ch| !ch.is_summon()) {
+                        character.gain_experience(amount);
+                    }
+                    self.log(format!("The party gained {} experience", amount))
+                        .await;
+                }
+
                 if !killed_by_action.is_empty() {
                     let character = self.active_character();
                     if let Some((sta, ap)) =
@@ -173,7 +329,9 @@ impl CoreGame {
                         }
                     }
                 }
-            } else {
+            }
+
+            if ends_turn {
                 let name = self.active_character().name;
                 self.log(format!("|{}| ended their turn", name)).await;
 
@@ -181,7 +339,7 @@ impl CoreGame {
                 //let prev_index_in_round = self.active_character().index_in_round.unwrap();
                 self.active_character().is_part_of_active_group.set(false);
                 self.active_character_id = self.characters.next_id();
-                self.active_character().is_part_of_active_group.set(true);
+                self.start_active_group();
                 println!("active char ended their turn");
 
                 self.notify_ui_of_new_active_char().await;
@@ -193,6 +351,57 @@ impl CoreGame {
                 if let Some((dx, dy)) = ch.is_being_pushed_in_direction.take() {
                     self.perform_character_pushed(ch, dx, dy).await?;
                 }
+                if let Some((toward, max_distance)) = ch.is_being_pulled_toward.take() {
+                    self.perform_character_pulled(ch, toward, max_distance)
+                        .await?;
+                }
+                if ch.is_losing_channel.take() {
+                    self.perform_break_channel(ch).await;
+                }
+                if ch.is_being_revealed.take() {
+                    self.perform_reveal(ch).await;
+                }
+                if ch.is_being_downed.take() {
+                    self.ui_handle_event(GameEvent::CharacterDowned { character: ch.id() })
+                        .await;
+                }
+                if ch.is_being_revived.take() {
+                    self.ui_handle_event(GameEvent::CharacterRevived { character: ch.id() })
+                        .await;
+                }
+                let dispelled = ch.dispelled_conditions.take();
+                if !dispelled.is_empty() {
+                    self.ui_handle_event(GameEvent::ConditionsDispelled {
+                        character: ch.id(),
+                        conditions: dispelled,
+                    })
+                    .await;
+                }
+                let shield_absorbed = ch.shield_absorbed.take();
+                if shield_absorbed > 0 {
+                    self.ui_handle_event(GameEvent::ShieldAbsorbedDamage {
+                        character: ch.id(),
+                        amount: shield_absorbed,
+                    })
+                    .await;
+                }
+                let shield_gained = ch.shield_gained.take();
+                if shield_gained > 0 {
+                    self.ui_handle_event(GameEvent::CharacterGainedShield {
+                        character: ch.id(),
+                        amount: shield_gained,
+                    })
+                    .await;
+                }
+            }
+
+            let summons: Vec<(CharacterId, SummonEffect)> = self
+                .characters
+                .iter()
+                .filter_map(|ch| ch.pending_summon.take().map(|summon| (ch.id(), summon)))
+                .collect();
+            for (summoner_id, summon) in summons {
+                self.perform_summon(summoner_id, summon).await;
             }
 
             // We must make sure to have a valid (alive, existing) active_character_id before handing over control
@@ -216,6 +425,9 @@ impl CoreGame {
                         ch.pos()
                     );
                     self.pathfind_grid.set_occupied(ch.pos(), None);
+                    if !ch.player_controlled() {
+                        self.perform_drop_loot(ch).await;
+                    }
                 }
             }
             let dead_character_ids = self.characters.remove_dead();
@@ -224,6 +436,7 @@ impl CoreGame {
                 for ch in self.characters.iter() {
                     ch.set_not_engaged_by(*dead_id);
                     ch.set_not_engaging(*dead_id);
+                    ch.set_not_taunted_by(*dead_id);
                 }
             }
 
@@ -242,6 +455,8 @@ impl CoreGame {
                     new_active,
                 })
                 .await;
+
+                self.perform_morale_checks().await;
             }
 
             // ... but at the same time, we don't want to lie to the UI and claim that the new turn started
@@ -266,12 +481,9 @@ impl CoreGame {
                         character.on_new_round();
                     }
 
-                    if self.active_character().player_controlled() {
-                        // Player chars can act "simultaneously"
-                        for player_char in self.player_characters() {
-                            player_char.is_part_of_active_group.set(true);
-                        }
-                    }
+                    self.start_active_group();
+
+                    self.perform_tick_ground_effects().await;
 
                     self.round_index += 1;
 
@@ -281,16 +493,68 @@ impl CoreGame {
                 let game_time = self.current_time();
 
                 for character in self.characters.iter() {
+                    let was_petrified = character.has_condition(&Condition::Petrified);
                     character.set_current_game_time(game_time);
+                    if was_petrified && !character.has_condition(&Condition::Petrified) {
+                        self.log(format!("{} is no longer Petrified", character.name))
+                            .await;
+                    }
                 }
             }
         }
     }
 
-    fn some_alive_player_chars(&self) -> bool {
-        self.characters
+    async fn perform_morale_checks(&mut self) {
+        if self.enemy_starting_count == 0 {
+            return;
+        }
+        let remaining = self
+            .characters
+            .iter()
+            .filter(|ch| !ch.player_controlled() && !ch.is_summon())
+            .count() as f32;
+        let ratio = remaining / self.enemy_starting_count as f32;
+        let passed = MORALE_THRESHOLDS
             .iter()
-            .any(|character| character.player_controlled() && !character.is_dead())
+            .filter(|threshold| ratio < **threshold)
+            .count() as u32;
+        if passed <= self.morale_thresholds_passed {
+            return;
+        }
+        self.morale_thresholds_passed = passed;
+
+        for ch in self.characters.iter() {
+            if !ch.player_controlled() && !ch.has_condition(&Condition::Panicked) {
+                self.perform_morale_check(ch).await;
+            }
+        }
+    }
+
+    /// Returns true if the character panics
+    async fn perform_morale_check(&self, character: &Character) -> bool {
+        let Some(morale) = character.morale() else {
+            return false;
+        };
+        let roll = self.roll_d20_with_advantage(0);
+        let pressure = self.morale_thresholds_passed * MORALE_PRESSURE_PER_THRESHOLD;
+        let resistance = character.will() + morale;
+        let panics = roll + pressure > resistance;
+        self.log(format!(
+            "Morale check: |{}| rolled {} +{} (losses) vs {} (|<stat>Will| +morale)",
+            character.name, roll, pressure, resistance
+        ))
+        .await;
+        if panics {
+            character.conditions.borrow_mut().add(Condition::Panicked);
+            self.log(format!("|{}| panicked!", character.name)).await;
+        }
+        panics
+    }
+
+    fn some_alive_player_chars(&self) -> bool {
+        self.characters.iter().any(|character| {
+            character.player_controlled() && !character.is_dead() && !character.is_downed()
+        })
     }
 
     async fn perform_character_pushed(
@@ -352,7 +616,6 @@ impl CoreGame {
                     remainder -= 1;
                     dmg += 1;
                 }
-                let dmg = total_collision_dmg as u32;
                 if dmg > 0 {
                     self.perform_losing_health(ch, dmg);
                     self.ui_handle_event(GameEvent::CharacterTookDamage {
@@ -368,6 +631,205 @@ impl CoreGame {
         Ok(())
     }
 
+    async fn perform_summon(&mut self, summoner_id: CharacterId, summon: SummonEffect) {
+        let summoner = Rc::clone(self.characters.get_rc(summoner_id));
+        let (x, y) = summoner.pos();
+        let free_pos = [
+            (3, 0),
+            (-3, 0),
+            (0, 3),
+            (0, -3),
+            (3, 3),
+            (3, -3),
+            (-3, 3),
+            (-3, -3),
+        ]
+        .into_iter()
+        .map(|(dx, dy)| (x + dx, y + dy))
+        .find(|pos| self.pathfind_grid.is_free(None, *pos));
+        let Some(pos) = free_pos else {
+            self.log(format!(
+                "{} had no room to summon {}",
+                summoner.name, summon.name
+            ))
+            .await;
+            return;
+        };
+
+        // The summon fights on the same side as its summoner
+        let kind = match &summoner.kind {
+            CharacterKind::Player(party, player_id) => {
+                CharacterKind::Player(Rc::clone(party), *player_id)
+            }
+            CharacterKind::Bot(..) => CharacterKind::Bot(Bot {
+                behaviour: BotBehaviour::Fighter(Default::default()),
+                base_movement: 9.0,
+                morale: None,
+            }),
+        };
+        let (str, agi, intel, spi) = summon.attributes;
+        let character = Character::new(
+            kind,
+            summon.name,
+            summon.portrait,
+            summon.sprite,
+            Attributes::new(str, agi, intel, spi),
+            pos,
+        );
+        character.health.change_max_value_to(summon.max_health);
+        if let Some(weapon) = summon.weapon {
+            character.set_weapon(HandType::MainHand, weapon);
+        }
+        character.set_id(self.next_character_id);
+        self.next_character_id += 1;
+        character.on_battle_start();
+
+        // The summon shares its summoner's slot in the round (acting right after it, starting next
+        // round), so that the round length and thereby the game time is unaffected.
+        character.has_taken_a_turn_this_round.set(true);
+        character.index_in_round.set(summoner.index_in_round.get());
+        character.round_length.set(Some(self.round_length));
+        character.set_current_game_time(self.current_time());
+        character.expires_at.set(Some(
+            self.current_time() + summon.duration_rounds * self.round_length,
+        ));
+
+        character.set_terrain(&self.pathfind_grid);
+
+        let character = Rc::new(character);
+        self.pathfind_grid
+            .set_occupied(pos, Some(Occupation::Character(character.id())));
+        self.characters
+            .insert_after(Rc::clone(&character), summoner_id);
+        self.on_character_positions_changed();
+
+        self.ui_handle_event(GameEvent::CharacterSummoned {
+            character,
+            summoner: summoner_id,
+        })
+        .await;
+    }
+
+    async fn perform_channeled_ability(&self, character_id: CharacterId) {
+        let caster = self.characters.get_rc(character_id);
+        let Some(channel) = caster.channeled_ability.get() else {
+            return;
+        };
+        if !caster.conditions.borrow().has(&Condition::Channeling) {
+            // The condition was removed by some other means
+            caster.channeled_ability.set(None);
+            return;
+        }
+        if channel.cast_at == self.current_time() {
+            return;
+        }
+        let AbilityTarget::None {
+            self_area: Some(area_effect),
+            ..
+        } = channel.ability.target
+        else {
+            unreachable!("only abilities with a self-area can be channeled")
+        };
+
+        let dice_roll = self.roll_d20_with_advantage(0);
+        let modifier = caster.spell_modifier() as i32;
+        let result = dice_roll as i32 + modifier;
+        let line = format!(
+            "Rolled: {} +{} (|<dice>| |<stat>Spell|) = |<value>{}|",
+            dice_roll, modifier, result
+        );
+        let mut detail_lines = vec![line.clone()];
+
+        let targets = Self::perform_ability_area_effect(
+            channel.ability.name,
+            AbilityRoll::RolledWithSpellModifier {
+                dice_roll,
+                result,
+                line,
+            },
+            &[],
+            caster,
+            channel.area_center,
+            area_effect,
+            &mut detail_lines,
+            ActionPerformanceMode::Real(self),
+        );
+
+        self.ui_handle_event(GameEvent::AbilityResolved(AbilityResolvedEvent {
+            actor: character_id,
+            target_outcome: None,
+            area_outcome: Some(AbilityAreaOutcome {
+                center: channel.area_center,
+                targets,
+                shape: area_effect.shape,
+            }),
+            ability: channel.ability,
+            detail_lines,
+            cast_index: 0,
+        }))
+        .await;
+    }
+
+    async fn perform_reveal(&self, character: &Character) {
+        if character.clear_condition(Condition::Hidden).is_some() {
+            self.ui_handle_event(GameEvent::CharacterRevealed {
+                character: character.id(),
+            })
+            .await;
+        }
+    }
+
+    async fn perform_break_channel(&self, character: &Character) {
+        if let Some(channel) = character.break_channel() {
+            self.ui_handle_event(GameEvent::ChannelBroken {
+                character: character.id(),
+                ability: channel.ability,
+            })
+            .await;
+        }
+    }
+
+    async fn perform_character_pulled(
+        &self,
+        character: &Character,
+        toward: CharacterId,
+        max_distance: u32,
+    ) -> Result<(), QuitEvent> {
+        let toward = self.characters.get(toward);
+        if let Some(positions) =
+            find_pull_path(&self.pathfind_grid, character, toward.pos(), max_distance)
+        {
+            self.perform_movement(character.id(), positions, MovementType::Pulled)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Player characters that are next to each other in the turn order can act "simultaneously",
+    /// i.e. in any order.
+    fn start_active_group(&self) {
+        let active_id = self.active_character_id;
+        self.active_character().is_part_of_active_group.set(true);
+        if !self.active_character().player_controlled() {
+            return;
+        }
+        for ch in self
+            .characters
+            .iter()
+            .skip_while(|ch| ch.id() != active_id)
+            .skip(1)
+            .filter(|ch| !ch.has_taken_a_turn_this_round.get())
+            .take_while(|ch| ch.player_controlled())
+        {
+            ch.is_part_of_active_group.set(true);
+        }
+    }
+
+    /// The characters, in the order that they act each round (determined by initiative)
+    pub fn turn_order(&self) -> Vec<CharacterId> {
+        self.characters.iter().map(|ch| ch.id()).collect()
+    }
+
     async fn notify_ui_of_new_active_char(&self) {
         self.ui_handle_event(GameEvent::NewActiveCharacter {
             new_active: self.active_character_id,
@@ -375,6 +837,12 @@ impl CoreGame {
         .await;
     }
 
+    /// Whether there's a clear line between the two positions, i.e. no tall terrain and no other
+    /// characters standing in the way
+    pub fn has_line_of_sight(&self, from: Position, to: Position) -> bool {
+        !self.pathfind_grid.obstructed_line_of_sight(from, to)
+    }
+
     pub fn active_character(&self) -> &Character {
         self.characters.get(self.active_character_id)
     }
@@ -412,6 +880,16 @@ impl CoreGame {
                 let attacker = self.characters.get_rc(self.active_character_id);
                 let defender = self.characters.get(target);
 
+                if attacker.has_condition(&Condition::Confused)
+                    && attacker.player_controlled() == defender.player_controlled()
+                {
+                    self.log(format!(
+                        "|{}| is confused and turns on |{}|!",
+                        attacker.name, defender.name
+                    ))
+                    .await;
+                }
+
                 assert!(
                     attacker
                         .reaches_with_attack(
@@ -422,9 +900,17 @@ impl CoreGame {
                         .1
                         != ActionReach::No
                 );
+                // Ranged (including thrown) attacks need a clear line of sight
+                assert!(
+                    attacker.weapon(hand).unwrap().is_melee()
+                        || self.has_line_of_sight(attacker.pos(), defender.pos())
+                );
 
                 let mut action_point_cost = attacker.weapon(hand).unwrap().action_point_cost as i32;
 
+                attacker.stamina.spend(attacker.fatigue_stamina_cost());
+                attacker.on_performed_fatiguing_action();
+
                 for enhancement in &enhancements {
                     action_point_cost += enhancement.action_point_cost as i32;
                     action_point_cost -= enhancement.effect.action_point_discount as i32;
@@ -444,7 +930,7 @@ impl CoreGame {
                         let unfriendly =
                             other_char.player_controlled() != attacker.player_controlled();
                         if unfriendly
-                            && within_meele(attacker.pos(), other_char.pos())
+                            && other_char.threatens(other_char.pos(), attacker.pos())
                             && target != other_char.id()
                             && other_char.can_use_opportunity_attack(attacker.id())
                         {
@@ -493,10 +979,39 @@ impl CoreGame {
                 if attacker.is_dead() {
                     Ok(ActionOutcome::Default)
                 } else {
+                    // An adjacent guardian may step in and take the attack instead
+                    let mut target = target;
+                    for ch in self.characters.iter() {
+                        let is_ally = ch.player_controlled() == defender.player_controlled();
+                        if is_ally
+                            && ch.id() != target
+                            && ch.id() != attacker.id()
+                            && ch.can_intercept(attacker.pos(), defender.pos())
+                        {
+                            let chooses_to_intercept = self
+                                .user_interface
+                                .choose_intercept(self, ch.id(), attacker.id(), target)
+                                .await?;
+                            if chooses_to_intercept {
+                                ch.action_points.spend(GUARDIAN_AP_COST);
+                                self.ui_handle_event(GameEvent::CharacterIntercepted {
+                                    protector: ch.id(),
+                                    victim: target,
+                                })
+                                .await;
+                                target = ch.id();
+                                break;
+                            }
+                        }
+                    }
+                    let defender = self.characters.get(target);
+                    let is_within_melee = within_meele(attacker.pos(), defender.pos());
+
                     // TODO: Should not be able to react when flanked?
-                    let defender_can_react_to_attack = !defender
+                    let defender_can_react_to_attack = defender
                         .usable_on_attacked_reactions(is_within_melee, true)
-                        .is_empty();
+                        .into_iter()
+                        .any(|r| defender.is_reaction_effective_against(r, attacker.pos()));
 
                     let reaction = if defender_can_react_to_attack {
                         let maybe_self_reaction = self
@@ -518,9 +1033,10 @@ impl CoreGame {
                             let is_ally = ch.player_controlled() == defender.player_controlled();
                             if is_ally
                                 && are_entities_within_melee(defender.pos(), ch.pos())
-                                && !ch
+                                && ch
                                     .usable_on_attacked_reactions(is_within_melee, false)
-                                    .is_empty()
+                                    .into_iter()
+                                    .any(|r| ch.is_reaction_effective_against(r, attacker.pos()))
                             {
                                 let r = self
                                     .user_interface
@@ -575,6 +1091,15 @@ impl CoreGame {
                     self.ui_handle_event(GameEvent::Attacked(event.clone()))
                         .await;
 
+                    if let Some((reactor_id, reaction)) = reaction {
+                        if reaction.effect.counterattack
+                            && reactor_id == target
+                            && event.outcome.hit_type == HitType::Graze
+                        {
+                            self.perform_counterattack(target, reaction).await;
+                        }
+                    }
+
                     let outcome = ActionOutcome::AttackHit {
                         victim_id: event.target,
                         damage: event.outcome.damage,
@@ -589,6 +1114,21 @@ impl CoreGame {
                 target,
             } => {
                 let caster = self.characters.get_rc(self.active_character_id);
+                caster.stamina.spend(caster.fatigue_stamina_cost());
+                caster.on_performed_fatiguing_action();
+                if ability.is_once_per_fight() {
+                    caster
+                        .abilities_used_this_fight
+                        .borrow_mut()
+                        .push(ability.id);
+                }
+                if ability.roll == Some(AbilityRollType::Spell)
+                    && self.perform_counterspell_window(&caster, ability).await?
+                {
+                    // The spell was already committed to, so its cost is lost
+                    caster.spend_ability_costs(ability, &enhancements);
+                    return Ok(ActionOutcome::Default);
+                }
                 let ability_resolved_events = Self::perform_ability(
                     caster,
                     ability,
@@ -598,10 +1138,26 @@ impl CoreGame {
                 )
                 .await;
 
+                if ability.id == AbilityId::Overwatch {
+                    // The rest of the turn's AP is set aside for the overwatch shot
+                    let reserved_ap = caster.action_points.current();
+                    caster.action_points.spend(reserved_ap);
+                    caster
+                        .conditions
+                        .borrow_mut()
+                        .set_stacks(Condition::Overwatch, reserved_ap);
+                }
+
                 let mut enemies_hit = vec![];
                 for event in ability_resolved_events {
                     event.enemies_hit(&mut enemies_hit);
                 }
+                // Area effects that hit everyone may also have hit the caster's allies
+                enemies_hit.retain(|id| {
+                    self.characters
+                        .safe_get(*id)
+                        .is_some_and(|c| c.player_controlled() != caster.player_controlled())
+                });
 
                 let outcome = if enemies_hit.is_empty() {
                     ActionOutcome::Default
@@ -617,8 +1173,19 @@ impl CoreGame {
                 extra_cost,
                 positions,
                 total_distance,
+                enhancements,
             } => {
                 let character = self.active_character();
+                if character.is_immobilized() {
+                    println!("{} can't move while immobilized", character.name);
+                    return Ok(ActionOutcome::Default);
+                }
+                // The free movement gained from enhancements is used before any stamina is spent
+                for enhancement in &enhancements {
+                    character.action_points.spend(enhancement.action_point_cost);
+                    character.stamina.spend(enhancement.stamina_cost);
+                    character.gain_movement(character.movement_enhancement_distance(enhancement));
+                }
                 //character.action_points.spend(extra_cost);
                 character.stamina.spend(extra_cost);
                 let paid_distance = (extra_cost * MOVE_DISTANCE_PER_STAMINA) as f32;
@@ -635,13 +1202,19 @@ impl CoreGame {
 
             Action::ChangeEquipment { from, to } => {
                 let character = self.active_character();
-                character.action_points.spend(1);
+                character
+                    .action_points
+                    .spend(character.change_equipment_ap_cost());
+                if character.has_free_equipment_change() {
+                    character.has_used_free_equipment_change.set(true);
+                }
                 character.swap_equipment_slots(from, to);
                 Ok(ActionOutcome::Default)
             }
 
             Action::UseConsumable {
                 inventory_equipment_index,
+                target,
             } => {
                 let character = self.active_character();
                 character.action_points.spend(1);
@@ -668,17 +1241,84 @@ impl CoreGame {
                     detail_lines.push(line);
                 }
 
+                let mut area_outcome = None;
+                if let (Some(thrown), Some(target_pos)) = (consumable.thrown, target) {
+                    assert!(within_range_squared(
+                        thrown.range.squared(),
+                        character.pos(),
+                        target_pos
+                    ));
+                    assert!(self.has_line_of_sight(character.pos(), target_pos));
+                    let dice_roll = self.roll_d20_with_advantage(0);
+                    let modifier = character.attack_modifier(HandType::MainHand) as i32;
+                    let result = dice_roll as i32 + modifier;
+                    let line = format!(
+                        "Rolled: {} +{} (|<dice>| |<stat>Attack|) = |<value>{}|",
+                        dice_roll, modifier, result
+                    );
+                    detail_lines.push(line.clone());
+
+                    let targets = Self::perform_ability_area_effect(
+                        consumable.name,
+                        AbilityRoll::RolledWithAttackModifier {
+                            dice_roll,
+                            result,
+                            line,
+                        },
+                        &[],
+                        &self.characters.get_rc(character.id()),
+                        target_pos,
+                        thrown.area_effect,
+                        &mut detail_lines,
+                        ActionPerformanceMode::Real(self),
+                    );
+                    area_outcome = Some(AbilityAreaOutcome {
+                        center: target_pos,
+                        targets,
+                        shape: thrown.area_effect.shape,
+                    });
+                }
+
+                // The item is used up, regardless of whether anyone was hit
                 character.set_equipment(None, slot_role);
 
                 self.ui_handle_event(GameEvent::ConsumableWasUsed {
                     user: character.id(),
                     consumable,
                     detail_lines,
+                    area_outcome,
                 })
                 .await;
 
                 Ok(ActionOutcome::Default)
             }
+
+            Action::DelayTurn => {
+                let name = self.active_character().name;
+                match self.characters.delay(self.active_character_id) {
+                    Some(next_id) => {
+                        self.log(format!("|{}| delayed their turn", name)).await;
+                        self.active_character().is_part_of_active_group.set(false);
+                        self.active_character_id = next_id;
+                        self.active_character().is_part_of_active_group.set(true);
+                        self.notify_ui_of_new_active_char().await;
+                    }
+                    None => {
+                        self.log(format!("|{}| is already last in the turn order", name))
+                            .await;
+                    }
+                }
+                Ok(ActionOutcome::Default)
+            }
+
+            Action::GuardStance => {
+                let character = self.active_character();
+                // The AP is converted once the turn has ended
+                character.is_entering_guard_stance.set(true);
+                self.log(format!("|{}| took a guard stance", character.name))
+                    .await;
+                Ok(ActionOutcome::Default)
+            }
         }
     }
 
@@ -686,6 +1326,21 @@ impl CoreGame {
         self.user_interface.handle_event(self, event).await
     }
 
+    /// Arrows can be spent by any kind of attack (including ability-based shots and reactions),
+    /// so rather than checking after each of them, this is done once the action has played out.
+    async fn report_depleted_arrows(&self) {
+        let depleted: Vec<CharacterId> = self
+            .characters
+            .iter()
+            .filter(|character| character.ran_out_of_arrows.take())
+            .map(|character| character.id())
+            .collect();
+        for character in depleted {
+            self.ui_handle_event(GameEvent::ArrowsDepleted { character })
+                .await;
+        }
+    }
+
     async fn perform_movement(
         &self,
         character_id: CharacterId,
@@ -694,6 +1349,11 @@ impl CoreGame {
     ) -> Result<(), QuitEvent> {
         let character = self.characters.get(character_id);
         //dbg!(("perform movement: {:?}", &positions));
+
+        self.perform_break_channel(character).await;
+        if character.clear_condition(Condition::Aiming).is_some() {
+            self.log(format!("{} lost their aim", character.name)).await;
+        }
         let start_position = positions.remove(0);
         assert!(start_position == character.pos());
         assert!(
@@ -726,12 +1386,19 @@ impl CoreGame {
 
             for other_char in self.characters.iter() {
                 let unfriendly = other_char.player_controlled() != character.player_controlled();
-                let leaving_melee = within_meele(character.pos(), other_char.pos())
-                    && !within_meele(new_position, other_char.pos());
+                let in_zone = |pos: Position| {
+                    within_meele(pos, other_char.pos())
+                        || other_char.threatens(other_char.pos(), pos)
+                        || character.threatens(pos, other_char.pos())
+                };
+                let leaving_melee = in_zone(character.pos()) && !in_zone(new_position);
 
                 if unfriendly && leaving_melee {
                     // Movement opportunity attack
-                    if movement_type == MovementType::Regular
+                    if matches!(
+                        movement_type,
+                        MovementType::Regular | MovementType::AbilityEngage
+                    ) && other_char.threatens(other_char.pos(), character.pos())
                         && other_char.can_use_opportunity_attack(character.id())
                     {
                         let reactor = other_char;
@@ -812,8 +1479,52 @@ impl CoreGame {
             })
             .await;
 
+            let was_on_ground_effect = self.ground_effect_under(character).is_some();
+
             character.set_position(new_position);
 
+            if character.player_controlled() {
+                self.perform_pick_up_loot(character).await;
+            }
+
+            if !was_on_ground_effect {
+                if let Some(effect) = self.ground_effect_under(character).and_then(|e| e.on_enter) {
+                    self.perform_ground_effect(character, effect).await;
+                }
+            }
+
+            self.perform_trigger_traps(character).await;
+            if character.is_dead() {
+                println!("Breaking out of movement loop as the mover died");
+                break;
+            }
+
+            if matches!(
+                movement_type,
+                MovementType::Regular | MovementType::AbilityEngage
+            ) {
+                self.perform_overwatch(character, prev_position).await?;
+                if character.is_dead() {
+                    println!("Breaking out of movement loop as the mover died");
+                    break;
+                }
+            }
+
+            // Having moved at least one step before this one, the mover has built up a charge
+            if step_idx > 0
+                && matches!(
+                    movement_type,
+                    MovementType::Regular | MovementType::AbilityEngage
+                )
+            {
+                self.perform_brace_against_charge(character, prev_position)
+                    .await?;
+                if character.is_dead() {
+                    println!("Breaking out of movement loop as the mover died");
+                    break;
+                }
+            }
+
             step_idx += 1;
         }
 
@@ -824,32 +1535,453 @@ impl CoreGame {
         Ok(())
     }
 
-    fn on_character_positions_changed(&self) {
-        let mut positions = vec![];
-        for character in self.characters.iter() {
-            positions.push((character.pos(), character.player_controlled()));
-        }
+    /// Relocates the character without passing the cells in between, so it doesn't provoke any
+    /// opportunity attacks (or trigger anything else along the way).
+    async fn perform_teleport(&self, character: &Character, destination: Position) {
+        self.perform_break_channel(character).await;
+        character.clear_condition(Condition::Aiming);
+
+        for other_char in self.characters.iter() {
+            character.set_not_engaging(other_char.id());
+            character.set_not_engaged_by(other_char.id());
+            other_char.set_not_engaging(character.id());
+            other_char.set_not_engaged_by(character.id());
+        }
+
+        let id = character.id();
+        let prev_position = character.pos();
+        self.pathfind_grid.set_occupied(prev_position, None);
+        self.pathfind_grid
+            .set_occupied(destination, Some(Occupation::Character(id)));
+        character.set_facing_toward(destination);
+
+        self.ui_handle_event(GameEvent::Moved {
+            character: id,
+            from: prev_position,
+            to: destination,
+            movement_type: MovementType::Teleported,
+            step_idx: 0,
+        })
+        .await;
+
+        let was_on_ground_effect = self.ground_effect_under(character).is_some();
+        character.set_position(destination);
+
+        if character.player_controlled() {
+            self.perform_pick_up_loot(character).await;
+        }
+        if !was_on_ground_effect {
+            if let Some(effect) = self.ground_effect_under(character).and_then(|e| e.on_enter) {
+                self.perform_ground_effect(character, effect).await;
+            }
+        }
+        self.perform_trigger_traps(character).await;
+
+        self.on_character_positions_changed();
+    }
+
+    async fn perform_drop_loot(&self, victim: &Character) {
+        let loot = victim.loot.take();
+        if loot.money == 0 && loot.items.is_empty() {
+            return;
+        }
+        let Some(receiver) = self.player_characters().next() else {
+            return;
+        };
+
+        receiver.party().gain_money(loot.money);
+
+        let mut deposited = vec![];
+        let mut left_on_ground = vec![];
+        for item in loot.items {
+            if receiver.party().deposit(item) {
+                deposited.push(item);
+            } else {
+                left_on_ground.push(item);
+            }
+        }
+        if !left_on_ground.is_empty() {
+            self.ground_loot
+                .borrow_mut()
+                .entry(victim.pos())
+                .or_default()
+                .extend(left_on_ground.iter().copied());
+        }
+
+        self.ui_handle_event(GameEvent::LootDropped {
+            position: victim.pos(),
+            money: loot.money,
+            deposited,
+            left_on_ground,
+        })
+        .await;
+    }
+
+    async fn perform_pick_up_loot(&self, character: &Character) {
+        let positions: Vec<Position> = self
+            .ground_loot
+            .borrow()
+            .keys()
+            .copied()
+            .filter(|pos| character.occupies_cell(*pos))
+            .collect();
+
+        for position in positions {
+            let mut picked_up = vec![];
+            {
+                let mut ground_loot = self.ground_loot.borrow_mut();
+                let items = ground_loot.get_mut(&position).unwrap();
+                // Whatever still doesn't fit in the stash stays on the ground
+                items.retain(|item| {
+                    if character.party().deposit(*item) {
+                        picked_up.push(*item);
+                        false
+                    } else {
+                        true
+                    }
+                });
+                if items.is_empty() {
+                    ground_loot.remove(&position);
+                }
+            }
+
+            if !picked_up.is_empty() {
+                self.ui_handle_event(GameEvent::LootPickedUp {
+                    character: character.id(),
+                    position,
+                    items: picked_up,
+                    remaining_on_ground: self.ground_loot.borrow().contains_key(&position),
+                })
+                .await;
+            }
+        }
+    }
+
+    async fn perform_overwatch(
+        &self,
+        character: &Character,
+        prev_position: Position,
+    ) -> Result<(), QuitEvent> {
+        for reactor in self.characters.iter() {
+            let Some(weapon) = reactor.weapon(HandType::MainHand) else {
+                continue;
+            };
+            let reserved_ap = reactor
+                .conditions
+                .borrow()
+                .get_stacks(&Condition::Overwatch);
+            let in_view = |pos: Position| {
+                reactor
+                    .reaches_with_attack(HandType::MainHand, pos, std::iter::empty())
+                    .1
+                    != ActionReach::No
+                    && self.has_line_of_sight(reactor.pos(), pos)
+            };
+            let is_triggered = reactor.player_controlled() != character.player_controlled()
+                && !weapon.is_melee()
+                && reserved_ap >= weapon.action_point_cost
+                && !reactor.has_condition(&Condition::Frozen)
+                && !reactor.has_condition(&Condition::Petrified)
+                && !reactor.is_downed()
+                && !in_view(prev_position)
+                && in_view(character.pos());
+            if !is_triggered {
+                continue;
+            }
+
+            let chooses_to_shoot = self
+                .user_interface
+                .choose_overwatch(
+                    self,
+                    reactor.id(),
+                    character.id(),
+                    (prev_position, character.pos()),
+                )
+                .await?;
+            if !chooses_to_shoot {
+                continue;
+            }
+
+            // Only one shot per stance; the reserved AP pays for it
+            reactor.clear_condition(Condition::Overwatch);
+            reactor.set_facing_toward(character.pos());
+            self.ui_handle_event(GameEvent::CharacterReactedWithOverwatch {
+                reactor: reactor.id(),
+                target: character.id(),
+            })
+            .await;
+
+            self.ui_handle_event(GameEvent::AttackWasInitiated {
+                actor: reactor.id(),
+                target: character.id(),
+            })
+            .await;
+
+            let event = Self::perform_attack(
+                reactor,
+                HandType::MainHand,
+                &[],
+                character,
+                None,
+                0,
+                ActionPerformanceMode::Real(self),
+                None,
+            );
+            self.ui_handle_event(GameEvent::Attacked(event)).await;
+
+            if character.is_dead() {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    async fn perform_brace_against_charge(
+        &self,
+        character: &Character,
+        prev_position: Position,
+    ) -> Result<(), QuitEvent> {
+        for reactor in self.characters.iter() {
+            let is_charged = reactor.player_controlled() != character.player_controlled()
+                && reactor.has_condition(&Condition::Braced)
+                && reactor.has_reach_weapon()
+                && reactor.action_points.current() >= 1
+                && !reactor.threatens(reactor.pos(), prev_position)
+                && reactor.threatens(reactor.pos(), character.pos());
+            if !is_charged {
+                continue;
+            }
+
+            let chooses_to_attack = self
+                .user_interface
+                .choose_movement_opportunity_attack(
+                    self,
+                    reactor.id(),
+                    character.id(),
+                    (prev_position, character.pos()),
+                )
+                .await?;
+            if !chooses_to_attack {
+                continue;
+            }
+
+            reactor.clear_condition(Condition::Braced);
+            reactor.set_facing_toward(character.pos());
+            self.log(format!(
+                "{} braced against {}'s charge",
+                reactor.name, character.name
+            ))
+            .await;
+            self.ui_handle_event(GameEvent::CharacterReactedWithOpportunityAttack {
+                reactor: reactor.id(),
+            })
+            .await;
+
+            reactor.action_points.spend(1);
+
+            self.ui_handle_event(GameEvent::AttackWasInitiated {
+                actor: reactor.id(),
+                target: character.id(),
+            })
+            .await;
+
+            // The charger runs right into the set weapon, doubling its damage
+            let weapon_damage = reactor.weapon(HandType::MainHand).unwrap().damage;
+            let event = Self::perform_attack(
+                reactor,
+                HandType::MainHand,
+                &[(
+                    "Braced",
+                    AttackEnhancementEffect {
+                        bonus_damage: weapon_damage,
+                        ..AttackEnhancementEffect::default()
+                    },
+                )],
+                character,
+                None,
+                0,
+                ActionPerformanceMode::Real(self),
+                None,
+            );
+            self.ui_handle_event(GameEvent::Attacked(event)).await;
+
+            if character.is_dead() {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    fn ground_effect_under(&self, character: &Character) -> Option<GroundEffect> {
+        self.ground_effects
+            .borrow()
+            .iter()
+            .find(|(pos, _)| character.occupies_cell(**pos))
+            .map(|(_, effect)| *effect)
+    }
+
+    async fn perform_place_ground_effect(&self, cells: Vec<Position>, effect: GroundEffect) {
+        {
+            let (w, h) = self.pathfind_grid.dimensions();
+            let mut ground_effects = self.ground_effects.borrow_mut();
+            for cell in cells
+                .into_iter()
+                .filter(|(x, y)| (0..w as i32).contains(x) && (0..h as i32).contains(y))
+            {
+                // A fresh effect replaces whatever was lingering there
+                ground_effects.insert(cell, effect);
+            }
+        }
+        self.notify_ui_of_ground_effects().await;
+    }
+
+    async fn perform_tick_ground_effects(&self) {
+        let changed = {
+            let mut ground_effects = self.ground_effects.borrow_mut();
+            let num_before = ground_effects.len();
+            for effect in ground_effects.values_mut() {
+                effect.remaining_rounds = effect.remaining_rounds.saturating_sub(1);
+            }
+            ground_effects.retain(|_, effect| effect.remaining_rounds > 0);
+            ground_effects.len() != num_before
+        };
+        if changed {
+            self.notify_ui_of_ground_effects().await;
+        }
+    }
+
+    async fn notify_ui_of_ground_effects(&self) {
+        let positions = self.ground_effects.borrow().keys().copied().collect();
+        self.ui_handle_event(GameEvent::GroundEffectsChanged { positions })
+            .await;
+    }
+
+    async fn perform_place_trap(
+        &self,
+        placer: &Rc<Character>,
+        ability_name: &'static str,
+        ability_roll: AbilityRoll,
+        effect: SpellNegativeEffect,
+        position: Position,
+    ) {
+        self.traps.borrow_mut().push(Trap {
+            position,
+            placer: Rc::clone(placer),
+            ability_name,
+            ability_roll,
+            effect,
+        });
+        self.notify_ui_of_traps().await;
+
+        // Placing it right under an enemy's feet sets it off immediately
+        for character in self.characters.iter() {
+            if character.occupies_cell(position) {
+                self.perform_trigger_traps(&character).await;
+            }
+        }
+    }
+
+    async fn perform_trigger_traps(&self, character: &Character) {
+        let triggered: Vec<Trap> = {
+            let mut traps = self.traps.borrow_mut();
+            let (triggered, remaining) = traps.drain(..).partition(|trap: &Trap| {
+                character.occupies_cell(trap.position)
+                    && trap.placer.player_controlled() != character.player_controlled()
+            });
+            *traps = remaining;
+            triggered
+        };
+        if triggered.is_empty() {
+            return;
+        }
+        self.notify_ui_of_traps().await;
+
+        for trap in triggered {
+            if character.is_dead() {
+                break;
+            }
+            let mut detail_lines = vec![];
+            let outcome = Self::perform_spell_enemy_effect(
+                &trap.placer,
+                trap.ability_name,
+                &trap.ability_roll,
+                &[],
+                trap.effect,
+                character,
+                &mut detail_lines,
+                None,
+                ActionPerformanceMode::Real(self),
+            );
+            self.ui_handle_event(GameEvent::TrapTriggered {
+                victim: character.id(),
+                position: trap.position,
+                ability_name: trap.ability_name,
+                damage: outcome.damage(),
+                detail_lines,
+            })
+            .await;
+        }
+    }
+
+    async fn notify_ui_of_traps(&self) {
+        // The players only get to see their own traps
+        let positions = self
+            .traps
+            .borrow()
+            .iter()
+            .filter(|trap| trap.placer.player_controlled())
+            .map(|trap| trap.position)
+            .collect();
+        self.ui_handle_event(GameEvent::TrapsChanged { positions })
+            .await;
+    }
+
+    async fn perform_ground_effect(&self, character: &Character, effect: ApplyEffect) {
+        let (applied, log_line, _damage) =
+            self.perform_effect_application(effect, None, None, character);
+        if let Some(ApplyEffect::Condition(apply_condition)) = applied {
+            self.ui_handle_event(GameEvent::CharacterReceivedCondition {
+                character: character.id(),
+                condition: apply_condition.condition,
+            })
+            .await;
+        }
+        self.log(log_line.trim_start().to_string()).await;
+    }
+
+    fn on_character_positions_changed(&self) {
+        let mut positions = vec![];
+        for character in self.characters.iter() {
+            positions.push((character.pos(), character.player_controlled()));
+        }
 
         for character in self.characters.iter() {
+            let mut num_adjacent_enemies = 0;
+            for (pos, player_controlled) in &positions {
+                if *player_controlled != character.player_controlled()
+                    && are_entities_within_melee(*pos, character.pos())
+                {
+                    num_adjacent_enemies += 1;
+                }
+            }
+            character.is_adjacent_to_enemy.set(num_adjacent_enemies > 0);
+
             if character
                 .known_passive_skills
                 .borrow()
                 .contains(&PassiveSkill::ThrillOfBattle)
             {
-                let mut num_adjacent_enemies = 0;
-                for (pos, player_controlled) in &positions {
-                    if *player_controlled != character.player_controlled()
-                        && are_entities_within_melee(*pos, character.pos())
-                    {
-                        num_adjacent_enemies += 1;
-                    }
-                }
                 dbg!(num_adjacent_enemies);
                 character
                     .conditions
                     .borrow_mut()
                     .add_or_remove(Condition::ThrillOfBattle, num_adjacent_enemies >= 2);
             }
+
+            if num_adjacent_enemies > 0 && character.has_condition(&Condition::Hidden) {
+                // This isn't async, so the UI is notified once the current action has resolved
+                character.is_being_revealed.set(true);
+            }
         }
     }
 
@@ -868,18 +2000,33 @@ impl CoreGame {
                 actual_effect = Some(ApplyEffect::RemoveActionPoints(lost));
                 format!("  {} lost {} AP", receiver.name, n)
             }
+            ApplyEffect::GainActionPoints(n) => {
+                let gained = receiver.action_points.gain(n);
+                actual_effect = Some(ApplyEffect::GainActionPoints(gained));
+                format!("  {} gained {} AP", receiver.name, gained)
+            }
             ApplyEffect::GainStamina(n) => {
                 let gained = receiver.stamina.gain(n);
                 actual_effect = Some(ApplyEffect::GainStamina(gained));
                 format!("  {} gained {} stamina", receiver.name, gained)
             }
             ApplyEffect::GainHealth(n) => {
-                let gained = receiver.health.gain(n);
+                let gained = self.perform_gain_health(receiver, n);
                 actual_effect = Some(ApplyEffect::GainHealth(gained));
                 format!("  {} gained {} health", receiver.name, gained)
             }
+            ApplyEffect::Condition(apply_condition)
+                if receiver.is_immune_to(apply_condition.condition) =>
+            {
+                immunity_line(receiver, apply_condition.condition)
+            }
             e @ ApplyEffect::Condition(apply_condition) => {
                 actual_effect = Some(e);
+                if apply_condition.condition == Condition::Taunted {
+                    if let Some(giver) = giver {
+                        receiver.taunted_by.set(Some(giver.id()));
+                    }
+                }
                 self.perform_receive_condition(apply_condition, receiver)
             }
             e @ ApplyEffect::PerBleeding {
@@ -938,6 +2085,43 @@ impl CoreGame {
                 actual_effect = Some(e);
                 format!("  {} was knocked back ({})", receiver.name, amount)
             }
+            ApplyEffect::DrainMana {
+                amount,
+                damage_per_point,
+            } => {
+                let drained = receiver.mana.lose(amount);
+                receiver.on_mana_changed();
+                if receiver.mana.current() == 0 {
+                    // With no mana left, there's nothing for the surge to empower
+                    receiver.clear_condition(Condition::ArcaneSurge);
+                }
+                actual_effect = Some(ApplyEffect::DrainMana {
+                    amount: drained,
+                    damage_per_point,
+                });
+                let mut line = format!("  {} lost {} mana", receiver.name, drained);
+                if damage_per_point > 0 {
+                    damage_dealt = self.perform_losing_health(receiver, drained * damage_per_point);
+                    line.push_str(&format!(" and {} health", damage_dealt));
+                }
+                line
+            }
+            ApplyEffect::Dispel(dispel) => {
+                let removed = receiver.dispel(dispel);
+                if removed.is_empty() {
+                    format!("  {} had nothing to dispel", receiver.name)
+                } else {
+                    actual_effect = Some(ApplyEffect::Dispel(DispelEffect {
+                        positive: dispel.positive,
+                        max_count: removed.len() as u32,
+                    }));
+                    let names: Vec<&str> = removed.iter().map(|c| c.name()).collect();
+                    let line = format!("  {} lost {}", receiver.name, names.join(", "));
+                    // This isn't async, so the UI is notified once the current action has resolved
+                    receiver.dispelled_conditions.borrow_mut().extend(removed);
+                    line
+                }
+            }
         };
 
         (actual_effect, line, damage_dealt)
@@ -952,26 +2136,106 @@ impl CoreGame {
         apply_condition: ApplyCondition,
         receiver: &Character,
     ) -> String {
-        let ends_at = apply_condition
-            .duration_rounds
-            .map(|rounds| self.current_time() + (rounds * self.round_length));
-        receiver.receive_condition(apply_condition.condition, apply_condition.stacks, ends_at);
+        let condition = apply_condition.condition;
+
+        if receiver.is_immune_to(condition) {
+            return immunity_line(receiver, condition);
+        }
+
+        // Resistance reduces the stacks if there are any, and otherwise the duration
+        let mut stacks = apply_condition.stacks;
+        let mut duration_rounds = apply_condition.duration_rounds;
+        if let Some(n) = stacks {
+            stacks = Some(receiver.resist_condition(condition, n));
+        } else if let Some(rounds) = duration_rounds {
+            duration_rounds = Some(receiver.resist_condition(condition, rounds));
+        }
+        let resisted =
+            stacks != apply_condition.stacks || duration_rounds != apply_condition.duration_rounds;
+
+        if stacks == Some(0) || duration_rounds == Some(0) {
+            return format!(
+                "  {} resisted |<keyword>{}|",
+                receiver.name,
+                condition.name()
+            );
+        }
+
+        let ends_at =
+            duration_rounds.map(|rounds| self.current_time() + (rounds * self.round_length));
+        // (receive_condition applies the stack resistance itself)
+        receiver.receive_condition(condition, apply_condition.stacks, ends_at);
         let mut line = format!(
             "  {} received |<keyword>{}|",
             receiver.name,
-            apply_condition.condition.name()
+            condition.name()
         );
 
-        if let Some(stacks) = apply_condition.stacks {
+        if let Some(stacks) = stacks {
             line.push_str(&format!(" x {}", stacks));
         }
 
-        if let Some(duration) = apply_condition.duration_rounds {
+        if let Some(duration) = duration_rounds {
             line.push_str(&format!(" ({})", duration));
         }
+
+        if resisted {
+            line.push_str(" |<faded>(resisted)|");
+        }
         line
     }
 
+    /// Enemies of the caster that know Counterspell get a chance to interrupt the spell before it's rolled.
+    /// Returns true if the spell was countered.
+    async fn perform_counterspell_window(
+        &self,
+        caster: &Character,
+        ability: Ability,
+    ) -> Result<bool, QuitEvent> {
+        for ch in self.characters.iter() {
+            let is_enemy = ch.player_controlled() != caster.player_controlled();
+            if is_enemy
+                && ch.can_counterspell(caster.pos())
+                && self.has_line_of_sight(ch.pos(), caster.pos())
+            {
+                let chooses_to_counter = self
+                    .user_interface
+                    .choose_counterspell(self, ch.id(), caster.id(), ability)
+                    .await?;
+                if chooses_to_counter {
+                    ch.action_points.spend(COUNTERSPELL_AP_COST);
+                    ch.spend_mana(COUNTERSPELL_MANA_COST);
+
+                    let mut detail_lines = vec![];
+                    let mut roll_spell = |character: &Character| {
+                        let dice_roll = self.roll_d20_with_advantage(0);
+                        let modifier = character.spell_modifier();
+                        let result = dice_roll + modifier;
+                        detail_lines.push(format!(
+                            "{}: {} +{} (|<dice>| |<stat>Spell|) = |<value>{}|",
+                            character.name, dice_roll, modifier, result
+                        ));
+                        result
+                    };
+                    let counter_result = roll_spell(ch);
+                    let spell_result = roll_spell(caster);
+                    let success = counter_result > spell_result;
+
+                    self.ui_handle_event(GameEvent::CharacterCounterspelled {
+                        reactor: ch.id(),
+                        caster: caster.id(),
+                        ability_name: ability.name,
+                        success,
+                        detail_lines,
+                    })
+                    .await;
+                    return Ok(success);
+                }
+            }
+        }
+        Ok(false)
+    }
+
     async fn perform_ability(
         caster: &Rc<Character>,
         ability: Ability,
@@ -988,17 +2252,9 @@ impl CoreGame {
         let caster_id = caster.id();
 
         let real_game: Option<&CoreGame> = mode.real_game();
-        let simulated_roll = mode.simulated_roll();
 
         if real_game.is_some() {
-            caster.action_points.spend(ability.action_point_cost);
-            caster.spend_mana(ability.mana_cost);
-            caster.stamina.spend(ability.stamina_cost);
-            for enhancement in enhancements {
-                caster.action_points.spend(enhancement.action_point_cost);
-                caster.spend_mana(enhancement.mana_cost);
-                caster.stamina.spend(enhancement.stamina_cost);
-            }
+            caster.spend_ability_costs(ability, enhancements);
         }
 
         let mut enemies_hit = vec![];
@@ -1012,8 +2268,30 @@ impl CoreGame {
                 }
             }
         }
+        let is_flurry = matches!(
+            ability.target,
+            AbilityTarget::Enemy {
+                effect: AbilityNegativeEffect::PerformAttack(AbilityAttackEffect {
+                    flurry: true,
+                    ..
+                }),
+                ..
+            }
+        );
+        if is_flurry {
+            cast_n_times = 2;
+        }
 
         for i in 0..cast_n_times {
+            if is_flurry && i > 0 {
+                if let ActionTarget::Character(target_id, _) = selected_target {
+                    if mode.characters().get(*target_id).is_dead() {
+                        // No need for the off-hand to follow up
+                        break;
+                    }
+                }
+            }
+
             let mut detail_lines = vec![];
 
             let mut advantange_level = 0_i32;
@@ -1030,7 +2308,7 @@ impl CoreGame {
             let mut maybe_ability_roll = None;
 
             if let Some(roll_type) = ability.roll {
-                let dice_roll = simulated_roll.unwrap_or(roll_d20_with_advantage(advantange_level));
+                let dice_roll = mode.roll_d20_with_advantage(advantange_level);
 
                 if let Some(description) = roll_description(advantange_level) {
                     detail_lines.push(description);
@@ -1061,6 +2339,7 @@ impl CoreGame {
                         dice_roll_line.push_str(&format!(" = |<value>{}|", ability_result));
 
                         maybe_ability_roll = Some(AbilityRoll::RolledWithSpellModifier {
+                            dice_roll,
                             result: ability_result,
                             line: dice_roll_line,
                         });
@@ -1087,6 +2366,7 @@ impl CoreGame {
                         dice_roll_line.push_str(&format!(" = |<value>{}|", ability_result));
 
                         maybe_ability_roll = Some(AbilityRoll::RolledWithAttackModifier {
+                            dice_roll,
                             result: ability_result,
                             line: dice_roll_line,
                         });
@@ -1097,27 +2377,66 @@ impl CoreGame {
                 };
             }
 
+            let ability_dice_roll = maybe_ability_roll
+                .as_ref()
+                .and_then(|ability_roll| ability_roll.dice_roll());
+
             let mut target_outcome = None;
             let mut area_outcome = None;
-
             match ability.target {
-                AbilityTarget::Enemy {
-                    effect,
-                    impact_circle,
-                    ..
-                } => {
+                AbilityTarget::Enemy { reach, effect, .. } => {
+                    let impact_circle = ability.impact_circle(enhancements);
                     let ActionTarget::Character(target_id, movement) = &selected_target else {
                         unreachable!()
                     };
 
+                    let mut effect = effect;
+
+                    if let AbilityNegativeEffect::PerformAttack(attack_effect) = &mut effect {
+                        if attack_effect.flurry && i > 0 {
+                            attack_effect.hand = HandType::OffHand;
+                        }
+                    }
+
                     if let Some(game) = real_game {
-                        if let Some(positions) = movement {
+                        if i == 0 && matches!(reach, AbilityReach::MoveIntoMelee(..)) {
+                            let target = game.characters.get(*target_id);
+                            assert!(can_move_into_melee(
+                                &game.pathfind_grid,
+                                caster,
+                                target,
+                                ability,
+                                enhancements
+                            )
+                            .is_some());
+                        }
+                        if let Some(positions) = movement
+                            .as_ref()
+                            .filter(|_| i == 0 && !caster.is_immobilized())
+                        {
+                            let mut charge_distance = 0.0;
+                            let mut prev_pos = caster.pos();
+                            for pos in positions {
+                                charge_distance += distance_between(prev_pos, *pos);
+                                prev_pos = *pos;
+                            }
+
                             game.perform_movement(
                                 caster.id(),
                                 positions.clone(),
                                 MovementType::AbilityEngage,
                             )
                             .await;
+
+                            if caster.is_dead() {
+                                // Killed by an opportunity attack along the way
+                                return resolve_events;
+                            }
+
+                            if let AbilityNegativeEffect::PerformAttack(attack_effect) = &mut effect
+                            {
+                                attack_effect.charge_bonus_damage = charge_distance.floor() as u32;
+                            }
                         }
                     }
 
@@ -1142,10 +2461,10 @@ impl CoreGame {
                     let mut ability_roll = maybe_ability_roll.unwrap();
 
                     let rolled = match &mut ability_roll {
-                        AbilityRoll::RolledWithSpellModifier { result, line } => {
+                        AbilityRoll::RolledWithSpellModifier { result, line, .. } => {
                             Some((result, line))
                         }
-                        AbilityRoll::RolledWithAttackModifier { result, line } => {
+                        AbilityRoll::RolledWithAttackModifier { result, line, .. } => {
                             Some((result, line))
                         }
                         AbilityRoll::WillRollDuringAttack { .. } => None,
@@ -1156,11 +2475,8 @@ impl CoreGame {
 
                         let spell_enemy_effect = effect.unwrap_spell();
                         if let Some(contest) = spell_enemy_effect.defense_type {
-                            let (def_str, def_value) = match contest {
-                                DefenseType::Will => ("Will", target.will()),
-                                DefenseType::Evasion => ("Evasion", target.evasion()),
-                                DefenseType::Toughness => ("Toughness", target.toughness()),
-                            };
+                            let (used_defense, def_value) = target.resolve_defense(contest);
+                            let def_str = used_defense.label();
                             let def_line = format!(
                                 "{} - {} (|<shield>|<stat>{}|) = |<value>{}|",
                                 result,
@@ -1212,7 +2528,11 @@ impl CoreGame {
                     dbg!(before.elapsed());
                 }
 
-                AbilityTarget::Ally { range: _, effect } => {
+                AbilityTarget::Ally {
+                    range: _,
+                    effect,
+                    pull_toward_caster,
+                } => {
                     let ActionTarget::Character(target_id, movement) = &selected_target else {
                         unreachable!()
                     };
@@ -1253,12 +2573,25 @@ impl CoreGame {
                         mode,
                     );
 
+                    if let (Some(game), Some(max_distance)) = (real_game, pull_toward_caster) {
+                        if find_pull_path(&game.pathfind_grid, target, caster.pos(), max_distance)
+                            .is_some()
+                        {
+                            detail_lines.push(format!("  {} was pulled", target.name));
+                            // This isn't async, so the movement happens once the ability has resolved
+                            target
+                                .is_being_pulled_toward
+                                .set(Some((caster_id, max_distance)));
+                        }
+                    }
+
                     target_outcome = Some((*target_id, outcome));
                 }
 
                 AbilityTarget::Area {
                     range: _,
                     area_effect,
+                    ground_effect,
                 } => {
                     let target_pos = selected_target.unwrap_position();
 
@@ -1296,11 +2629,68 @@ impl CoreGame {
                         targets: outcomes,
                         shape: area_effect.shape,
                     });
+
+                    if let (Some(game), Some(ground_effect)) = (real_game, ground_effect) {
+                        let cells = area_cells(caster.pos(), target_pos, area_effect.shape);
+                        game.perform_place_ground_effect(cells, ground_effect).await;
+                    }
+                }
+
+                AbilityTarget::Trap { range: _, effect } => {
+                    let target_pos = selected_target.unwrap_position();
+
+                    if let Some(game) = real_game {
+                        assert!(caster.reaches_with_ability(ability, enhancements, target_pos));
+                        caster.set_facing_toward(target_pos);
+                        game.ui_handle_event(GameEvent::AbilityWasInitiated {
+                            actor: caster_id,
+                            ability,
+                            target: None,
+                            area_at: None,
+                        })
+                        .await;
+
+                        let ability_roll = maybe_ability_roll.unwrap();
+                        if let Some((_ability_result, dice_roll_line)) = ability_roll.actual_roll()
+                        {
+                            detail_lines.push(dice_roll_line.to_string());
+                        }
+                        detail_lines.push(format!("{} placed a trap", caster.name));
+                        game.perform_place_trap(
+                            caster,
+                            ability.name,
+                            ability_roll,
+                            effect,
+                            target_pos,
+                        )
+                        .await;
+                    }
+                }
+
+                AbilityTarget::Teleport { range: _ } => {
+                    let target_pos = selected_target.unwrap_position();
+
+                    if let Some(game) = real_game {
+                        game.ui_handle_event(GameEvent::AbilityWasInitiated {
+                            actor: caster_id,
+                            ability,
+                            target: None,
+                            area_at: None,
+                        })
+                        .await;
+
+                        if caster.pos() != target_pos {
+                            assert!(caster.reaches_with_ability(ability, enhancements, target_pos));
+                            game.perform_teleport(caster, target_pos).await;
+                            detail_lines.push(format!("{} teleported", caster.name));
+                        }
+                    }
                 }
 
                 AbilityTarget::None {
                     self_area,
                     self_effect,
+                    summon,
                 } => {
                     if let Some(game) = real_game {
                         game.ui_handle_event(GameEvent::AbilityWasInitiated {
@@ -1312,35 +2702,68 @@ impl CoreGame {
                         .await;
                     }
 
-                    if let Some(AbilityRoll::RolledWithSpellModifier { result: _, line }) =
-                        &maybe_ability_roll
-                    {
-                        detail_lines.push(line.clone());
-                    }
+                    let maybe_ability_result = maybe_ability_roll
+                        .as_ref()
+                        .and_then(|ability_roll| ability_roll.actual_roll())
+                        .map(|(ability_result, dice_roll_line)| {
+                            detail_lines.push(dice_roll_line.to_string());
+                            ability_result
+                        });
 
-                    if let Some(effect) = self_effect {
-                        let degree_of_success = if let Some(ability_roll) = &maybe_ability_roll {
-                            let (ability_result, _dice_roll_line) =
-                                ability_roll.unwrap_actual_roll();
-                            ability_result / 10
+                    if let Some(mut effect) = self_effect {
+                        if ability.id == AbilityId::SecondWind {
+                            // The closer the caster is to going down, the bigger the comeback
+                            let missing_health = caster.health.max() - caster.health.current();
+                            effect.healing += missing_health / SECOND_WIND_MISSING_HEALTH_DIVISOR;
+                        }
+
+                        let backfire = effect.backfire.filter(|backfire| {
+                            maybe_ability_result.is_some_and(|result| result < backfire.roll_below)
+                        });
+
+                        if let Some(backfire) = backfire {
+                            detail_lines.push(format!(
+                                "Backfire! |<faded>(below {})|",
+                                backfire.roll_below
+                            ));
+                            let mut applied_effects = vec![];
+                            if let Some(game) = real_game {
+                                let (applied, log_line, _damage) = game.perform_effect_application(
+                                    backfire.effect,
+                                    None,
+                                    None,
+                                    caster,
+                                );
+                                if let Some(applied) = applied {
+                                    applied_effects.push(applied);
+                                }
+                                detail_lines.push(log_line);
+                            } else {
+                                applied_effects.push(backfire.effect);
+                            }
+                            target_outcome = Some((
+                                caster_id,
+                                AbilityTargetOutcome::AffectedAlly { applied_effects },
+                            ));
                         } else {
-                            0
-                        };
+                            let degree_of_success =
+                                maybe_ability_result.map(|result| result / 10).unwrap_or(0);
 
-                        if degree_of_success > 0 {
-                            detail_lines.push(format!("Fortune: {}", degree_of_success));
-                        }
+                            if degree_of_success > 0 {
+                                detail_lines.push(format!("Fortune: {}", degree_of_success));
+                            }
 
-                        let outcome = Self::perform_ability_ally_effect(
-                            ability.name,
-                            enhancements,
-                            effect,
-                            caster,
-                            &mut detail_lines,
-                            degree_of_success as u32,
-                            mode,
-                        );
-                        target_outcome = Some((caster_id, outcome));
+                            let outcome = Self::perform_ability_ally_effect(
+                                ability.name,
+                                enhancements,
+                                effect,
+                                caster,
+                                &mut detail_lines,
+                                degree_of_success as u32,
+                                mode,
+                            );
+                            target_outcome = Some((caster_id, outcome));
+                        }
                     }
 
                     if let Some(area_effect) = self_area {
@@ -1362,11 +2785,34 @@ impl CoreGame {
                             shape: area_effect.shape,
                         });
                     }
+
+                    if let Some(summon) = summon {
+                        detail_lines.push(format!("Summoned {}", summon.name));
+                        if real_game.is_some() {
+                            // Characters can't be added from here, so it happens once the ability has resolved
+                            caster.pending_summon.set(Some(summon));
+                        }
+                    }
                 }
             };
 
+            if let (Some(backlash), Some(dice_roll)) = (ability.backlash, ability_dice_roll) {
+                if dice_roll <= backlash.dice_roll_at_most {
+                    detail_lines.push(format!("Backlash! |<faded>(rolled {})|", dice_roll));
+                    if let Some(game) = real_game {
+                        let (_applied, log_line, _damage) =
+                            game.perform_effect_application(backlash.effect, None, None, caster);
+                        detail_lines.push(log_line);
+                    }
+                }
+            }
+
             if i < cast_n_times - 1 {
-                detail_lines.push(format!("{} cast again!", caster.name))
+                if is_flurry {
+                    detail_lines.push(format!("{} follows up with the off-hand!", caster.name))
+                } else {
+                    detail_lines.push(format!("{} cast again!", caster.name))
+                }
             }
 
             if let Some((target_id, outcome)) = &target_outcome {
@@ -1390,6 +2836,7 @@ impl CoreGame {
                 area_outcome,
                 ability,
                 detail_lines,
+                cast_index: i,
             };
 
             resolve_events.push(resolve_event.clone());
@@ -1401,6 +2848,16 @@ impl CoreGame {
             }
         }
 
+        if let Some(game) = real_game {
+            if ability.is_channeled() && caster.conditions.borrow().has(&Condition::Channeling) {
+                caster.channeled_ability.set(Some(ChanneledAbility {
+                    ability,
+                    area_center: caster.pos(),
+                    cast_at: game.current_time(),
+                }));
+            }
+        }
+
         resolve_events
     }
 
@@ -1447,7 +2904,7 @@ impl CoreGame {
     }
 
     fn perform_ability_area_ally_effect(
-        mut shape: AreaShape,
+        shape: AreaShape,
         name: &'static str,
         enhancements: &[AbilityEnhancement],
         caster: &Character,
@@ -1459,15 +2916,14 @@ impl CoreGame {
     ) -> Vec<(CharacterId, AbilityTargetOutcome)> {
         let mut target_outcomes = vec![];
 
-        for enhancement in enhancements {
-            let e = enhancement.spell_effect.unwrap();
-            if e.increased_radius_tenths > 0 {
-                let AreaShape::Circle(radius) = &mut shape else {
-                    panic!()
-                };
-                *radius = radius.plusf(e.increased_radius_tenths as f32 * 0.1);
-            }
-        }
+        let target_ids = characters_in_area(
+            mode.characters().iter(),
+            caster,
+            area_pos,
+            shape,
+            AreaTargetAcquisition::Allies,
+            enhancements,
+        );
 
         let roll_result = ability_roll.unwrap_actual_roll().0;
 
@@ -1477,11 +2933,7 @@ impl CoreGame {
         }
 
         for other_char in mode.characters().iter() {
-            if other_char.player_controlled() != caster.player_controlled() {
-                continue;
-            }
-
-            if is_target_within_shape(caster.pos(), area_pos, shape, other_char) {
+            if target_ids.contains(&other_char.id()) {
                 detail_lines.push(other_char.name.to_string());
 
                 let outcome = Self::perform_ability_ally_effect(
@@ -1527,12 +2979,21 @@ impl CoreGame {
             detail_lines.push(line);
 
             if let Some(game) = real_game {
-                let health_gained = game.perform_gain_health(target, healing);
-                detail_lines.push(format!(
-                    "  {} was healed for {}",
-                    target.name, health_gained
-                ));
-                applied_effects.push(ApplyEffect::GainHealth(health_gained));
+                if target.is_downed() {
+                    let health_gained = game.perform_revive(target);
+                    detail_lines.push(format!(
+                        "  {} was revived with {} health",
+                        target.name, health_gained
+                    ));
+                    applied_effects.push(ApplyEffect::GainHealth(health_gained));
+                } else {
+                    let health_gained = game.perform_gain_health(target, healing);
+                    detail_lines.push(format!(
+                        "  {} was healed for {}",
+                        target.name, health_gained
+                    ));
+                    applied_effects.push(ApplyEffect::GainHealth(health_gained));
+                }
             } else {
                 // This might include over-heal, but hey.
                 applied_effects.push(ApplyEffect::GainHealth(healing));
@@ -1545,16 +3006,19 @@ impl CoreGame {
                     ApplyEffect::RemoveActionPoints(ref mut n) => *n += degree_of_success,
                     ApplyEffect::GainHealth(ref mut n) => *n += degree_of_success,
                     ApplyEffect::GainStamina(ref mut n) => *n += degree_of_success,
-                    ApplyEffect::Condition(ref apply_condition) => {
-                        /*
-                        if let Some(stacks) = condition.stacks() {
+                    // A good roll doesn't let the ally act even more
+                    ApplyEffect::GainActionPoints(..) => {}
+                    ApplyEffect::Condition(ref mut apply_condition) => {
+                        // Conditions that only have a duration are unaffected
+                        if let Some(stacks) = &mut apply_condition.stacks {
                             *stacks += degree_of_success;
                         }
-                         */
                     }
                     ApplyEffect::PerBleeding { .. } => {}
                     ApplyEffect::ConsumeCondition { .. } => {}
                     ApplyEffect::Pushed { .. } => {}
+                    ApplyEffect::DrainMana { .. } => {}
+                    ApplyEffect::Dispel { .. } => {}
                 }
 
                 let (applied, log_line, _damage) =
@@ -1579,7 +3043,7 @@ impl CoreGame {
     }
 
     fn perform_ability_area_enemy_effect(
-        mut shape: AreaShape,
+        shape: AreaShape,
         name: &'static str,
         ability_roll: AbilityRoll,
         enhancements: &[AbilityEnhancement],
@@ -1594,39 +3058,24 @@ impl CoreGame {
 
         let mut target_outcomes = vec![];
 
-        for enhancement in enhancements {
-            if let Some(e) = enhancement.spell_effect {
-                if e.increased_radius_tenths > 0 {
-                    let AreaShape::Circle(radius) = &mut shape else {
-                        panic!()
-                    };
-                    *radius = radius.plusf(e.increased_radius_tenths as f32 * 0.1);
-                }
-            }
-        }
+        let target_ids = characters_in_area(
+            mode.characters().iter(),
+            caster,
+            area_pos,
+            shape,
+            acquisition,
+            enhancements,
+        );
 
         for other_char in mode.characters().iter() {
-            let is_ally = other_char.player_controlled() == caster.player_controlled();
-            let valid_target = match acquisition {
-                AreaTargetAcquisition::Enemies => !is_ally,
-                AreaTargetAcquisition::Everyone => true,
-                AreaTargetAcquisition::Allies => unreachable!(),
-            };
-            if !valid_target {
-                continue;
-            }
-
-            if is_target_within_shape(caster.pos(), area_pos, shape, other_char) {
+            if target_ids.contains(&other_char.id()) {
                 let mut line = format!("|{}|", other_char.name);
                 match effect {
                     AbilityNegativeEffect::Spell(spell_enemy_effect) => {
                         if let Some(contest) = spell_enemy_effect.defense_type {
                             let roll_result = ability_roll.unwrap_actual_roll().0;
-                            let (def_str, def_value) = match contest {
-                                DefenseType::Will => ("Will", other_char.will()),
-                                DefenseType::Evasion => ("Evasion", other_char.evasion()),
-                                DefenseType::Toughness => ("Toughness", other_char.toughness()),
-                            };
+                            let (used_defense, def_value) = other_char.resolve_defense(contest);
+                            let def_str = used_defense.label();
                             line.push_str(&format!(
                                 ": {} - {} (|<shield>|<stat>{}) = |<value>{}|",
                                 roll_result,
@@ -1730,7 +3179,7 @@ impl CoreGame {
                 caster.set_facing_toward(target.pos());
                 let event: AttackedEvent = Self::perform_attack(
                     caster,
-                    HandType::MainHand,
+                    ability_attack_effect.hand,
                     &attack_enhancement_effects,
                     target,
                     None,
@@ -1761,11 +3210,7 @@ impl CoreGame {
 
         let hit_type = match spell_enemy_effect.defense_type {
             Some(contest) => {
-                let defense = match contest {
-                    DefenseType::Will => target.will(),
-                    DefenseType::Evasion => target.evasion(),
-                    DefenseType::Toughness => target.toughness(),
-                };
+                let defense = target.defense(contest);
 
                 let final_result = ability_roll.unwrap_actual_roll().0 - defense as i32;
 
@@ -1799,6 +3244,14 @@ impl CoreGame {
                     dmg_calculation = n as i32;
                     dmg_str.push_str(&format!("{} |<faded>({})|", dmg_calculation, ability_name));
                 }
+                AbilityDamage::Scaling { base, .. } => {
+                    dmg_calculation = caster.spell_damage(ability_damage) as i32;
+                    dmg_str.push_str(&format!("{} |<faded>({})|", base, ability_name));
+                    let bonus_dmg = dmg_calculation - base as i32;
+                    if bonus_dmg > 0 {
+                        dmg_str.push_str(&format!(" +{} |<faded>(spell power)|", bonus_dmg));
+                    }
+                }
             };
 
             for enhancement in enhancements {
@@ -1815,8 +3268,17 @@ impl CoreGame {
             }
 
             if hit_type == HitType::Graze {
-                dmg_str.push_str(" -50% |<faded>(Graze)|");
-                dmg_calculation -= (dmg_calculation as f32 * 0.5).ceil() as i32;
+                match spell_enemy_effect.graze_behavior {
+                    SpellGrazeBehavior::Reduced => {
+                        dmg_str.push_str(" -50% |<faded>(Graze)|");
+                        dmg_calculation -= (dmg_calculation as f32 * 0.5).ceil() as i32;
+                    }
+                    SpellGrazeBehavior::DamageOnly => {}
+                    SpellGrazeBehavior::EffectsOnly => {
+                        dmg_str.push_str(" -100% |<faded>(Graze)|");
+                        dmg_calculation = 0;
+                    }
+                }
             } else if increased_by_good_roll && hit_type == HitType::Critical {
                 dmg_str.push_str(&format!(" +50% |<faded>(Crit)|"));
                 dmg_calculation += (dmg_calculation as f32 * 0.5).ceil() as i32;
@@ -1831,10 +3293,23 @@ impl CoreGame {
                 }
             }
 
+            if let Some(damage_type) = spell_enemy_effect.damage_type {
+                let resistance = target.protection_against(Some(damage_type));
+                if resistance > 0 {
+                    dmg_str.push_str(&format!(
+                        " -{resistance} |<faded>({} armor)|",
+                        damage_type.name()
+                    ));
+                    dmg_calculation -= resistance as i32;
+                }
+            }
+
             let damage = dmg_calculation.max(0) as u32;
 
             if let Some(game) = real_game {
-                game.perform_losing_health(target, damage);
+                if game.perform_losing_health(target, damage) > 0 {
+                    target.record_damage_from(caster);
+                }
                 dmg_str.push_str(&format!(" = |<value>{damage}|"));
                 detail_lines.push(dmg_str);
             }
@@ -1860,8 +3335,43 @@ impl CoreGame {
             }
         }
 
+        fn apply_hit_type_to_effect(effect: &mut ApplyEffect, hit_type: HitType) -> bool {
+            let mut reduced_to_nothing = false;
+            match effect {
+                ApplyEffect::RemoveActionPoints(n) => {
+                    apply_hit_type(n, hit_type, &mut reduced_to_nothing);
+                }
+                ApplyEffect::GainStamina(n) => apply_hit_type(n, hit_type, &mut reduced_to_nothing),
+                ApplyEffect::GainActionPoints(n) => {
+                    apply_hit_type(n, hit_type, &mut reduced_to_nothing)
+                }
+                ApplyEffect::GainHealth(n) => apply_hit_type(n, hit_type, &mut reduced_to_nothing),
+                ApplyEffect::Condition(apply_condition) => {
+                    if let Some(stacks) = &mut apply_condition.stacks {
+                        apply_hit_type(stacks, hit_type, &mut reduced_to_nothing);
+                    }
+                    if let Some(rounds) = &mut apply_condition.duration_rounds {
+                        apply_hit_type(rounds, hit_type, &mut reduced_to_nothing);
+                    }
+                }
+                ApplyEffect::PerBleeding { .. } => {}
+                ApplyEffect::ConsumeCondition { .. } => {}
+                ApplyEffect::Dispel { .. } => {}
+                ApplyEffect::Pushed(distance) => {
+                    apply_hit_type(distance, hit_type, &mut reduced_to_nothing);
+                }
+                ApplyEffect::DrainMana { amount, .. } => {
+                    apply_hit_type(amount, hit_type, &mut reduced_to_nothing);
+                }
+            }
+            reduced_to_nothing
+        }
+
         let mut damage_from_effects = 0;
 
+        let effects_lost_to_graze = hit_type == HitType::Graze
+            && spell_enemy_effect.graze_behavior == SpellGrazeBehavior::DamageOnly;
+
         if let Some(game) = real_game {
             for mut effect in spell_enemy_effect
                 .on_hit
@@ -1870,32 +3380,13 @@ impl CoreGame {
                 .copied()
                 .flatten()
             {
-                let mut reduced_to_nothing = false;
-                match effect {
-                    ApplyEffect::RemoveActionPoints(ref mut n) => {
-                        apply_hit_type(n, hit_type, &mut reduced_to_nothing);
-                    }
-                    ApplyEffect::GainStamina(ref mut n) => {
-                        apply_hit_type(n, hit_type, &mut reduced_to_nothing)
-                    }
-                    ApplyEffect::GainHealth(ref mut n) => {
-                        apply_hit_type(n, hit_type, &mut reduced_to_nothing)
-                    }
-                    ApplyEffect::Condition(ref mut apply_condition) => {
-                        if let Some(stacks) = &mut apply_condition.stacks {
-                            apply_hit_type(stacks, hit_type, &mut reduced_to_nothing);
-                        }
-                        if let Some(rounds) = &mut apply_condition.duration_rounds {
-                            apply_hit_type(rounds, hit_type, &mut reduced_to_nothing);
-                        }
-                    }
-                    ApplyEffect::PerBleeding { .. } => {}
-                    ApplyEffect::ConsumeCondition { .. } => {}
-                    ApplyEffect::Pushed(ref mut distance) => {
-                        apply_hit_type(distance, hit_type, &mut reduced_to_nothing);
-                    }
+                if effects_lost_to_graze {
+                    detail_lines.push(format!("|<keyword>{}| was lost |<faded>(Graze)|", effect));
+                    continue;
                 }
 
+                let reduced_to_nothing = apply_hit_type_to_effect(&mut effect, hit_type);
+
                 if reduced_to_nothing {
                     detail_lines.push(format!(
                         "|<keyword>{}| was reduced to nothing |<faded>(Graze)|",
@@ -1913,16 +3404,32 @@ impl CoreGame {
             }
 
             for enhancement in enhancements {
-                // TODO: shouldn't these also be affected by degree of success?
                 let e = enhancement.spell_effect.unwrap();
                 let effects = if area_center.is_some() {
                     e.area_on_hit
                 } else {
                     e.target_on_hit
                 };
-                for effect in effects.iter().flatten().flatten() {
+                for mut effect in effects.iter().flatten().flatten().copied() {
+                    if effects_lost_to_graze {
+                        detail_lines.push(format!(
+                            "|<keyword>{}| was lost |<faded>(Graze, {})|",
+                            effect, enhancement.name
+                        ));
+                        continue;
+                    }
+
+                    let reduced_to_nothing = apply_hit_type_to_effect(&mut effect, hit_type);
+                    if reduced_to_nothing {
+                        detail_lines.push(format!(
+                            "|<keyword>{}| was reduced to nothing |<faded>(Graze, {})|",
+                            effect, enhancement.name
+                        ));
+                        continue;
+                    }
+
                     let (applied, log_line, damage) =
-                        game.perform_effect_application(*effect, Some(caster), area_center, target);
+                        game.perform_effect_application(effect, Some(caster), area_center, target);
                     if let Some(applied) = applied {
                         applied_effects.push(applied);
                     }
@@ -1963,21 +3470,101 @@ impl CoreGame {
     }
 
     fn perform_losing_health(&self, character: &Character, amount: u32) -> u32 {
-        let amount_lost = character.health.lose(amount);
+        if character.has_condition(&Condition::Petrified) {
+            // Stone doesn't bleed
+            return 0;
+        }
+        let shield = character
+            .conditions
+            .borrow()
+            .get_stacks(&Condition::Shielded);
+        let absorbed = shield.min(amount);
+        if absorbed > 0 {
+            character
+                .conditions
+                .borrow_mut()
+                .lose_stacks(&Condition::Shielded, absorbed);
+            // This isn't async, so the UI is notified once the current action has resolved
+            character
+                .shield_absorbed
+                .set(character.shield_absorbed.get() + absorbed);
+        }
+
+        let amount_lost = character.health.lose(amount - absorbed);
+        if character.is_downed() && amount > absorbed {
+            // Any further harm finishes off a downed character
+            character.clear_condition(Condition::Downed);
+            character.conditions.borrow_mut().add(Condition::Dead);
+        }
         character.on_health_changed();
+        if amount_lost > 0 && character.has_condition(&Condition::Hidden) {
+            character.is_being_revealed.set(true);
+        }
+        if amount_lost > 0 && character.channeled_ability.get().is_some() {
+            // This isn't async, so the channel is broken (and the UI notified) once the current action has resolved
+            character.is_losing_channel.set(true);
+        }
         amount_lost
     }
 
     fn perform_gain_health(&self, character: &Character, amount: u32) -> u32 {
+        if character.is_downed() {
+            // Only an ally's healing can bring a downed character back (see perform_revive)
+            return 0;
+        }
         let amount_gained = character.health.gain(amount);
+        let overheal = amount - amount_gained;
+        if overheal > 0 && !character.is_dead() {
+            let mut conditions = character.conditions.borrow_mut();
+            let shield = conditions.get_stacks(&Condition::Shielded);
+            conditions.set_stacks(Condition::Shielded, shield + overheal);
+            character
+                .shield_gained
+                .set(character.shield_gained.get() + overheal);
+        }
         character.on_health_changed();
         amount_gained
     }
 
+    fn perform_revive(&self, character: &Character) -> u32 {
+        character.clear_condition(Condition::Downed);
+        let health = (character.health.max() / REVIVE_HEALTH_DIVISOR).max(1);
+        let amount_gained = character.health.gain(health);
+        character.on_health_changed();
+        // This isn't async, so the UI is notified once the current action has resolved
+        character.is_being_revived.set(true);
+        amount_gained
+    }
+
     async fn log(&self, line: impl Into<String>) {
         self.ui_handle_event(GameEvent::LogLine(line.into())).await;
     }
 
+    /// Describes what the attack would result in for a median dice roll (including the full
+    /// breakdown in the detail lines), without affecting the actual characters.
+    pub fn dry_run_attack(
+        characters: &Characters,
+        attacker: &Character,
+        hand_type: HandType,
+        enhancements: &[(&'static str, AttackEnhancementEffect)],
+        defender: &Character,
+        reaction: Option<(CharacterId, OnAttackedReaction)>,
+    ) -> AttackedEvent {
+        // The attack runs against copies, so that nothing it does to the characters persists
+        let attacker = Rc::new(attacker.clone());
+        let defender = defender.clone();
+        Self::perform_attack(
+            &attacker,
+            hand_type,
+            enhancements,
+            &defender,
+            reaction,
+            0,
+            ActionPerformanceMode::DryRun(DRY_RUN_DICE_ROLL, characters),
+            None,
+        )
+    }
+
     fn perform_attack(
         attacker: &Rc<Character>,
         hand_type: HandType,
@@ -1988,10 +3575,8 @@ impl CoreGame {
         mode: ActionPerformanceMode,
         ability_attack_effect: Option<AbilityAttackEffect>,
     ) -> AttackedEvent {
-        let game = match mode {
-            ActionPerformanceMode::Real(core_game) => Some(core_game),
-            ActionPerformanceMode::SimulatedRoll(..) => None,
-        };
+        let game = mode.real_game();
+        let describe = mode.describes();
 
         let mut attack_bonus = attack_roll_bonus(
             attacker,
@@ -2015,20 +3600,36 @@ impl CoreGame {
 
         let mut detail_lines = vec![];
 
-        let mut armor_value = defender.protection_from_armor();
+        let damage_type = attacker.weapon(hand_type).unwrap().damage_type;
+        let mut armor_value = defender.protection_against(Some(damage_type));
+
+        let cover_evasion = cover_bonus_evasion(attacker, hand_type, defender);
+        if cover_evasion > 0 {
+            evasion += cover_evasion;
+            if describe {
+                detail_lines.push(format!(
+                    "  |<shield>| |<stat>Evasion|: {} +{} |<faded>(Cover)| = |<value>{}|",
+                    evasion - cover_evasion,
+                    cover_evasion,
+                    evasion
+                ));
+            }
+        }
 
         if let Some((reactor, reaction)) = maybe_reaction {
-            if let Some(game) = game {
-                let reactor = game.characters.get(reactor);
+            let reactor = mode.characters().get(reactor);
+            if game.is_some() {
                 reactor.on_use_on_attacked_reaction(reaction);
+            }
+            if describe {
                 detail_lines.push(format!("{} reacted with {}", reactor.name, reaction.name));
             }
 
-            let bonus_evasion = reaction.effect.bonus_evasion;
+            let bonus_evasion = reaction_bonus_evasion(attacker, hand_type, defender, reaction);
             if bonus_evasion > 0 {
                 evasion += bonus_evasion;
 
-                if game.is_some() {
+                if describe {
                     detail_lines.push(format!(
                         "  |<shield>| |<stat>Evasion|: {} +{} |<faded>({})| = |<value>{}|",
                         evasion - bonus_evasion,
@@ -2036,6 +3637,13 @@ impl CoreGame {
                         reaction.name,
                         evasion
                     ));
+                    if bonus_evasion != reaction.effect.bonus_evasion {
+                        detail_lines.push(format!(
+                            "    |<faded>{} {:+} from weapon matchup|",
+                            reaction.name,
+                            bonus_evasion as i32 - reaction.effect.bonus_evasion as i32
+                        ));
+                    }
                     /*
                     let p_hit =
                         probability_of_d20_reaching(evasion - attack_modifier, attack_bonus);
@@ -2046,7 +3654,7 @@ impl CoreGame {
 
             let bonus_armor = reaction.effect.bonus_armor;
             if bonus_armor > 0 {
-                if game.is_some() {
+                if describe {
                     detail_lines.push(format!(
                         "  Armor: {} +{} |<faded>({})| = {}",
                         armor_value,
@@ -2067,13 +3675,11 @@ impl CoreGame {
              */
         }
 
-        let unmodified_roll = mode
-            .simulated_roll()
-            .unwrap_or(roll_d20_with_advantage(attack_bonus.advantage));
+        let unmodified_roll = mode.roll_d20_with_advantage(attack_bonus.advantage);
         let roll_result = (unmodified_roll + attack_modifier) as i32 + attack_bonus.flat_amount;
         let final_result = roll_result - evasion as i32;
 
-        if game.is_some() {
+        if describe {
             if let Some(description) = roll_description(attack_bonus.advantage) {
                 detail_lines.push(description);
             }
@@ -2120,7 +3726,7 @@ impl CoreGame {
             armor_str.push_str(&format!(" -{} |<faded>({})|", penetration, label));
         }
 
-        if game.is_some() {
+        if describe {
             // TODO: Include details here about where this attack bonus comes from
             let attack_bonus_str = if attack_bonus.flat_amount > 0 {
                 format!("(+{}) ", attack_bonus.flat_amount)
@@ -2136,12 +3742,20 @@ impl CoreGame {
             detail_lines.push(format!(
                 "{} - {} (|<shield>|<stat>Evasion|) = |<value>{}|",
                 roll_result, evasion, final_result
-            ))
+            ));
         }
 
         let weapon = attacker.weapon(hand_type).unwrap();
+        // A broken weapon loses its special effect until it's repaired
+        let weapon_on_true_hit = weapon
+            .on_true_hit
+            .filter(|_| !attacker.is_weapon_broken(hand_type));
+        let weapon_on_crit = weapon
+            .on_crit
+            .filter(|_| !attacker.is_weapon_broken(hand_type));
         let outcome = {
             let mut on_true_hit_effect = None;
+            let mut on_crit_effect = None;
             let dmg_override = ability_attack_effect.map(|e| e.override_damage).flatten();
             let mut dmg_str = "  Damage: ".to_string();
             let mut dmg_calculation;
@@ -2151,6 +3765,10 @@ impl CoreGame {
             } else {
                 dmg_calculation = weapon.damage as i32;
                 dmg_str.push_str(&format!("{} |<faded>({})|", dmg_calculation, weapon.name));
+                if attacker.is_weapon_broken(hand_type) {
+                    dmg_str.push_str(&format!(" -{} |<faded>(broken)|", BROKEN_WEAPON_PENALTY));
+                    dmg_calculation -= BROKEN_WEAPON_PENALTY as i32;
+                }
                 /*
                 // Versatile is confusing; especially as it's not reflected graphically
                 if matches!(weapon.grip, WeaponGrip::Versatile)
@@ -2163,6 +3781,16 @@ impl CoreGame {
                  */
             }
 
+            if let Some(charge_bonus) = ability_attack_effect
+                .map(|e| e.charge_bonus_damage)
+                .filter(|bonus| *bonus > 0)
+            {
+                // Can at most double the weapon's damage
+                let bonus_dmg = charge_bonus.min(weapon.damage);
+                dmg_str.push_str(&format!(" +{} |<faded>(charge)|", bonus_dmg));
+                dmg_calculation += bonus_dmg as i32;
+            }
+
             let mut graze_improvement = None;
             let mut crit_improvement = None;
 
@@ -2180,37 +3808,79 @@ impl CoreGame {
                 }
             }
 
-            if attacker
+            if attacker.has_condition(&Condition::Hidden) {
+                dmg_str.push_str(&format!(" +{} |<faded>(Hidden)|", HIDDEN_BONUS_DAMAGE));
+                dmg_calculation += HIDDEN_BONUS_DAMAGE as i32;
+            }
+
+            if attacker
+                .known_passive_skills
+                .borrow()
+                .contains(&PassiveSkill::Honorless)
+            {
+                let bonus_dmg = 1;
+                if is_target_flanked(attacker.pos(), defender) {
+                    dmg_str.push_str(&format!(" +{} |<faded>(Honorless)|", bonus_dmg));
+                    dmg_calculation += bonus_dmg;
+                }
+            }
+
+            // Only for attacks on the attacker's own turn. Predictions leave it out, since they
+            // can't know which attacks will come before this one.
+            let is_own_turn = game.is_some_and(|game| game.active_character_id == attacker.id());
+            let momentum = attacker.consecutive_hits();
+            if is_own_turn
+                && momentum > 0
+                && attacker
+                    .known_passive_skills
+                    .borrow()
+                    .contains(&PassiveSkill::Momentum)
+            {
+                dmg_str.push_str(&format!(" +{} |<faded>(Momentum)|", momentum));
+                dmg_calculation += momentum as i32;
+            }
+
+            if defender
                 .known_passive_skills
                 .borrow()
-                .contains(&PassiveSkill::Honorless)
+                .contains(&PassiveSkill::Berserker)
             {
                 let bonus_dmg = 1;
-                if is_target_flanked(attacker.pos(), defender) {
-                    dmg_str.push_str(&format!(" +{} |<faded>(Honorless)|", bonus_dmg));
-                    dmg_calculation += bonus_dmg;
-                }
+                dmg_str.push_str(&format!(" +{} |<faded>(Berserker)|", bonus_dmg));
+                dmg_calculation += bonus_dmg;
             }
 
             if !armor_penetrators.is_empty() {
                 detail_lines.push(format!("  Armor: {} = {}", armor_str, armor_value));
             }
             if armor_value > 0 {
-                dmg_str.push_str(&format!(" -{armor_value} |<faded>(armor)|"));
+                dmg_str.push_str(&format!(
+                    " -{armor_value} |<faded>({} armor)|",
+                    damage_type.name()
+                ));
                 dmg_calculation -= armor_value as i32;
             }
 
             //  <=5: graze
             // 6-15: hit
-            // >=16: crit
+            // >=16: crit (unless the weapon crits more easily)
             let hit_type = if final_result <= 5 {
                 HitType::Graze
-            } else if final_result <= 15 {
+            } else if final_result < weapon.crit_threshold {
                 HitType::Regular
             } else {
                 HitType::Critical
             };
 
+            if is_own_turn {
+                match hit_type {
+                    HitType::Graze => attacker.set_consecutive_hits(0),
+                    HitType::Regular | HitType::Critical => {
+                        attacker.set_consecutive_hits(momentum + 1)
+                    }
+                }
+            }
+
             match hit_type {
                 HitType::Graze => {
                     if let Some(source) = graze_improvement {
@@ -2223,19 +3893,25 @@ impl CoreGame {
                     detail_lines.push("  Graze |<faded>(5 or lower)|".to_string());
                 }
                 HitType::Regular => {
-                    on_true_hit_effect = weapon.on_true_hit;
-                    detail_lines.push("  Hit |<faded>(6-15)|".to_string());
+                    on_true_hit_effect = weapon_on_true_hit;
+                    detail_lines.push(format!("  Hit |<faded>(6-{})|", weapon.crit_threshold - 1));
                 }
                 HitType::Critical => {
+                    let mut percent = weapon.crit_multiplier_percent;
                     if let Some(source) = crit_improvement {
-                        dmg_str.push_str(&format!(" +75% |<faded>(crit, {})|", source));
-                        dmg_calculation += (dmg_calculation as f32 * 0.75).ceil() as i32;
+                        percent += 25;
+                        dmg_str.push_str(&format!(" +{}% |<faded>(crit, {})|", percent, source));
                     } else {
-                        dmg_str.push_str(" +50% |<faded>(crit)|");
-                        dmg_calculation += (dmg_calculation as f32 * 0.5).ceil() as i32;
+                        dmg_str.push_str(&format!(" +{}% |<faded>(crit)|", percent));
                     }
-                    on_true_hit_effect = weapon.on_true_hit;
-                    detail_lines.push("  Critical Hit |<faded>(16 or higher)|".to_string());
+                    dmg_calculation +=
+                        (dmg_calculation as f32 * percent as f32 / 100.0).ceil() as i32;
+                    on_true_hit_effect = weapon_on_true_hit;
+                    on_crit_effect = weapon_on_crit;
+                    detail_lines.push(format!(
+                        "  Critical Hit |<faded>({} or higher)|",
+                        weapon.crit_threshold
+                    ));
                 }
             }
 
@@ -2247,32 +3923,66 @@ impl CoreGame {
 
             let mut actual_health_lost = 0;
 
-            if let Some(game) = game {
+            if describe {
                 dmg_str.push_str(&format!(" = |<value>{damage}|"));
                 detail_lines.push(dmg_str);
+            }
+
+            if let Some(game) = game {
                 actual_health_lost = game.perform_losing_health(defender, damage);
+                if actual_health_lost > 0 {
+                    defender.record_damage_from(attacker);
+                }
+
+                if weapon.lifesteal_percent > 0 && actual_health_lost > 0 {
+                    // Capped at the missing health, so that a healthy attacker isn't "healed" for 0
+                    let missing_health = attacker.health.max() - attacker.health.current();
+                    let healing = (actual_health_lost * weapon.lifesteal_percent)
+                        .div_ceil(100)
+                        .min(missing_health);
+                    if healing > 0 {
+                        let healed = game.perform_gain_health(attacker, healing);
+                        detail_lines.push(format!(
+                            "  {} healed for |<value>{}| |<faded>(lifesteal)|",
+                            attacker.name, healed
+                        ));
+                    }
+                }
+
+                let thorns = defender.conditions.borrow().get_stacks(&Condition::Thorns);
+                if thorns > 0 && actual_health_lost > 0 && weapon.is_melee() {
+                    // Reflected damage is not an attack, so it doesn't trigger the attacker's thorns
+                    let reflected = (actual_health_lost * thorns).div_ceil(100);
+                    let reflected = game.perform_losing_health(attacker, reflected);
+                    detail_lines.push(format!(
+                        "  {} took |<value>{}| damage |<faded>(Thorns)|",
+                        attacker.name, reflected
+                    ));
+                }
             }
 
             let mut applied_effects = vec![];
 
             if let Some(game) = game {
-                if let Some(effect) = on_true_hit_effect {
+                for (effect, label) in [(on_true_hit_effect, "true hit"), (on_crit_effect, "crit")]
+                {
                     match effect {
-                        AttackHitEffect::Apply(effect) => {
-                            let (applied, log_line, _damage) = game.perform_effect_application(
+                        Some(AttackHitEffect::Apply(effect)) => {
+                            let (_applied, log_line, _damage) = game.perform_effect_application(
                                 effect,
                                 Some(attacker),
                                 None,
                                 defender,
                             );
-                            detail_lines.push(format!("{} |<faded>(true hit)|", log_line))
+                            detail_lines.push(format!("{} |<faded>({})|", log_line, label))
                         }
-                        AttackHitEffect::SkipExertion => skip_attack_exertion = true,
+                        Some(AttackHitEffect::SkipExertion) => skip_attack_exertion = true,
+                        None => {}
                     }
                 }
 
                 if let Some(effect) = ability_attack_effect.map(|e| e.on_hit).flatten() {
-                    let (applied, log_line, _damage) =
+                    let (_applied, log_line, _damage) =
                         game.perform_effect_application(effect, Some(attacker), None, defender);
                     detail_lines.push(log_line);
                 }
@@ -2291,11 +4001,12 @@ impl CoreGame {
                                 ) => {
                                     let mut resist = false;
                                     if let Some(defense_type) = defense_type {
-                                        let defense = defender.defense(defense_type);
+                                        let (used_defense, defense) =
+                                            defender.resolve_defense(defense_type);
                                         detail_lines.push(format!(
                                             "{} vs {}={}",
                                             roll_result,
-                                            defense_type.name(),
+                                            used_defense.name(),
                                             defense
                                         ));
                                         if roll_result < defense as i32 {
@@ -2318,6 +4029,21 @@ impl CoreGame {
                                         log_line
                                     }
                                 }
+                                AttackEnhancementOnHitEffect::Disarm(defense_type) => {
+                                    let (used_defense, defense) =
+                                        defender.resolve_defense(defense_type);
+                                    detail_lines.push(format!(
+                                        "{} vs {}={}",
+                                        roll_result,
+                                        used_defense.name(),
+                                        defense
+                                    ));
+                                    if roll_result < defense as i32 {
+                                        "Resist".to_string()
+                                    } else {
+                                        game.perform_disarm(defender)
+                                    }
+                                }
                             };
 
                             detail_lines.push(format!("{} |<faded>({})|", log_line, name))
@@ -2340,7 +4066,7 @@ impl CoreGame {
 
                     if let Some(arrow) = used_arrow {
                         if let Some(apply_effect) = arrow.on_damage_apply {
-                            let (applied, log_line, _damage) = game.perform_effect_application(
+                            let (_applied, log_line, _damage) = game.perform_effect_application(
                                 apply_effect,
                                 Some(attacker),
                                 None,
@@ -2386,6 +4112,7 @@ impl CoreGame {
                     let area_target_outcomes = Self::perform_ability_area_effect(
                         arrow.name,
                         AbilityRoll::RolledWithSpellModifier {
+                            dice_roll: unmodified_roll,
                             result: roll_result,
                             line: "".to_string(),
                         },
@@ -2400,6 +4127,44 @@ impl CoreGame {
                 }
             }
 
+            if weapon.is_melee()
+                && matches!(outcome.hit_type, HitType::Regular | HitType::Critical)
+                && outcome.damage > 0
+                && attacker
+                    .known_passive_skills
+                    .borrow()
+                    .contains(&PassiveSkill::Cleave)
+            {
+                // Only one extra enemy is hit; preferably one that's close to dying
+                let cleave_target = game
+                    .characters
+                    .iter()
+                    .filter(|ch| {
+                        ch.id() != defender.id()
+                            && ch.player_controlled() != attacker.player_controlled()
+                            && !ch.is_dead()
+                            && are_entities_within_melee(defender.pos(), ch.pos())
+                    })
+                    .min_by_key(|ch| ch.health.current());
+
+                if let Some(cleave_target) = cleave_target {
+                    let cleave_damage = outcome.damage / 2;
+                    game.perform_losing_health(cleave_target, cleave_damage);
+                    detail_lines.push(format!(
+                        "{} took {} damage |<faded>(Cleave)|",
+                        cleave_target.name, cleave_damage
+                    ));
+                    area_outcomes.get_or_insert_with(Vec::new).push((
+                        cleave_target.id(),
+                        AbilityTargetOutcome::HitEnemy {
+                            damage: Some(cleave_damage),
+                            applied_effects: vec![],
+                            hit_type: outcome.hit_type,
+                        },
+                    ));
+                }
+            }
+
             if weapon.is_melee() {
                 if let Some(previously_engaged) = attacker.engagement_target.take() {
                     game.characters
@@ -2411,19 +4176,131 @@ impl CoreGame {
             }
         }
 
+        if game.is_some() && hand_type == HandType::OffHand && !skip_attack_exertion {
+            attacker.receive_condition(Condition::OffHandExertion, Some(1), None);
+        }
+
+        let is_flurry = ability_attack_effect.is_some_and(|e| e.flurry);
+        if game.is_some() && hand_type == HandType::MainHand && is_flurry && !skip_attack_exertion {
+            attacker.receive_condition(Condition::MainHandExertion, Some(1), None);
+        }
+
+        if game.is_some() && !skip_attack_exertion && attacker.wear_weapon(hand_type) {
+            detail_lines.push(format!("{}'s {} broke!", attacker.name, weapon.name));
+        }
+
+        if game.is_some() {
+            // Stealth only lasts for the first attack
+            attacker
+                .is_being_revealed
+                .set(attacker.has_condition(&Condition::Hidden));
+            // All of the accumulated aim goes into this attack
+            attacker.clear_condition(Condition::Aiming);
+        }
+
+        if game.is_some() && weapon.thrown {
+            // There's no notion of items lying on the ground, so the weapon is gone. Another copy
+            // can be drawn from the inventory by changing equipment.
+            attacker.hand(hand_type).set(Hand::default());
+            attacker.on_changed_equipment();
+            detail_lines.push(format!("{} threw the {}", attacker.name, weapon.name));
+        }
+
         AttackedEvent {
             attacker: attacker.id(),
             target: defender.id(),
             outcome,
             detail_lines,
             area_outcomes,
+            projectile: if weapon.is_melee() {
+                None
+            } else {
+                Some((attacker.pos(), defender.pos()))
+            },
+        }
+    }
+
+    fn perform_disarm(&self, character: &Character) -> String {
+        let Some(weapon) = character.weapon(HandType::MainHand) else {
+            return format!("{} has no weapon to lose", character.name);
+        };
+
+        // This also empties the hand of a two-handed weapon. Anything in the off-hand is kept.
+        character.hand(HandType::MainHand).set(Hand::default());
+        // There's no notion of items lying on the ground, so the weapon ends up in the inventory
+        // (if there's room for it)
+        let kept_weapon = character.try_gain_equipment(EquipmentEntry::Weapon(weapon));
+        character.on_changed_equipment();
+        character.receive_condition(Condition::Disarmed, None, None);
+
+        if kept_weapon {
+            format!("{} was disarmed", character.name)
+        } else {
+            format!(
+                "{} was disarmed, and lost the {}",
+                character.name, weapon.name
+            )
+        }
+    }
+
+    async fn perform_counterattack(&self, reactor_id: CharacterId, reaction: OnAttackedReaction) {
+        let reactor = self.characters.get_rc(reactor_id);
+        let attacker = self.characters.get_rc(self.active_character_id);
+
+        if reactor.is_dead() || attacker.is_dead() {
+            return;
         }
+        if !reactor.has_equipped_melee_weapon() {
+            return;
+        }
+        let (_range, reach) =
+            reactor.reaches_with_attack(HandType::MainHand, attacker.pos(), std::iter::empty());
+        if !matches!(
+            reach,
+            ActionReach::Yes | ActionReach::YesButDisadvantage(..)
+        ) {
+            return;
+        }
+
+        reactor.set_facing_toward(attacker.pos());
+        self.ui_handle_event(GameEvent::AttackWasInitiated {
+            actor: reactor_id,
+            target: attacker.id(),
+        })
+        .await;
+
+        // The counterattack is free (its cost was paid by the reaction), and it can't itself be
+        // reacted to, so there's no risk of an endless exchange.
+        let mut event = Self::perform_attack(
+            reactor,
+            HandType::MainHand,
+            &[],
+            attacker,
+            None,
+            0,
+            ActionPerformanceMode::Real(self),
+            None,
+        );
+        event.detail_lines.insert(
+            0,
+            format!(
+                "{} turned the attack aside and struck back |<faded>({})|",
+                reactor.name, reaction.name
+            ),
+        );
+        self.ui_handle_event(GameEvent::Attacked(event)).await;
     }
 
-    async fn perform_on_hit_reaction(&mut self, reactor_id: CharacterId, reaction: OnHitReaction) {
+    async fn perform_on_hit_reaction(
+        &mut self,
+        reactor_id: CharacterId,
+        reaction: OnHitReaction,
+        damage_taken: u32,
+    ) {
         let reactor = self.characters.get(reactor_id);
         reactor.action_points.spend(reaction.action_point_cost);
         reactor.stamina.spend(reaction.stamina_cost);
+        reactor.spend_mana(reaction.mana_cost);
         let reactor_name = reactor.name;
 
         match reaction.effect {
@@ -2451,7 +4328,7 @@ impl CoreGame {
                     let attacker = self.characters.get(self.active_character_id);
                     let reactor = self.characters.get(reactor_id);
                     let toughness = attacker.toughness();
-                    let roll = roll_d20_with_advantage(0);
+                    let roll = self.roll_d20_with_advantage(0);
                     let attack_mod = reactor.attack_modifier(HandType::MainHand);
                     let res = roll + attack_mod;
                     lines.push(format!(
@@ -2505,13 +4382,169 @@ impl CoreGame {
                 })
                 .await;
             }
+            OnHitReactionEffect::SpellReflect(percentage) => {
+                let (lines, damage) =
+                    self.perform_spell_reflect(reactor_id, percentage, damage_taken);
+
+                self.ui_handle_event(GameEvent::CharacterReactedToHit {
+                    main_line: format!("{} reacted with Spell reflect", reactor_name),
+                    detail_lines: lines,
+                    reactor: reactor_id,
+                    outcome: HitReactionOutcome {
+                        received_condition: None,
+                        offensive: None,
+                    },
+                })
+                .await;
+
+                if let Some(damage) = damage {
+                    self.ui_handle_event(GameEvent::CharacterTookDamage {
+                        character: self.active_character_id,
+                        amount: damage,
+                        source: DamageSource::Reflected,
+                    })
+                    .await;
+                }
+            }
+        }
+    }
+
+    /// Returns the detail lines and, unless the attacker resisted, the damage that was reflected
+    fn perform_spell_reflect(
+        &self,
+        reactor_id: CharacterId,
+        percentage: u32,
+        damage_taken: u32,
+    ) -> (Vec<String>, Option<u32>) {
+        let mut lines = vec![];
+
+        let attacker = self.characters.get(self.active_character_id);
+        let reactor = self.characters.get(reactor_id);
+        let will = attacker.will();
+        let roll = self.roll_d20_with_advantage(0);
+        let spell_mod = reactor.spell_modifier();
+        let res = roll + spell_mod;
+        lines.push(format!(
+            "Rolled: {} (+{} spell mod) = {}, vs will={}",
+            roll, spell_mod, res, will,
+        ));
+
+        if res < will {
+            lines.push("  Resisted!".to_string());
+            return (lines, None);
+        }
+
+        let reflected = (damage_taken * percentage).div_ceil(100);
+        // The reflected damage is not an attack, so it can't in turn be reacted to
+        let damage = self.perform_losing_health(attacker, reflected);
+        (lines, Some(damage))
+    }
+
+    /// Puts the surviving player characters back into a resting state once the fight is won.
+    /// Unlike a regular end of turn, this doesn't touch whose turn it is.
+    async fn end_fight_cleanup(&mut self) {
+        let mut survivors = vec![];
+        for character in self.player_characters() {
+            if character.is_summon() {
+                continue;
+            }
+            // Conditions that come from passive skills are re-applied below
+            character.conditions.borrow_mut().clear();
+            if character.health.current() == 0 {
+                // Downed party members get back on their feet once the fight is over
+                character.health.gain(1);
+            }
+            character.channeled_ability.set(None);
+            character.ability_cooldowns.borrow_mut().clear();
+            *character.terrain.borrow_mut() = None;
+            character
+                .known_actions
+                .borrow_mut()
+                .retain(|action| *action != BaseAction::UseAbility(STAND_UP));
+            character.stamina.set_to_max();
+            character.is_engaged_by.borrow_mut().clear();
+            character.engagement_target.set(None);
+            character.regain_full_movement();
+            character.update_player_encumbrance();
+            character.on_health_changed();
+            survivors.push(character.id());
         }
+
+        self.ui_handle_event(GameEvent::FightEnded { survivors })
+            .await;
     }
 
     async fn perform_end_of_turn_character(&mut self) {
+        self.perform_channeled_ability(self.active_character_id)
+            .await;
+
         let character = self.active_character();
         character.has_taken_a_turn_this_round.set(true);
+        character.set_consecutive_hits(0);
+        character.actions_this_turn.set(0);
+        character.has_used_free_equipment_change.set(false);
         let name = character.name;
+
+        if character.is_entering_guard_stance.take() {
+            let leftover_ap = character.action_points.current();
+            if leftover_ap > 0 {
+                character.action_points.spend(leftover_ap);
+                // Wears off at the start of the character's next turn
+                let ends_at = self.current_time() + self.round_length;
+                character.receive_condition(
+                    Condition::Guarding,
+                    Some(leftover_ap * GUARDING_EVASION_PER_AP),
+                    Some(ends_at),
+                );
+                self.log(format!(
+                    "{} is guarding (+{} evasion)",
+                    name,
+                    leftover_ap * GUARDING_EVASION_PER_AP
+                ))
+                .await;
+            }
+        }
+
+        if character.has_condition(&Condition::Panicked) {
+            // The tide may have turned since they panicked
+            character.clear_condition(Condition::Panicked);
+            if !self.perform_morale_check(character).await {
+                self.log(format!("{} regained their nerve", name)).await;
+            }
+        }
+
+        if character.is_downed() {
+            let bled_out = character
+                .conditions
+                .borrow_mut()
+                .lose_stacks(&Condition::Downed, 1);
+            if bled_out {
+                self.log(format!("{} succumbed to their wounds", name))
+                    .await;
+                character.conditions.borrow_mut().add(Condition::Dead);
+            }
+            return;
+        }
+
+        if character
+            .expires_at
+            .get()
+            .is_some_and(|expires_at| self.current_time() >= expires_at)
+        {
+            self.log(format!("{} vanished", name)).await;
+            // It's then removed like any dead character
+            character.clear_condition(Condition::Shielded);
+            self.perform_losing_health(character, character.health.current());
+            return;
+        }
+
+        if let Some(effect) = self
+            .ground_effect_under(character)
+            .and_then(|e| e.on_end_turn)
+        {
+            self.perform_ground_effect(character, effect).await;
+        }
+
         let conditions = &character.conditions;
 
         let bleed_stacks = conditions.borrow().get_stacks(&Condition::Bleeding);
@@ -2551,7 +4584,11 @@ impl CoreGame {
                     // TODO: if "melee" doesn't encompass diagonally adjacent, the spreading feels
                     // a bit too unlikely
                     other.id != character.id
+                        && other.flammable.get()
+                        && !other.is_immune_to(Condition::Burning)
                         && are_entities_within_melee(other.pos(), character.pos())
+                        // A wall between them stops the fire from spreading
+                        && self.has_line_of_sight(character.pos(), other.pos())
                 })
                 .collect();
             if !adj_others.is_empty() {
@@ -2587,6 +4624,44 @@ impl CoreGame {
             }
         }
 
+        let poison_stacks = conditions.borrow().get_stacks(&Condition::Poisoned);
+        if poison_stacks > 0 {
+            // Like spell damage, poison is not reduced by armor
+            let damage = self.perform_losing_health(character, poison_stacks);
+            self.ui_handle_event(GameEvent::CharacterTookDamage {
+                character: character.id(),
+                amount: damage,
+                source: DamageSource::Condition(Condition::Poisoned),
+            })
+            .await;
+            if conditions.borrow_mut().lose_stacks(&Condition::Poisoned, 1) {
+                self.log(format!("{} is no longer Poisoned", name)).await;
+            }
+        }
+
+        let regen_stacks = conditions.borrow().get_stacks(&Condition::Regenerating);
+        if regen_stacks > 0 && !character.is_dead() {
+            // Unlike direct healing, regeneration doesn't spill over into a shield
+            let missing_health = character.health.max() - character.health.current();
+            let health_gained =
+                self.perform_gain_health(character, regen_stacks.min(missing_health));
+            if health_gained > 0 {
+                self.ui_handle_event(GameEvent::CharacterGainedHealth {
+                    character: character.id(),
+                    amount: health_gained,
+                    source: Condition::Regenerating,
+                })
+                .await;
+            }
+            // Decays even when there's nothing left to heal
+            if conditions
+                .borrow_mut()
+                .lose_stacks(&Condition::Regenerating, 1)
+            {
+                self.log(format!("{} stopped Regenerating", name)).await;
+            }
+        }
+
         if conditions.borrow().has(&Condition::HealthPotionRecovering) {
             conditions
                 .borrow_mut()
@@ -2606,6 +4681,10 @@ impl CoreGame {
         if conditions.borrow_mut().remove(&Condition::Raging) {
             self.log(format!("{} stopped Raging", name)).await;
         }
+        if conditions.borrow_mut().remove(&Condition::Shielded) {
+            character.on_health_changed();
+            self.log(format!("{} lost their shield", name)).await;
+        }
         if conditions.borrow().has(&Condition::ArcaneSurge)
             && conditions
                 .borrow_mut()
@@ -2635,6 +4714,14 @@ impl CoreGame {
     }
 }
 
+fn immunity_line(receiver: &Character, condition: Condition) -> String {
+    format!(
+        "  {} is immune to |<keyword>{}|",
+        receiver.name,
+        condition.name()
+    )
+}
+
 fn roll_description(advantage: i32) -> Option<String> {
     match advantage.cmp(&0) {
         Ordering::Less => Some(format!(
@@ -2651,33 +4738,45 @@ pub enum MovementType {
     Regular,
     AbilityEngage,
     KnockedBack,
+    Pulled,
+    Teleported,
 }
 
 #[derive(Copy, Clone)]
 enum ActionPerformanceMode<'a> {
     Real(&'a CoreGame),
     SimulatedRoll(u32, &'a Characters),
+    // Like a simulated roll, but with the detail lines that a real action would produce
+    DryRun(u32, &'a Characters),
 }
 
 impl ActionPerformanceMode<'_> {
     fn characters(&self) -> &Characters {
         match self {
             ActionPerformanceMode::Real(core_game) => &core_game.characters,
-            ActionPerformanceMode::SimulatedRoll(_, characters) => characters,
+            ActionPerformanceMode::SimulatedRoll(_, characters)
+            | ActionPerformanceMode::DryRun(_, characters) => characters,
         }
     }
 
-    fn simulated_roll(&self) -> Option<u32> {
+    fn real_game(&self) -> Option<&CoreGame> {
         match self {
-            ActionPerformanceMode::Real(..) => None,
-            ActionPerformanceMode::SimulatedRoll(roll, _) => Some(*roll),
+            ActionPerformanceMode::Real(core_game) => Some(*core_game),
+            ActionPerformanceMode::SimulatedRoll(..) | ActionPerformanceMode::DryRun(..) => None,
         }
     }
 
-    fn real_game(&self) -> Option<&CoreGame> {
+    fn describes(&self) -> bool {
+        !matches!(self, ActionPerformanceMode::SimulatedRoll(..))
+    }
+
+    fn roll_d20_with_advantage(&self, advantage_level: i32) -> u32 {
         match self {
-            ActionPerformanceMode::Real(core_game) => Some(*core_game),
-            ActionPerformanceMode::SimulatedRoll(..) => None,
+            ActionPerformanceMode::Real(core_game) => {
+                core_game.roll_d20_with_advantage(advantage_level)
+            }
+            ActionPerformanceMode::SimulatedRoll(roll, _)
+            | ActionPerformanceMode::DryRun(roll, _) => *roll,
         }
     }
 }
@@ -2691,6 +4790,8 @@ pub struct AttackPrediction {
     pub details: Vec<(&'static str, Goodness)>,
     pub graze_chance: f32,
     pub crit_chance: f32,
+    // The chance of triggering the weapon's on-crit effect, if it has one
+    pub crit_effect_chance: Option<f32>,
 }
 
 pub struct AbilityPrediction {
@@ -2700,10 +4801,15 @@ pub struct AbilityPrediction {
 #[derive(Debug, Clone)]
 pub struct TargetPrediction {
     pub damage: DamageInterval,
+    pub avg_damage: f32,
     pub is_buff: bool,
     pub details: Vec<(&'static str, Goodness)>,
+    // The chance that the target is affected at all (i.e. doesn't resist)
+    pub hit_chance: Option<f32>,
     pub graze_chance: Option<f32>,
     pub crit_chance: Option<f32>,
+    // The effects that the target receives if it's affected
+    pub applied_effects: Vec<ApplyEffect>,
 }
 
 impl From<AttackPrediction> for TargetPrediction {
@@ -2713,10 +4819,13 @@ impl From<AttackPrediction> for TargetPrediction {
                 min: value.min_damage,
                 max: value.max_damage,
             },
+            avg_damage: value.avg_damage,
             is_buff: false,
             details: value.details,
+            hit_chance: Some(value.percentage_chance_deal_damage as f32 / 100.0),
             graze_chance: Some(value.graze_chance),
             crit_chance: Some(value.crit_chance),
+            applied_effects: vec![],
         }
     }
 }
@@ -2762,6 +4871,25 @@ pub fn predict_ability(
                     }
                 }
 
+                let is_primary_target = matches!(
+                    selected_target,
+                    ActionTarget::Character(id, ..) if *id == target_id
+                );
+
+                let target_char = characters.get(target_id);
+                let mut applied_effects =
+                    potential_applied_effects(ability, enhancements, is_primary_target);
+                let num_effects = applied_effects.len();
+                applied_effects.retain(|effect| match effect {
+                    ApplyEffect::Condition(apply_condition) => {
+                        !target_char.is_immune_to(apply_condition.condition)
+                    }
+                    _ => true,
+                });
+                if applied_effects.len() < num_effects {
+                    details.push(("Immune", Goodness::Bad));
+                }
+
                 targets.insert(
                     target_id,
                     TargetPrediction {
@@ -2769,16 +4897,28 @@ pub fn predict_ability(
                             min: result.damage,
                             max: 0, // filled in later
                         },
+                        avg_damage: 0.0, // filled in later
                         is_buff: result.is_buff,
                         details,
+                        hit_chance: None,   // filled in later
                         graze_chance: None, // filled in later
                         crit_chance: None,  // filled in later
+                        applied_effects,
                     },
                 );
             } else if unmodified_roll == 20 {
                 targets.get_mut(&target_id).unwrap().damage.max = result.damage;
             }
 
+            if let Some(target) = targets.get_mut(&target_id) {
+                target.avg_damage += result.damage as f32 / 20.0;
+                if !result.resisted {
+                    target.hit_chance.get_or_insert_with(|| {
+                        probability_of_d20_reaching(unmodified_roll, DiceRollBonus::none())
+                    });
+                }
+            }
+
             match result.hit_type {
                 Some(HitType::Regular) => {
                     let graze_chance = &mut targets.get_mut(&target_id).unwrap().graze_chance;
@@ -2797,9 +4937,88 @@ pub fn predict_ability(
         }
     }
 
+    for prediction in targets.values_mut() {
+        // Not even a natural 20 gets through
+        prediction.hit_chance.get_or_insert(0.0);
+    }
+
     AbilityPrediction { targets }
 }
 
+// Ability prediction doesn't run the code that applies effects, so instead we list the effects
+// that the ability (with its enhancements) applies on a successful hit.
+fn potential_applied_effects(
+    ability: Ability,
+    enhancements: &[AbilityEnhancement],
+    is_primary_target: bool,
+) -> Vec<ApplyEffect> {
+    fn from_ability_effect(effect: AbilityEffect) -> Vec<ApplyEffect> {
+        match effect {
+            AbilityEffect::Negative(AbilityNegativeEffect::Spell(spell)) => {
+                spell.on_hit.iter().flatten().flatten().copied().collect()
+            }
+            AbilityEffect::Negative(AbilityNegativeEffect::PerformAttack(attack)) => {
+                attack.on_hit.into_iter().collect()
+            }
+            AbilityEffect::Positive(positive) => {
+                positive.apply.iter().flatten().flatten().copied().collect()
+            }
+        }
+    }
+
+    let mut effects = match ability.target {
+        AbilityTarget::Enemy {
+            effect,
+            impact_circle,
+            ..
+        } => {
+            if is_primary_target {
+                from_ability_effect(AbilityEffect::Negative(effect))
+            } else {
+                impact_circle
+                    .map(|(_range, _acquisition, effect)| {
+                        from_ability_effect(AbilityEffect::Negative(effect))
+                    })
+                    .unwrap_or_default()
+            }
+        }
+        AbilityTarget::Ally { effect, .. } => from_ability_effect(AbilityEffect::Positive(effect)),
+        AbilityTarget::Area { area_effect, .. } => from_ability_effect(area_effect.effect),
+        AbilityTarget::Teleport { .. } => vec![],
+        AbilityTarget::Trap { effect, .. } => from_ability_effect(AbilityEffect::Negative(
+            AbilityNegativeEffect::Spell(effect),
+        )),
+        AbilityTarget::None {
+            self_area,
+            self_effect,
+            ..
+        } => {
+            if is_primary_target {
+                self_effect
+                    .map(|effect| from_ability_effect(AbilityEffect::Positive(effect)))
+                    .unwrap_or_default()
+            } else {
+                self_area
+                    .map(|area| from_ability_effect(area.effect))
+                    .unwrap_or_default()
+            }
+        }
+    };
+
+    for enhancement in enhancements {
+        if let Some(spell_effect) = enhancement.spell_effect {
+            let on_hit = if is_primary_target {
+                spell_effect.target_on_hit
+            } else {
+                spell_effect.area_on_hit
+            };
+            effects.extend(on_hit.iter().flatten().flatten().copied());
+        }
+    }
+
+    effects
+}
+
 pub fn predict_attack(
     characters: &Characters,
     attacker: &Rc<Character>,
@@ -2809,10 +5028,8 @@ pub fn predict_attack(
     reaction: Option<(CharacterId, OnAttackedReaction)>,
     ability_roll_modifier: i32,
 ) -> AttackPrediction {
-    let mut damage_outcomes = vec![];
     let mut min_dmg = None;
     let mut max_dmg = 0;
-    let mut percentage_deal_damage = 0;
 
     let mut advantage = 0;
 
@@ -2824,17 +5041,22 @@ pub fn predict_attack(
             advantage += adv;
         }
     }
-    for (label, contributor) in defender.incoming_attack_bonuses(reaction.map(|(_id, r)| r)) {
+    let has_cover = has_cover_against(attacker, hand_type, defender);
+    for (label, contributor) in
+        defender.incoming_attack_bonuses(reaction.map(|(_id, r)| r), has_cover)
+    {
         details.push((label, contributor.goodness()));
         if let RollBonusContributor::Advantage(adv) = contributor {
             advantage += adv;
         }
     }
 
-    let mut regular_hit_threshold = 21;
-    let mut crit_threshold = 21;
+    let roll_distribution = outcome_distribution(DiceRollBonus::from_advantage(advantage));
+    let mut hit_chance = 0.0;
+    let mut crit_chance = 0.0;
+    let mut chance_deal_damage = 0.0;
+    let mut avg_damage = 0.0;
 
-    // TODO: The average doesn't account for advantage!
     // TODO: This could be expensive if we are performing non-negligible calculations in perform_attack
     // (like checking wall collisions for ranged attacks?)
     for unmodified_roll in 1..=20 {
@@ -2853,57 +5075,225 @@ pub fn predict_attack(
             damage, hit_type, ..
         } = event.outcome;
 
+        let p = roll_distribution[unmodified_roll as usize];
         match hit_type {
             HitType::Graze => {}
-            HitType::Regular => regular_hit_threshold = regular_hit_threshold.min(unmodified_roll),
-            HitType::Critical => crit_threshold = crit_threshold.min(unmodified_roll),
+            HitType::Regular => hit_chance += p,
+            HitType::Critical => {
+                hit_chance += p;
+                crit_chance += p;
+            }
         }
 
         if min_dmg.is_none() {
             min_dmg = Some(damage);
         }
         max_dmg = damage;
-        if damage > 0 && percentage_deal_damage == 0 {
-            percentage_deal_damage = (21 - unmodified_roll) * 100 / 20;
+        if damage > 0 {
+            chance_deal_damage += p;
         }
+        avg_damage += p * damage as f32;
+    }
+
+    let percentage_deal_damage = (chance_deal_damage * 100.0).round() as u32;
+    let graze_chance = 1.0 - hit_chance;
+
+    let crit_effect_chance = attacker
+        .weapon(hand_type)
+        .and_then(|weapon| weapon.on_crit)
+        .filter(|_| !attacker.is_weapon_broken(hand_type))
+        .map(|_| crit_chance);
+    if crit_effect_chance.is_some_and(|chance| chance > 0.0) {
+        details.push(("Crit effect", Goodness::Good));
+    }
+
+    AttackPrediction {
+        percentage_chance_deal_damage: percentage_deal_damage,
+        min_damage: min_dmg.unwrap(),
+        max_damage: max_dmg,
+        avg_damage,
+        details,
+        graze_chance,
+        crit_chance,
+        crit_effect_chance,
+    }
+}
+
+impl CoreGame {
+    /// The reaction that the defender could use to make the attack as likely as possible to miss,
+    /// among the ones they can currently afford.
+    pub fn best_evasion_reaction(
+        attacker: &Character,
+        hand_type: HandType,
+        defender: &Character,
+    ) -> Option<OnAttackedReaction> {
+        let is_within_melee = within_meele(attacker.pos(), defender.pos());
+        defender
+            .usable_on_attacked_reactions(is_within_melee, true)
+            .into_iter()
+            .filter(|reaction| defender.is_reaction_effective_against(*reaction, attacker.pos()))
+            .map(|reaction| {
+                let bonus = reaction_bonus_evasion(attacker, hand_type, defender, reaction);
+                (reaction, bonus)
+            })
+            .filter(|(_reaction, bonus)| *bonus > 0)
+            .max_by_key(|(_reaction, bonus)| *bonus)
+            .map(|(reaction, _bonus)| reaction)
+    }
 
-        damage_outcomes.push(damage);
+    /// The chance to hit if the defender doesn't react, and (if they are able to) the chance to
+    /// hit if they react in the way that is worst for the attacker.
+    pub fn hit_chance_range(
+        attacker: &Character,
+        hand_type: HandType,
+        defender: &Character,
+        enhancements: &[(&'static str, AttackEnhancementEffect)],
+    ) -> (f32, Option<(OnAttackedReaction, f32)>) {
+        let optimistic = prob_attack_hit(attacker, hand_type, defender, enhancements, None);
+        let pessimistic =
+            Self::best_evasion_reaction(attacker, hand_type, defender).map(|reaction| {
+                let chance =
+                    prob_attack_hit(attacker, hand_type, defender, enhancements, Some(reaction));
+                (reaction, chance)
+            });
+        (optimistic, pessimistic)
     }
 
-    let avg_damage = damage_outcomes.iter().map(|dmg| *dmg as f32).sum::<f32>() / 20.0;
+    /// Every term that affects an attack, from the attacker's point of view, followed by the
+    /// resulting dice targets. Each entry is (label, goodness, value).
+    pub fn explain_attack(
+        attacker: &Character,
+        hand_type: HandType,
+        defender: &Character,
+        enhancements: &[(&'static str, AttackEnhancementEffect)],
+        reaction: Option<OnAttackedReaction>,
+    ) -> Vec<(String, Goodness, String)> {
+        fn contributor_value(contributor: RollBonusContributor) -> String {
+            match contributor {
+                RollBonusContributor::Advantage(n) if n > 1 => format!("advantage x{}", n),
+                RollBonusContributor::Advantage(n) if n < -1 => format!("disadvantage x{}", -n),
+                RollBonusContributor::Advantage(n) if n > 0 => "advantage".to_string(),
+                RollBonusContributor::Advantage(_) => "disadvantage".to_string(),
+                RollBonusContributor::FlatAmount(n) => format!("{:+}", n),
+                // Already part of the attack modifier or the defense
+                RollBonusContributor::OtherNegative | RollBonusContributor::OtherPositive => {
+                    "".to_string()
+                }
+            }
+        }
+
+        let mut terms = vec![];
+        for (label, contributor) in
+            attacker.outgoing_attack_bonuses(hand_type, enhancements, defender)
+        {
+            terms.push((
+                label.to_string(),
+                contributor.goodness(),
+                contributor_value(contributor),
+            ));
+        }
+        let has_cover = has_cover_against(attacker, hand_type, defender);
+        for (label, contributor) in defender.incoming_attack_bonuses(reaction, has_cover) {
+            terms.push((
+                format!("Target: {}", label),
+                contributor.goodness(),
+                contributor_value(contributor),
+            ));
+        }
+
+        let mut armor = defender
+            .protection_against(attacker.weapon(hand_type).map(|weapon| weapon.damage_type));
+        for (name, effect) in enhancements {
+            if effect.armor_penetration > 0 {
+                terms.push((
+                    name.to_string(),
+                    Goodness::Good,
+                    format!("-{} armor", effect.armor_penetration),
+                ));
+                armor = armor.saturating_sub(effect.armor_penetration);
+            }
+        }
+
+        let mut evasion = defender.evasion() + cover_bonus_evasion(attacker, hand_type, defender);
+        if let Some(reaction) = reaction {
+            evasion += reaction_bonus_evasion(attacker, hand_type, defender, reaction);
+        }
+        let attack_modifier = attacker.attack_modifier(hand_type);
+        let bonus = attack_roll_bonus(attacker, hand_type, defender, enhancements, reaction);
 
-    let hit_chance = probability_of_d20_reaching(
-        regular_hit_threshold,
-        DiceRollBonus::from_advantage(advantage),
-    );
+        terms.push((
+            "Attack modifier".to_string(),
+            Goodness::Neutral,
+            format!("+{}", attack_modifier),
+        ));
+        terms.push((
+            "Target evasion".to_string(),
+            Goodness::Neutral,
+            evasion.to_string(),
+        ));
+        terms.push((
+            "Target armor".to_string(),
+            Goodness::Neutral,
+            armor.to_string(),
+        ));
 
-    let graze_chance = 1.0 - hit_chance;
-    let crit_chance =
-        probability_of_d20_reaching(crit_threshold, DiceRollBonus::from_advantage(advantage));
+        // The d20 needs to land on this value or above (before advantage is considered)
+        let dice_target = |defense: u32| {
+            let target = defense.saturating_sub(attack_modifier) as i32 - bonus.flat_amount;
+            let mut s = format!("{}+", target.clamp(1, 21));
+            match bonus.advantage {
+                0 => {}
+                n if n > 0 => s.push_str(" (advantage)"),
+                _ => s.push_str(" (disadvantage)"),
+            }
+            s
+        };
+        terms.push((
+            "To hit".to_string(),
+            Goodness::Neutral,
+            dice_target(evasion),
+        ));
+        terms.push((
+            "Armored hit".to_string(),
+            Goodness::Neutral,
+            dice_target(evasion + armor),
+        ));
 
-    AttackPrediction {
-        percentage_chance_deal_damage: percentage_deal_damage,
-        min_damage: min_dmg.unwrap(),
-        max_damage: max_dmg,
-        avg_damage,
-        details,
-        graze_chance,
-        crit_chance,
+        terms
     }
 }
 
 #[derive(Debug)]
 enum AbilityRoll {
-    RolledWithSpellModifier { result: i32, line: String },
-    RolledWithAttackModifier { result: i32, line: String },
-    WillRollDuringAttack { bonus: i32 },
+    RolledWithSpellModifier {
+        dice_roll: u32,
+        result: i32,
+        line: String,
+    },
+    RolledWithAttackModifier {
+        dice_roll: u32,
+        result: i32,
+        line: String,
+    },
+    WillRollDuringAttack {
+        bonus: i32,
+    },
 }
 
 impl AbilityRoll {
     fn actual_roll(&self) -> Option<(i32, &str)> {
         match self {
-            AbilityRoll::RolledWithSpellModifier { result, line } => Some((*result, line)),
-            AbilityRoll::RolledWithAttackModifier { result, line } => Some((*result, line)),
+            AbilityRoll::RolledWithSpellModifier { result, line, .. } => Some((*result, line)),
+            AbilityRoll::RolledWithAttackModifier { result, line, .. } => Some((*result, line)),
+            AbilityRoll::WillRollDuringAttack { .. } => None,
+        }
+    }
+
+    /// The unmodified d20
+    fn dice_roll(&self) -> Option<u32> {
+        match self {
+            AbilityRoll::RolledWithSpellModifier { dice_roll, .. } => Some(*dice_roll),
+            AbilityRoll::RolledWithAttackModifier { dice_roll, .. } => Some(*dice_roll),
             AbilityRoll::WillRollDuringAttack { .. } => None,
         }
     }
@@ -2944,9 +5334,24 @@ pub enum GameEvent {
         reactor: CharacterId,
         outcome: HitReactionOutcome,
     },
+    CharacterIntercepted {
+        protector: CharacterId,
+        victim: CharacterId,
+    },
+    CharacterCounterspelled {
+        reactor: CharacterId,
+        caster: CharacterId,
+        ability_name: &'static str,
+        success: bool,
+        detail_lines: Vec<String>,
+    },
     CharacterReactedWithOpportunityAttack {
         reactor: CharacterId,
     },
+    CharacterReactedWithOverwatch {
+        reactor: CharacterId,
+        target: CharacterId,
+    },
     AttackWasInitiated {
         actor: CharacterId,
         target: CharacterId,
@@ -2963,6 +5368,7 @@ pub enum GameEvent {
         user: CharacterId,
         consumable: Consumable,
         detail_lines: Vec<String>,
+        area_outcome: Option<AbilityAreaOutcome>,
     },
     CharactersDying {
         characters: Vec<CharacterId>,
@@ -2983,6 +5389,11 @@ pub enum GameEvent {
         amount: u32,
         source: DamageSource,
     },
+    CharacterGainedHealth {
+        character: CharacterId,
+        amount: u32,
+        source: Condition,
+    },
     CharacterReceivedCondition {
         character: CharacterId,
         condition: Condition,
@@ -2993,19 +5404,83 @@ pub enum GameEvent {
     CharacterGainedAP {
         character: CharacterId,
     },
+    FightEnded {
+        survivors: Vec<CharacterId>,
+    },
+    LootDropped {
+        position: Position,
+        money: u32,
+        deposited: Vec<EquipmentEntry>,
+        left_on_ground: Vec<EquipmentEntry>,
+    },
+    LootPickedUp {
+        character: CharacterId,
+        position: Position,
+        items: Vec<EquipmentEntry>,
+        remaining_on_ground: bool,
+    },
+    GroundEffectsChanged {
+        positions: Vec<Position>,
+    },
+    // Only the traps that the player side has placed
+    TrapsChanged {
+        positions: Vec<Position>,
+    },
+    TrapTriggered {
+        victim: CharacterId,
+        position: Position,
+        ability_name: &'static str,
+        damage: Option<u32>,
+        detail_lines: Vec<String>,
+    },
+    ArrowsDepleted {
+        character: CharacterId,
+    },
+    ChannelBroken {
+        character: CharacterId,
+        ability: Ability,
+    },
+    CharacterGainedShield {
+        character: CharacterId,
+        amount: u32,
+    },
+    ShieldAbsorbedDamage {
+        character: CharacterId,
+        amount: u32,
+    },
+    CharacterRevealed {
+        character: CharacterId,
+    },
+    ConditionsDispelled {
+        character: CharacterId,
+        conditions: Vec<Condition>,
+    },
+    CharacterDowned {
+        character: CharacterId,
+    },
+    CharacterRevived {
+        character: CharacterId,
+    },
+    CharacterSummoned {
+        character: Rc<Character>,
+        summoner: CharacterId,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum DamageSource {
     Condition(Condition),
     KnockbackCollision,
+    Reflected,
 }
 
 impl DamageSource {
     pub fn name(&self) -> &'static str {
         match self {
+            DamageSource::Condition(Condition::Poisoned) => "Poison",
             DamageSource::Condition(condition) => condition.name(),
             DamageSource::KnockbackCollision => "Collision",
+            DamageSource::Reflected => "Spell reflect",
         }
     }
 }
@@ -3017,6 +5492,8 @@ pub struct AbilityResolvedEvent {
     pub area_outcome: Option<AbilityAreaOutcome>,
     pub ability: Ability,
     pub detail_lines: Vec<String>,
+    // For abilities that are cast more than once (one resolution event per cast)
+    pub cast_index: u32,
 }
 
 #[derive(Debug, Clone)]
@@ -3054,6 +5531,7 @@ impl AbilityResolvedEvent {
                     damage: outcome.damage().unwrap_or(0),
                     is_buff: outcome.is_buff(),
                     hit_type: outcome.hit_type(),
+                    resisted: matches!(outcome, AbilityTargetOutcome::Resisted),
                 },
             );
         }
@@ -3063,8 +5541,12 @@ impl AbilityResolvedEvent {
                     damage: 0,
                     is_buff: false,
                     hit_type: outcome.hit_type(),
+                    resisted: true,
                 });
                 entry.damage += outcome.damage().unwrap_or(0);
+                if !matches!(outcome, AbilityTargetOutcome::Resisted) {
+                    entry.resisted = false;
+                }
                 if outcome.is_buff() {
                     entry.is_buff = true;
                 }
@@ -3078,6 +5560,7 @@ struct TargetResult {
     damage: u32,
     is_buff: bool,
     hit_type: Option<HitType>,
+    resisted: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -3087,6 +5570,8 @@ pub struct AttackedEvent {
     pub outcome: AttackOutcome,
     pub detail_lines: Vec<String>,
     pub area_outcomes: Option<Vec<(CharacterId, AbilityTargetOutcome)>>,
+    // Where the projectile flies from and to, if it's a ranged attack
+    pub projectile: Option<(Position, Position)>,
 }
 
 #[derive(Debug, Clone)]
@@ -3206,6 +5691,60 @@ pub fn attack_roll_bonus(
     bonus
 }
 
+/// The evasion that a reaction grants against an attack. A parry is easier with a heavier weapon
+/// than the attacker's, and harder against a heavier one, but always helps at least a little.
+pub fn reaction_bonus_evasion(
+    attacker: &Character,
+    hand: HandType,
+    defender: &Character,
+    reaction: OnAttackedReaction,
+) -> u32 {
+    let base = reaction.effect.bonus_evasion;
+    if reaction.id != OnAttackedReactionId::Parry || base == 0 {
+        return base;
+    }
+
+    let attacker_heft = attacker.weapon(hand).map(parry_heft).unwrap_or(0);
+    let defender_heft = defender
+        .weapon(reaction.used_hand.unwrap_or(HandType::MainHand))
+        .map(parry_heft)
+        .unwrap_or(0);
+
+    (base as i32 + defender_heft - attacker_heft).clamp(
+        PARRY_MIN_BONUS_EVASION as i32,
+        (base + PARRY_MAX_EXTRA_EVASION) as i32,
+    ) as u32
+}
+
+/// Ranged attacks are harder to land on a defender that is right behind an obstacle
+pub fn cover_bonus_evasion(attacker: &Character, hand: HandType, defender: &Character) -> u32 {
+    if has_cover_against(attacker, hand, defender) {
+        COVER_EVASION_BONUS
+    } else {
+        0
+    }
+}
+
+pub fn has_cover_against(attacker: &Character, hand: HandType, defender: &Character) -> bool {
+    let is_ranged = attacker
+        .weapon(hand)
+        .is_some_and(|weapon| !weapon.is_melee());
+    is_ranged
+        && defender
+            .terrain
+            .borrow()
+            .as_ref()
+            .is_some_and(|grid| grid.has_cover(attacker.pos(), defender.pos()))
+}
+
+fn parry_heft(weapon: Weapon) -> i32 {
+    let grip_bonus = match weapon.grip {
+        WeaponGrip::TwoHanded => 2,
+        _ => 0,
+    };
+    weapon.weight as i32 + grip_bonus
+}
+
 pub fn prob_attack_hit(
     attacker: &Character,
     hand: HandType,
@@ -3214,10 +5753,10 @@ pub fn prob_attack_hit(
     reaction: Option<OnAttackedReaction>,
 ) -> f32 {
     let bonus = attack_roll_bonus(attacker, hand, defender, enhancements, reaction);
-    let mut evasion = defender.evasion();
+    let mut evasion = defender.evasion() + cover_bonus_evasion(attacker, hand, defender);
 
     if let Some(reaction) = reaction {
-        evasion += reaction.effect.bonus_evasion;
+        evasion += reaction_bonus_evasion(attacker, hand, defender, reaction);
     }
 
     let dice_target = evasion
@@ -3234,13 +5773,14 @@ pub fn prob_attack_penetrating_hit(
     reaction: Option<OnAttackedReaction>,
 ) -> f32 {
     let bonus = attack_roll_bonus(attacker, hand, defender, enhancements, reaction);
-    let mut evasion = defender.evasion();
+    let mut evasion = defender.evasion() + cover_bonus_evasion(attacker, hand, defender);
 
     if let Some(reaction) = reaction {
-        evasion += reaction.effect.bonus_evasion;
+        evasion += reaction_bonus_evasion(attacker, hand, defender, reaction);
     }
 
-    let mut armor = defender.protection_from_armor();
+    let mut armor =
+        defender.protection_against(attacker.weapon(hand).map(|weapon| weapon.damage_type));
     for (_name, effect) in enhancements {
         armor = armor.saturating_sub(effect.armor_penetration);
     }
@@ -3296,16 +5836,14 @@ impl Characters {
             "Each character must have a unique ID"
         );
 
-        // Player characters act first
-        // TODO: it should be sorted by caller
-        characters.sort_by(
-            |a, b| match (a.player_controlled(), b.player_controlled()) {
-                (true, true) => Ordering::Equal,
-                (false, false) => Ordering::Equal,
-                (true, false) => Ordering::Less,
-                (false, true) => Ordering::Greater,
-            },
-        );
+        // Highest initiative acts first. Ties are broken in favor of player characters, and then
+        // by id, so that the order is deterministic.
+        characters.sort_by(|a, b| {
+            b.initiative()
+                .cmp(&a.initiative())
+                .then_with(|| b.player_controlled().cmp(&a.player_controlled()))
+                .then_with(|| a.id().cmp(&b.id()))
+        });
         Self(
             characters
                 .into_iter()
@@ -3319,6 +5857,34 @@ impl Characters {
         )
     }
 
+    /// Moves the character one step later in the turn order, by swapping places with the next
+    /// character that hasn't yet acted this round. Returns the id of that character.
+    fn delay(&mut self, character_id: CharacterId) -> Option<CharacterId> {
+        let i = self.0.iter().position(|ch| ch.id() == character_id)?;
+        let j = (i + 1..self.0.len()).find(|j| !self.0[*j].has_taken_a_turn_this_round.get())?;
+
+        // The characters trade indices, so that the one who acts now takes over the earlier index.
+        // That way the current game time stays the same, and no conditions expire prematurely.
+        let index_i = self.0[i].index_in_round.get();
+        let index_j = self.0[j].index_in_round.get();
+        self.0[i].index_in_round.set(index_j);
+        self.0[j].index_in_round.set(index_i);
+        self.0.swap(i, j);
+
+        Some(self.0[i].id())
+    }
+
+    /// Adds a (summoned) character to the turn order, right after another character
+    pub fn insert_after(&mut self, character: Rc<Character>, after: CharacterId) {
+        let i = self
+            .0
+            .iter()
+            .position(|ch| ch.id() == after)
+            .map(|i| i + 1)
+            .unwrap_or(self.0.len());
+        self.0.insert(i, character);
+    }
+
     fn next_id(&self) -> CharacterId {
         for ch in self.iter() {
             if !ch.has_taken_a_turn_this_round.get() {
@@ -3358,7 +5924,7 @@ impl Characters {
 
     pub fn any_alive_player_chars(&self) -> bool {
         self.iter()
-            .any(|ch| ch.player_controlled() && !ch.is_dead())
+            .any(|ch| ch.player_controlled() && !ch.is_dead() && !ch.is_downed())
     }
 
     pub fn remove_dead(&mut self) -> Vec<CharacterId> {
@@ -3382,6 +5948,7 @@ impl Characters {
 #[derive(Debug, Copy, Clone, PartialEq, Hash)]
 pub enum ApplyEffect {
     RemoveActionPoints(u32),
+    GainActionPoints(u32),
     Condition(ApplyCondition),
     GainHealth(u32),
     GainStamina(u32),
@@ -3393,12 +5960,25 @@ pub enum ApplyEffect {
         condition: Condition,
     },
     Pushed(u32),
+    DrainMana {
+        amount: u32,
+        damage_per_point: u32,
+    },
+    Dispel(DispelEffect),
+}
+
+/// Strips conditions from the receiver; beneficial ones from enemies, harmful ones from allies
+#[derive(Debug, Copy, Clone, PartialEq, Hash)]
+pub struct DispelEffect {
+    pub positive: bool,
+    pub max_count: u32,
 }
 
 impl ApplyEffect {
     fn multiply(&mut self, factor: u32) {
         match self {
             ApplyEffect::RemoveActionPoints(n) => *n *= factor,
+            ApplyEffect::GainActionPoints(n) => *n *= factor,
             ApplyEffect::Condition(apply_condition) => {
                 if let Some(rounds) = &mut apply_condition.duration_rounds {
                     *rounds *= factor;
@@ -3415,6 +5995,8 @@ impl ApplyEffect {
             } => todo!(),
             ApplyEffect::ConsumeCondition { condition } => todo!(),
             ApplyEffect::Pushed(n) => *n *= factor,
+            ApplyEffect::DrainMana { amount, .. } => *amount *= factor,
+            ApplyEffect::Dispel(dispel) => dispel.max_count *= factor,
         }
     }
 }
@@ -3423,6 +6005,7 @@ impl Display for ApplyEffect {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             ApplyEffect::RemoveActionPoints(n) => f.write_fmt(format_args!("-{n} AP")),
+            ApplyEffect::GainActionPoints(n) => f.write_fmt(format_args!("+{n} AP")),
             ApplyEffect::GainStamina(n) => f.write_fmt(format_args!("+{n} stamina")),
             ApplyEffect::GainHealth(n) => f.write_fmt(format_args!("{n}")),
             ApplyEffect::Condition(apply_condition) => {
@@ -3440,6 +6023,8 @@ impl Display for ApplyEffect {
                 f.write_fmt(format_args!("|<strikethrough>{}|", condition.name()))
             }
             ApplyEffect::Pushed(..) => f.write_str("Pushed"),
+            ApplyEffect::DrainMana { amount, .. } => f.write_fmt(format_args!("-{amount} mana")),
+            ApplyEffect::Dispel(dispel) => f.write_fmt(format_args!("Dispel {}", dispel.max_count)),
         }
     }
 }
@@ -3456,6 +6041,8 @@ pub struct OnAttackedReaction {
     pub required_attack_type: Option<AttackType>,
     pub used_hand: Option<HandType>,
     pub target: OnAttackedReactionTarget,
+    // Only helps against attackers in front of the reactor (e.g. a raised shield)
+    pub only_from_front: bool,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Hash)]
@@ -3463,12 +6050,15 @@ pub enum OnAttackedReactionId {
     Parry,
     SideStep,
     Block,
+    Riposte,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Hash)]
 pub struct OnAttackedReactionEffect {
     pub bonus_evasion: u32,
     pub bonus_armor: u32,
+    // The reactor strikes back with their main hand if the attack is reduced to a graze
+    pub counterattack: bool,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Hash)]
@@ -3484,6 +6074,7 @@ pub struct OnHitReaction {
     pub icon: IconId,
     pub action_point_cost: u32,
     pub stamina_cost: u32,
+    pub mana_cost: u32,
     pub effect: OnHitReactionEffect,
     pub required_attack_type: Option<AttackType>,
 }
@@ -3498,6 +6089,8 @@ pub enum AttackType {
 pub enum OnHitReactionEffect {
     Rage,
     ShieldBash,
+    // Reflects this percentage of the damage back at the attacker (contested by their will)
+    SpellReflect(u32),
 }
 
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -3550,6 +6143,25 @@ pub enum Condition {
     Adrenalin,
     ArcaneSurge,
     HealthPotionRecovering,
+    Prone,
+    Poisoned,
+    Rooted,
+    Taunted,
+    Disarmed,
+    Channeling,
+    Shielded,
+    Hidden,
+    Frozen,
+    Regenerating,
+    Confused,
+    Momentum,
+    Downed,
+    Overwatch,
+    Panicked,
+    Thorns,
+    Aiming,
+    Guarding,
+    Petrified,
 }
 
 impl Condition {
@@ -3570,6 +6182,13 @@ impl Condition {
             Encumbered => "Encumbered",
             NearDeath => "Near-death",
             Dead => "Dead",
+            Downed => "Downed",
+            Overwatch => "Overwatch",
+            Panicked => "Panicked",
+            Thorns => "Thorns",
+            Aiming => "Aiming",
+            Guarding => "Guarding",
+            Petrified => "Petrified",
             Slowed => "Slowed",
             Hastened => "Hastened",
             Inspired => "Inspired",
@@ -3582,6 +6201,18 @@ impl Condition {
             Adrenalin => "Adrenalin",
             ArcaneSurge => "Arcane surge",
             HealthPotionRecovering => "Recovering",
+            Prone => "Prone",
+            Poisoned => "Poisoned",
+            Rooted => "Rooted",
+            Taunted => "Taunted",
+            Disarmed => "Disarmed",
+            Channeling => "Channeling",
+            Shielded => "Shielded",
+            Hidden => "Hidden",
+            Frozen => "Frozen",
+            Regenerating => "Regenerating",
+            Confused => "Confused",
+            Momentum => "Momentum",
         }
     }
 
@@ -3600,14 +6231,21 @@ impl Condition {
             //Bleeding => "Deals |<value>x| damage over time. (50% of remaining at the end of each turn)",
             Bleeding => "End of turn: lose |<value>x| health. Halved every turn.",
             Burning => "End of turn: deals |<value>x| damage. 50% spreads to adjacent.",
-            Braced => "|<value>+3| |<shield>|<stat>Evasion| against the next attack.",
+            Braced => "|<value>+3| |<shield>|<stat>Evasion| against the next attack. Strikes enemies that charge into reach for double damage.",
             Distracted => "|<value>-6| |<shield>|<stat>Evasion| against the next attack.",
             Weakened => "|<value>-x| to all |<shield>| and |dice>.",
             MainHandExertion => "-x on further similar actions.",
             OffHandExertion => "-x on further similar actions.",
-            Encumbered => "|<value>-x| |<shield>|<stat>Evasion|, |<value>-x| on |<dice>|.",
+            Encumbered => "|<value>-x| |<shield>|<stat>Evasion|, |<value>-x| on |<dice>|, 5% slower movement per point.",
             NearDeath => "|<value>-1| AP regen, Disadvantage on actions, enemies have Advantage. (Triggers on < 20% health)",
             Dead => "This character is dead.",
+            Panicked => "Tries to flee from the fight. Can not react.",
+            Thorns => "Melee attackers take |<value>x|% of the damage they deal back.",
+            Aiming => "|<value>+x| |<dice>| on the next attack. Lost when moving.",
+            Guarding => "|<value>+x| |<shield>|<stat>Evasion| until their next turn.",
+            Petrified => "Turned to stone. Skips their turns and can not react, but is immune to damage and conditions.",
+            Overwatch => "Shoots the first enemy that moves into range and sight, using the |<value>x| reserved AP.",
+            Downed => "Skips their turns and can not react. Dies after |<value>x| more turns, unless revived by an ally's healing.",
             ReaperApCooldown => "Can not gain more AP from Reaper this turn.",
             BloodRage => "|<value>+5| |<dice>| |<stat>Attack| (passive skill).",
             CriticalCharge => "|<value>+5| |<dice>| |<stat>Spell| (passive skill).",
@@ -3615,6 +6253,18 @@ impl Condition {
             Adrenalin => "|<value>+1| AP per turn.",
             ArcaneSurge => "|<value>+x| |<dice>| |<stat>Spell|. Decays 1 at end of turn.",
             HealthPotionRecovering => "End of turn: |<heart>| heal |<value>2|",
+            Prone => "Disadvantage on attacks. Melee attackers have Advantage, ranged attackers have Disadvantage. Must stand up to move.",
+            Poisoned => "End of turn: lose |<value>x| health. Decays 1 every turn.",
+            Rooted => "Can not move. Can still attack and cast.",
+            Taunted => "Disadvantage on attacks against anyone but the taunter.",
+            Disarmed => "Main-hand weapon was knocked away. Change equipment to re-arm.",
+            Channeling => "Sustaining an ability each turn. Broken by taking damage or moving.",
+            Shielded => "Absorbs the next |<value>x| damage. Wears off at end of turn.",
+            Hidden => "Advantage and |<value>+3| damage on the next attack. Can't be targeted by enemies that aren't adjacent. Revealed by attacking, taking damage or ending up adjacent to an enemy.",
+            Frozen => "Skips the next |<value>x| turns. Can not react.",
+            Regenerating => "End of turn: |<heart>| heal |<value>x|. Decays 1 every turn.",
+            Confused => "Can't tell friend from foe. Attacks whoever is within reach.",
+            Momentum => "|<value>+x| damage on the next attack this turn (passive skill). Lost on a graze.",
         }
     }
 
@@ -3635,6 +6285,13 @@ impl Condition {
             Encumbered => false,
             NearDeath => false,
             Dead => false,
+            Downed => false,
+            Overwatch => true,
+            Panicked => false,
+            Thorns => true,
+            Aiming => true,
+            Guarding => true,
+            Petrified => false,
             Slowed => false,
             Hastened => true,
             Inspired => true,
@@ -3647,13 +6304,38 @@ impl Condition {
             Adrenalin => true,
             ArcaneSurge => true,
             HealthPotionRecovering => true,
+            Prone => false,
+            Poisoned => false,
+            Rooted => false,
+            Taunted => false,
+            Disarmed => false,
+            Channeling => true,
+            Shielded => true,
+            Hidden => true,
+            Frozen => false,
+            Regenerating => true,
+            Confused => false,
+            Momentum => true,
+        }
+    }
+
+    // Conditions that are bookkeeping or follow from the character's state, rather than being
+    // something that was put on them
+    pub const fn is_dispellable(&self) -> bool {
+        use Condition::*;
+        match self {
+            MainHandExertion | OffHandExertion | Encumbered | NearDeath | Dead | Downed
+            | ReaperApCooldown | BloodRage | CriticalCharge | ThrillOfBattle | Momentum | Prone
+            | Disarmed | Channeling | Hidden => false,
+            _ => true,
         }
     }
 
     pub const fn has_cumulative_stacking(&self) -> bool {
         use Condition::*;
         match self {
-            Bleeding | Burning | ArcaneSurge => true,
+            Bleeding | Burning | ArcaneSurge | Poisoned | MainHandExertion | OffHandExertion
+            | Shielded | Aiming => true,
             _ => false,
         }
     }
@@ -3667,6 +6349,7 @@ impl Condition {
             Bleeding => StatusId::Bleeding,
             Burning => StatusId::Burning,
             HealthPotionRecovering => StatusId::Healing,
+            Regenerating => StatusId::Healing,
             Hindered => StatusId::Hindered,
             Blinded => StatusId::Blinded,
             Exposed => StatusId::Exposed,
@@ -3675,6 +6358,8 @@ impl Condition {
             Inspired => StatusId::Inspired,
             NearDeath => StatusId::NearDeath,
             Dead => StatusId::Dead,
+            // TODO: unique icon
+            Downed => StatusId::NearDeath,
             CriticalCharge => StatusId::CriticalCharge,
             ReaperApCooldown => StatusId::ReaperApCooldown,
             BloodRage => StatusId::Rage,
@@ -3691,13 +6376,24 @@ impl Condition {
 }
 
 const PROTECTED_ARMOR_BONUS: u32 = 1;
+const BROKEN_WEAPON_PENALTY: u32 = 1;
 const BRACED_DEFENSE_BONUS: u32 = 3;
+const COVER_EVASION_BONUS: u32 = 3;
+pub const GUARDING_EVASION_PER_AP: u32 = 1;
 const DISTRACTED_DEFENSE_PENALTY: u32 = 6;
 const DAZED_EVASION_PENALTY: u32 = 5;
 const EXPOSED_DEFENSE_PENALTY: u32 = 3;
 const INSPIRED_WILL_BONUS: u32 = 3;
 const SLOWED_AP_PENALTY: u32 = 2;
 const HASTENED_AP_BONUS: u32 = 1;
+const ENCUMBRANCE_MOVEMENT_PENALTY_PER_STACK: f32 = 0.05;
+const MAX_ENCUMBRANCE_MOVEMENT_PENALTY: f32 = 0.5;
+const MIN_MOVE_SPEED_MODIFIER: f32 = 0.25;
+const HIDDEN_BONUS_DAMAGE: u32 = 3;
+pub const GUARDIAN_AP_COST: u32 = 1;
+pub const COUNTERSPELL_AP_COST: u32 = 1;
+pub const COUNTERSPELL_MANA_COST: u32 = 2;
+const COUNTERSPELL_RANGE: Range = Range::Float(8.5);
 
 #[derive(Debug, Copy, Clone, PartialEq, Hash)]
 pub struct ConditionInfo {
@@ -3867,6 +6563,7 @@ pub enum Action {
         // Including start position, all the way to the destination
         positions: Vec<Position>,
         extra_cost: u32,
+        enhancements: Vec<MovementEnhancement>,
     },
     ChangeEquipment {
         from: EquipmentSlotRole,
@@ -3874,7 +6571,11 @@ pub enum Action {
     },
     UseConsumable {
         inventory_equipment_index: usize,
+        target: Option<Position>,
     },
+    DelayTurn,
+    // Ends the turn, converting the remaining AP into evasion
+    GuardStance,
 }
 
 #[derive(Debug, Clone, PartialEq, Hash)]
@@ -3908,8 +6609,8 @@ pub enum BaseAction {
     Move,
     ChangeEquipment,
     UseConsumable,
-    // TODO add "DelayTurn" action that lets you put yourself one step later in the
-    // turn order
+    DelayTurn,
+    GuardStance,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -3926,6 +6627,8 @@ impl BaseAction {
             BaseAction::Move => 0,
             BaseAction::ChangeEquipment => 1,
             BaseAction::UseConsumable => 1,
+            BaseAction::DelayTurn => 0,
+            BaseAction::GuardStance => 0,
         }
     }
 
@@ -3936,6 +6639,8 @@ impl BaseAction {
             BaseAction::Move => 0,
             BaseAction::ChangeEquipment => 0,
             BaseAction::UseConsumable => 0,
+            BaseAction::DelayTurn => 0,
+            BaseAction::GuardStance => 0,
         }
     }
 
@@ -3946,6 +6651,8 @@ impl BaseAction {
             BaseAction::Move => 0,
             BaseAction::ChangeEquipment => 0,
             BaseAction::UseConsumable => 0,
+            BaseAction::DelayTurn => 0,
+            BaseAction::GuardStance => 0,
         }
     }
 }
@@ -3972,8 +6679,12 @@ pub struct Ability {
     pub mana_cost: u32,
     pub stamina_cost: u32,
     pub requirement: Option<EquipmentRequirement>,
+    // Number of rounds before the ability can be used again (0 means no cooldown)
+    pub cooldown_rounds: u32,
 
     pub roll: Option<AbilityRollType>,
+    // Risky abilities can hurt the caster on a terrible roll
+    pub backlash: Option<Backlash>,
     pub target: AbilityTarget,
     pub possible_enhancements: [Option<AbilityEnhancement>; 3],
     pub animation_color: Color,
@@ -3982,7 +6693,74 @@ pub struct Ability {
     pub charge_fx: Option<AbilityChargeFx>,
 }
 
+/// An ability that keeps affecting the area around the caster at the end of each of their turns,
+/// until they take damage or move.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ChanneledAbility {
+    pub ability: Ability,
+    pub area_center: Position,
+    // The game time of the casting turn, which shouldn't get an extra pulse on top of the cast itself
+    pub cast_at: u32,
+}
+
 impl Ability {
+    // Abilities that give the caster Channeling keep re-applying their self-area each turn
+    pub fn is_channeled(&self) -> bool {
+        let AbilityTarget::None {
+            self_area: Some(_),
+            self_effect: Some(effect),
+            ..
+        } = self.target
+        else {
+            return false;
+        };
+        effect.apply.iter().flatten().flatten().any(|e| {
+            matches!(
+                e,
+                ApplyEffect::Condition(ApplyCondition {
+                    condition: Condition::Channeling,
+                    ..
+                })
+            )
+        })
+    }
+
+    // Single-target spells can get an area around the target from an enhancement, which only
+    // deals the enhancement's area damage
+    pub fn impact_circle(
+        &self,
+        enhancements: &[AbilityEnhancement],
+    ) -> Option<(Range, AreaTargetAcquisition, AbilityNegativeEffect)> {
+        let AbilityTarget::Enemy {
+            effect,
+            impact_circle,
+            ..
+        } = self.target
+        else {
+            return None;
+        };
+        if impact_circle.is_some() {
+            return impact_circle;
+        }
+        let AbilityNegativeEffect::Spell(spell) = effect else {
+            return None;
+        };
+        let has_splash = enhancements
+            .iter()
+            .any(|e| e.spell_effect.is_some_and(|e| e.splash));
+        has_splash.then_some((
+            SPLASH_RADIUS,
+            AreaTargetAcquisition::Enemies,
+            AbilityNegativeEffect::Spell(SpellNegativeEffect {
+                defense_type: spell.defense_type,
+                damage: Some(AbilityDamage::Static(0)),
+                damage_type: spell.damage_type,
+                on_hit: None,
+                graze_behavior: SpellGrazeBehavior::Reduced,
+            }),
+        ))
+    }
+
     pub fn requires_melee_weapon(&self) -> bool {
         matches!(
             self.requirement,
@@ -3990,10 +6768,23 @@ impl Ability {
         )
     }
 
+    // Can't be used again until the next fight
+    pub fn is_once_per_fight(&self) -> bool {
+        self.id == AbilityId::SecondWind
+    }
+
     pub fn requires_shield(&self) -> bool {
         matches!(self.requirement, Some(EquipmentRequirement::Shield))
     }
 
+    pub fn requires_dual_wield(&self) -> bool {
+        matches!(self.requirement, Some(EquipmentRequirement::DualWield))
+    }
+
+    pub fn requires_reach_weapon(&self) -> bool {
+        matches!(self.requirement, Some(EquipmentRequirement::ReachWeapon))
+    }
+
     pub fn targets_single_enemy(&self) -> bool {
         matches!(self.target, AbilityTarget::Enemy { .. })
     }
@@ -4010,6 +6801,8 @@ impl Ability {
             AbilityTarget::Enemy { .. } => true,
             AbilityTarget::Ally { .. } => true,
             AbilityTarget::Area { .. } => true,
+            AbilityTarget::Teleport { .. } => true,
+            AbilityTarget::Trap { .. } => true,
             AbilityTarget::None { .. } => false,
         }
     }
@@ -4026,6 +6819,10 @@ impl Ability {
 pub enum EquipmentRequirement {
     Weapon(WeaponType),
     Shield,
+    // A weapon in each hand
+    DualWield,
+    // A melee weapon that threatens beyond melee range, e.g. a spear
+    ReachWeapon,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Hash)]
@@ -4033,19 +6830,48 @@ pub enum AbilityId {
     Tackle,
     ShieldBash,
     SweepAttack,
+    Flurry,
     LungeAttack,
     Brace,
+    SetWeapon,
+    Overwatch,
+    Aim,
     Scream,
     ShackledMind,
     MindBlast,
+    ManaBurn,
+    DispelMagic,
+    Cleanse,
+    RaiseSkeleton,
+    Hide,
+    Freeze,
+    Confuse,
+    WallOfFire,
+    Gust,
+    Blink,
+    SpikeTrap,
+    SoothingAura,
     InflictWounds,
+    SoulRend,
+    MindCrush,
+    Petrify,
     PiercingShot,
     Heal,
     HealingNova,
     SelfHeal,
+    SecondWind,
     HealingRain,
+    Regenerate,
     Inspire,
+    CommandStrike,
     Haste,
+    Thorns,
+    Rescue,
+    Frenzy,
+    Trip,
+    StandUp,
+    Entangle,
+    Taunt,
     Fireball,
     SearingLight,
     Kill,
@@ -4079,7 +6905,21 @@ pub enum AbilityEffect {
 pub struct SpellNegativeEffect {
     pub defense_type: Option<DefenseType>,
     pub damage: Option<AbilityDamage>,
+    // Untyped spell damage ignores armor
+    pub damage_type: Option<DamageType>,
     pub on_hit: Option<[Option<ApplyEffect>; 2]>,
+    pub graze_behavior: SpellGrazeBehavior,
+}
+
+/// What a spell loses when it only grazes its target
+#[derive(Debug, Copy, Clone, PartialEq, Hash)]
+pub enum SpellGrazeBehavior {
+    // Half damage, and on-hit effects at half strength
+    Reduced,
+    // Full damage, but no on-hit effects
+    DamageOnly,
+    // On-hit effects at half strength, but no damage
+    EffectsOnly,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Hash)]
@@ -4112,6 +6952,12 @@ impl AbilityNegativeEffect {
 pub struct AbilityAttackEffect {
     pub override_damage: Option<u32>,
     pub on_hit: Option<ApplyEffect>,
+    // The main-hand attack is followed by an off-hand attack against the same target
+    pub flurry: bool,
+    // Not part of the ability data; filled in when the attacker charged into melee
+    pub charge_bonus_damage: u32,
+    // Not part of the ability data; the off-hand is used for the second attack of a flurry
+    pub hand: HandType,
 }
 
 impl AbilityAttackEffect {
@@ -4119,6 +6965,9 @@ impl AbilityAttackEffect {
         Self {
             override_damage: None,
             on_hit: None,
+            flurry: false,
+            charge_bonus_damage: 0,
+            hand: HandType::MainHand,
         }
     }
 }
@@ -4127,12 +6976,32 @@ impl AbilityAttackEffect {
 pub enum AbilityDamage {
     Static(u32),
     AtLeast(u32),
+    // base + (spell modifier * per_modifier_tenths / 10), rounded down
+    Scaling { base: u32, per_modifier_tenths: u32 },
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Hash)]
 pub struct AbilityPositiveEffect {
     pub healing: u32,
     pub apply: Option<[Option<ApplyEffect>; 2]>,
+    // Only relevant for self-targeted abilities that have a roll
+    pub backfire: Option<Backfire>,
+}
+
+/// If the ability roll ends up below the threshold, the caster suffers the effect instead of
+/// receiving the positive effect.
+#[derive(Debug, Copy, Clone, PartialEq, Hash)]
+pub struct Backfire {
+    pub roll_below: i32,
+    pub effect: ApplyEffect,
+}
+
+/// If the unmodified ability die comes up at or below the threshold, the caster suffers the effect
+/// (in addition to the ability resolving as usual).
+#[derive(Debug, Copy, Clone, PartialEq, Hash)]
+pub struct Backlash {
+    pub dice_roll_at_most: u32,
+    pub effect: ApplyEffect,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -4146,17 +7015,46 @@ pub enum AbilityTarget {
     Ally {
         range: Range,
         effect: AbilityPositiveEffect,
+        // Move the ally (up to this many cells) toward the caster, e.g. to get them out of melee
+        pull_toward_caster: Option<u32>,
     },
 
     Area {
         range: Range,
         area_effect: AreaEffect,
+        // Lingers on the covered cells after the ability has resolved
+        ground_effect: Option<GroundEffect>,
     },
 
     None {
         self_area: Option<AreaEffect>,
         self_effect: Option<AbilityPositiveEffect>,
+        summon: Option<SummonEffect>,
+    },
+
+    // The caster instantly appears at a free position, without passing the cells in between
+    Teleport {
+        range: Range,
     },
+
+    // Hidden on a cell until an enemy steps on it, then resolved against them (once)
+    Trap {
+        range: Range,
+        effect: SpellNegativeEffect,
+    },
+}
+
+/// A temporary ally that appears next to the caster, and vanishes after a number of rounds
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SummonEffect {
+    pub name: &'static str,
+    pub portrait: PortraitId,
+    pub sprite: SpriteId,
+    // Strength, agility, intellect, spirit
+    pub attributes: (u32, u32, u32, u32),
+    pub max_health: u32,
+    pub weapon: Option<Weapon>,
+    pub duration_rounds: u32,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq)]
@@ -4166,6 +7064,24 @@ pub struct AreaEffect {
     pub effect: AbilityEffect,
 }
 
+/// Placed by a Trap ability. The caster's roll is made when placing it, and contested against the
+/// defense of whoever sets it off.
+struct Trap {
+    position: Position,
+    placer: Rc<Character>,
+    ability_name: &'static str,
+    ability_roll: AbilityRoll,
+    effect: SpellNegativeEffect,
+}
+
+/// An effect that lingers on a cell, affecting whoever walks into it or ends their turn on it
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct GroundEffect {
+    pub remaining_rounds: u32,
+    pub on_enter: Option<ApplyEffect>,
+    pub on_end_turn: Option<ApplyEffect>,
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum AreaShape {
     Circle(Range),
@@ -4191,6 +7107,8 @@ impl AbilityTarget {
             AbilityTarget::Enemy { .. } => true,
             AbilityTarget::Ally { .. } => true,
             AbilityTarget::Area { .. } => false,
+            AbilityTarget::Teleport { .. } => false,
+            AbilityTarget::Trap { .. } => false,
             AbilityTarget::None { .. } => false,
         }
     }
@@ -4203,6 +7121,8 @@ impl AbilityTarget {
             },
             AbilityTarget::Ally { range, .. } => Some(*range),
             AbilityTarget::Area { range, .. } => Some(*range),
+            AbilityTarget::Teleport { range } => Some(*range),
+            AbilityTarget::Trap { range, .. } => Some(*range),
             AbilityTarget::None { .. } => None,
         }
     }
@@ -4279,6 +7199,17 @@ impl AttackEnhancement {
     }
 }
 
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct MovementEnhancement {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub icon: IconId,
+    pub action_point_cost: u32,
+    pub stamina_cost: u32,
+    // Extra free movement for the turn, per point of agility
+    pub bonus_distance_per_agility: f32,
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Hash)]
 pub struct AbilityEnhancement {
     pub ability_id: AbilityId,
@@ -4392,6 +7323,9 @@ pub struct SpellEnhancementEffect {
     pub area_on_hit: Option<[Option<ApplyEffect>; 2]>,
     pub increased_range_tenths: u32,
     pub increased_radius_tenths: u32,
+    pub spare_allies: bool,
+    // Gives a single-target spell a small area around the target (see Ability::impact_circle)
+    pub splash: bool,
 }
 
 impl SpellEnhancementEffect {
@@ -4407,6 +7341,8 @@ impl SpellEnhancementEffect {
             area_on_hit: None,
             increased_range_tenths: 0,
             increased_radius_tenths: 0,
+            spare_allies: false,
+            splash: false,
         }
     }
 }
@@ -4415,6 +7351,8 @@ impl SpellEnhancementEffect {
 pub enum AttackEnhancementOnHitEffect {
     RegainActionPoint,
     Target(Option<DefenseType>, ApplyEffect),
+    // Knock the target's main-hand weapon away, unless they resist
+    Disarm(DefenseType),
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Hash)]
@@ -4422,6 +7360,10 @@ pub enum DefenseType {
     Will,
     Evasion,
     Toughness,
+    // Whichever of the two is lower for the defender
+    LowerOf(&'static DefenseType, &'static DefenseType),
+    // Whichever of the two is higher for the defender
+    HigherOf(&'static DefenseType, &'static DefenseType),
 }
 
 impl DefenseType {
@@ -4430,6 +7372,18 @@ impl DefenseType {
             DefenseType::Will => "will",
             DefenseType::Evasion => "evasion",
             DefenseType::Toughness => "toughness",
+            DefenseType::LowerOf(..) => "lowest defense",
+            DefenseType::HigherOf(..) => "highest defense",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DefenseType::Will => "Will",
+            DefenseType::Evasion => "Evasion",
+            DefenseType::Toughness => "Toughness",
+            DefenseType::LowerOf(..) => "Lowest defense",
+            DefenseType::HigherOf(..) => "Highest defense",
         }
     }
 }
@@ -4518,6 +7472,8 @@ pub enum CharacterKind {
 pub struct Bot {
     pub behaviour: BotBehaviour,
     pub base_movement: f32,
+    // Added to Will when resisting panic as the bot's side is decimated. None means it never panics.
+    pub morale: Option<u32>,
 }
 
 impl CharacterKind {
@@ -4544,6 +7500,47 @@ impl Party {
     pub fn gain_money(&self, amount: u32) {
         self.money.set(self.money.get() + amount);
     }
+
+    /// Puts the entry in the first empty stash slot. Returns false if the stash is full.
+    pub fn deposit(&self, entry: EquipmentEntry) -> bool {
+        match self.stash.iter().find(|slot| slot.get().is_none()) {
+            Some(slot) => {
+                slot.set(Some(entry));
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+/// What a bot drops for the party when it's killed
+#[derive(Debug, Clone, Default)]
+pub struct Loot {
+    pub money: u32,
+    pub items: Vec<EquipmentEntry>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum RestType {
+    Short,
+    Long,
+}
+
+impl RestType {
+    // The fraction of max health and max mana that is restored
+    pub fn recovery(&self) -> (f32, f32) {
+        match self {
+            RestType::Short => (0.25, 0.5),
+            RestType::Long => (0.5, 1.0),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct RestOutcome {
+    pub health_gained: u32,
+    pub mana_gained: u32,
+    pub stamina_gained: u32,
 }
 
 #[derive(Debug, Clone)]
@@ -4556,6 +7553,8 @@ pub struct Character {
     pub has_taken_a_turn_this_round: Cell<bool>,
     pub has_used_main_hand_reaction_this_round: Cell<bool>,
     pub has_used_off_hand_reaction_this_round: Cell<bool>,
+    // Rolled at the start of each fight, and determines the turn order
+    initiative: Cell<u32>,
 
     pub name: &'static str,
     pub portrait: PortraitId,
@@ -4578,6 +7577,8 @@ pub struct Character {
     main_hand: Cell<Hand>,
     off_hand: Cell<Hand>,
     pub arrows: Cell<Option<ArrowStack>>,
+    // Set when the last arrow is spent, until the game has told the UI about it
+    ran_out_of_arrows: Cell<bool>,
     pub conditions: RefCell<Conditions>,
     pub action_points: NumberedResource,
     pub stamina: NumberedResource,
@@ -4587,14 +7588,67 @@ pub struct Character {
     pub known_on_hit_reactions: RefCell<Vec<OnHitReaction>>,
     pub known_ability_enhancements: RefCell<Vec<AbilityEnhancement>>,
     pub known_passive_skills: RefCell<Vec<PassiveSkill>>,
+    level: Cell<u32>,
+    // Gained by killing enemies. Resets (keeping any surplus) when leveling up
+    experience: Cell<u32>,
+    // Passive skills that have been learned, regardless of whether equipment also grants them. The
+    // known passive skills are these plus any that are granted by the currently wielded weapons.
+    learned_passives: RefCell<Vec<PassiveSkill>>,
+    // Reduce the stacks (or if there are none, the duration) of received conditions. For example a
+    // creature that dwells in fire might only take half of any Burning applied to it.
+    pub condition_resistances: RefCell<HashMap<Condition, u32>>,
+    pub condition_resistance_percentages: RefCell<HashMap<Condition, u32>>,
+    // Conditions that are never applied to this character (undead don't bleed etc)
+    pub condition_immunities: RefCell<Vec<Condition>>,
+    pub loot: RefCell<Loot>,
+    // Whether Burning can spread to this character from a burning neighbour (constructs, golems etc
+    // are not flammable)
+    pub flammable: Cell<bool>,
 
     pub is_engaged_by: RefCell<HashMap<CharacterId, Rc<Character>>>,
     engagement_target: Cell<Option<CharacterId>>,
+    // Only relevant while the character is Taunted
+    taunted_by: Cell<Option<CharacterId>>,
 
     changed_equipment_listeners: RefCell<Vec<Weak<Cell<bool>>>>,
 
     pub is_facing_east: Cell<bool>,
+    pub facing: Cell<Direction>,
     is_being_pushed_in_direction: Cell<Option<(i32, i32)>>,
+    // The character pulling this one, and how far
+    is_being_pulled_toward: Cell<Option<(CharacterId, u32)>>,
+    channeled_ability: Cell<Option<ChanneledAbility>>,
+    // Set when taking damage while channeling
+    is_losing_channel: Cell<bool>,
+    pending_summon: Cell<Option<SummonEffect>>,
+    // Shield changes that the UI hasn't been notified about yet
+    shield_gained: Cell<u32>,
+    shield_absorbed: Cell<u32>,
+    is_adjacent_to_enemy: Cell<bool>,
+    // Set when a Hidden character attacks, takes damage or ends up next to an enemy
+    is_being_revealed: Cell<bool>,
+    // Hits (not grazes) landed so far during this character's current turn
+    consecutive_hits: Cell<u32>,
+    // Attacks and abilities used so far during this character's current turn
+    actions_this_turn: Cell<u32>,
+    // Set by the guard stance action, and resolved at the end of the turn
+    is_entering_guard_stance: Cell<bool>,
+    has_used_free_equipment_change: Cell<bool>,
+    // ACTION_POINTS_PER_TURN unless this character has a different action point economy
+    base_ap_per_turn: Cell<u32>,
+    // Once-per-fight abilities that have already been used during the current fight
+    abilities_used_this_fight: RefCell<Vec<AbilityId>>,
+    // Who has damaged this character during the current round (id, player controlled), for focus fire
+    damaged_this_round_by: RefCell<Vec<(CharacterId, bool)>>,
+    // The game time at which abilities that are on cooldown become available again
+    ability_cooldowns: RefCell<Vec<(AbilityId, u32)>>,
+    // The battlefield the character is on, used to look up elevation. Unset outside of fights.
+    terrain: RefCell<Option<Rc<PathfindGrid>>>,
+    dispelled_conditions: RefCell<Vec<Condition>>,
+    is_being_downed: Cell<bool>,
+    is_being_revived: Cell<bool>,
+    // Summoned characters vanish at this game time
+    expires_at: Cell<Option<u32>>,
 }
 
 impl Character {
@@ -4626,6 +7680,7 @@ impl Character {
             has_taken_a_turn_this_round: Cell::new(false),
             has_used_main_hand_reaction_this_round: Cell::new(false),
             has_used_off_hand_reaction_this_round: Cell::new(false),
+            initiative: Cell::new(0),
             is_part_of_active_group: Cell::new(false),
             portrait,
             sprite,
@@ -4643,6 +7698,7 @@ impl Character {
             main_hand: Default::default(),
             off_hand: Default::default(),
             arrows: Default::default(),
+            ran_out_of_arrows: Cell::new(false),
             conditions: Default::default(),
             current_game_time: Default::default(),
             action_points,
@@ -4657,21 +7713,77 @@ impl Character {
                 }),
                 BaseAction::ChangeEquipment,
                 BaseAction::UseConsumable,
+                BaseAction::DelayTurn,
+                BaseAction::GuardStance,
             ]),
             known_attacked_reactions: Default::default(),
             known_on_hit_reactions: Default::default(),
             known_ability_enhancements: Default::default(),
             known_passive_skills: Default::default(),
+            level: Cell::new(1),
+            experience: Cell::new(0),
+            learned_passives: Default::default(),
+            condition_resistances: Default::default(),
+            condition_resistance_percentages: Default::default(),
+            condition_immunities: Default::default(),
+            loot: Default::default(),
+            flammable: Cell::new(true),
             is_engaged_by: Default::default(),
             engagement_target: Default::default(),
+            taunted_by: Default::default(),
             changed_equipment_listeners: Default::default(),
             is_facing_east: Cell::new(false),
+            facing: Cell::new(Direction::West),
             is_being_pushed_in_direction: Cell::new(None),
+            is_being_pulled_toward: Cell::new(None),
+            channeled_ability: Cell::new(None),
+            is_losing_channel: Cell::new(false),
+            pending_summon: Cell::new(None),
+            shield_gained: Cell::new(0),
+            shield_absorbed: Cell::new(0),
+            is_adjacent_to_enemy: Cell::new(false),
+            is_being_revealed: Cell::new(false),
+            consecutive_hits: Cell::new(0),
+            actions_this_turn: Cell::new(0),
+            is_entering_guard_stance: Cell::new(false),
+            has_used_free_equipment_change: Cell::new(false),
+            base_ap_per_turn: Cell::new(ACTION_POINTS_PER_TURN),
+            abilities_used_this_fight: Default::default(),
+            damaged_this_round_by: Default::default(),
+            ability_cooldowns: Default::default(),
+            terrain: Default::default(),
+            dispelled_conditions: Default::default(),
+            is_being_downed: Cell::new(false),
+            is_being_revived: Cell::new(false),
+            expires_at: Cell::new(None),
+        }
+    }
+
+    /// Overrides the default ACTION_POINTS_PER_TURN and MAX_ACTION_POINTS, e.g. for a particularly fast character
+    pub fn set_action_point_economy(&self, ap_per_turn: u32, max_ap: u32) {
+        assert!(ap_per_turn <= max_ap);
+        self.base_ap_per_turn.set(ap_per_turn);
+        self.action_points.max.set(max_ap);
+        self.action_points
+            .current
+            .set(self.action_points.current().min(max_ap));
+    }
+
+    pub fn ap_per_turn(&self) -> u32 {
+        let base = self.base_ap_per_turn.get();
+        if self
+            .known_passive_skills
+            .borrow()
+            .contains(&PassiveSkill::Berserker)
+        {
+            base + 1
+        } else {
+            base
         }
     }
 
     pub fn end_of_turn_ap_gain(&self) -> u32 {
-        let mut gain_ap = ACTION_POINTS_PER_TURN;
+        let mut gain_ap = self.ap_per_turn();
         if self.conditions.borrow().has(&Condition::Adrenalin) {
             gain_ap += 1;
         }
@@ -4694,6 +7806,27 @@ impl Character {
         } else if dx < 0 {
             self.is_facing_east.set(false);
         }
+        if let Some(direction) = Direction::toward(self.position.get(), position) {
+            self.facing.set(direction);
+        }
+    }
+
+    /// Whether the position is within the (180°) arc in front of the character
+    pub fn is_facing(&self, position: Position) -> bool {
+        let (fx, fy) = self.facing.get().vector();
+        let (dx, dy) = (
+            position.0 - self.position.get().0,
+            position.1 - self.position.get().1,
+        );
+        fx * dx + fy * dy >= 0
+    }
+
+    pub fn is_reaction_effective_against(
+        &self,
+        reaction: OnAttackedReaction,
+        attacker_pos: Position,
+    ) -> bool {
+        !reaction.only_from_front || self.is_facing(attacker_pos)
     }
 
     fn set_position(&self, new_pos: Position) {
@@ -4727,7 +7860,19 @@ impl Character {
     }
 
     pub fn learn_passive(&self, passive: PassiveSkill) {
-        self.known_passive_skills.borrow_mut().push(passive);
+        let mut learned = self.learned_passives.borrow_mut();
+        if !learned.contains(&passive) {
+            learned.push(passive);
+        }
+        let mut known = self.known_passive_skills.borrow_mut();
+        if !known.contains(&passive) {
+            known.push(passive);
+        }
+    }
+
+    /// Known passive skills, excluding those that are only granted by equipment
+    pub fn learned_passive_skills(&self) -> Vec<PassiveSkill> {
+        self.learned_passives.borrow().clone()
     }
 
     fn set_current_game_time(&self, game_time: u32) {
@@ -4749,6 +7894,13 @@ impl Character {
         }
     }
 
+    pub fn party(&self) -> &Rc<Party> {
+        match &self.kind {
+            CharacterKind::Player(party, ..) => party,
+            CharacterKind::Bot(..) => panic!(),
+        }
+    }
+
     pub fn party_stash(&self) -> &[Cell<Option<EquipmentEntry>>; 6] {
         match &self.kind {
             CharacterKind::Player(party, ..) => &party.stash,
@@ -4758,6 +7910,10 @@ impl Character {
 
     fn on_health_changed(&self) {
         let health_ratio = self.health.ratio();
+        // An active shield keeps the character out of near-death
+        let shield = self.conditions.borrow().get_stacks(&Condition::Shielded);
+        let effective_health_ratio =
+            (self.health.current() + shield) as f32 / self.health.max() as f32;
         let has_blood_rage_passive = self
             .known_passive_skills
             .borrow()
@@ -4768,15 +7924,42 @@ impl Character {
         } else {
             self.conditions.borrow_mut().remove(&Condition::BloodRage);
         }
-        if !has_blood_rage_passive && health_ratio < 0.20 {
+        if !has_blood_rage_passive && effective_health_ratio < 0.20 {
             self.conditions.borrow_mut().add(Condition::NearDeath);
         } else {
             self.conditions.borrow_mut().remove(&Condition::NearDeath);
         }
 
-        if self.health.current() == 0 {
-            self.conditions.borrow_mut().remove(&Condition::NearDeath);
-            self.conditions.borrow_mut().add(Condition::Dead);
+        if self.health.current() == 0
+            && !self.is_dead()
+            && !self.is_downed()
+            && !self.has_condition(&Condition::Petrified)
+        {
+            let mut conditions = self.conditions.borrow_mut();
+            conditions.remove(&Condition::NearDeath);
+            if self.player_controlled() && !self.is_summon() {
+                // Party members hold on for a while, giving their allies a chance to revive them.
+                // Damage over time is what brought many of them down, so it stops here.
+                for condition in [Condition::Bleeding, Condition::Burning, Condition::Poisoned] {
+                    conditions.remove(&condition);
+                }
+                conditions.set_stacks(Condition::Downed, DOWNED_ROUNDS);
+                self.is_being_downed.set(true);
+            } else {
+                conditions.add(Condition::Dead);
+            }
+        }
+    }
+
+    fn spend_ability_costs(&self, ability: Ability, enhancements: &[AbilityEnhancement]) {
+        self.action_points.spend(ability.action_point_cost);
+        self.spend_mana(ability.mana_cost);
+        self.stamina.spend(ability.stamina_cost);
+        self.start_ability_cooldown(ability);
+        for enhancement in enhancements {
+            self.action_points.spend(enhancement.action_point_cost);
+            self.spend_mana(enhancement.mana_cost);
+            self.stamina.spend(enhancement.stamina_cost);
         }
     }
 
@@ -4851,6 +8034,21 @@ impl Character {
         self.is_engaged_by.borrow_mut().remove(&not_engager);
     }
 
+    fn set_not_taunted_by(&self, not_taunter: CharacterId) {
+        if self.taunted_by.get() == Some(not_taunter) {
+            self.taunted_by.set(None);
+            self.conditions.borrow_mut().remove(&Condition::Taunted);
+        }
+    }
+
+    pub fn taunter(&self) -> Option<CharacterId> {
+        if self.conditions.borrow().has(&Condition::Taunted) {
+            self.taunted_by.get()
+        } else {
+            None
+        }
+    }
+
     fn set_not_engaging(&self, target: CharacterId) {
         if self.engagement_target.get() == Some(target) {
             self.engagement_target.set(None);
@@ -4861,8 +8059,18 @@ impl Character {
         self.engagement_target.get() == Some(target)
     }
 
+    pub fn is_immobilized(&self) -> bool {
+        let conditions = self.conditions.borrow();
+        conditions.has(&Condition::Prone) || conditions.has(&Condition::Rooted)
+    }
+
+    /// The agility-based base speed, scaled by conditions. Each point of encumbrance slows the
+    /// character down a bit further, on top of any other slowing effects.
     pub fn move_speed(&self) -> f32 {
         let mut modifier = 1.0;
+        let encumbrance = self.conditions.borrow().get_stacks(&Condition::Encumbered);
+        modifier -= (encumbrance as f32 * ENCUMBRANCE_MOVEMENT_PENALTY_PER_STACK)
+            .min(MAX_ENCUMBRANCE_MOVEMENT_PENALTY);
         if self.conditions.borrow().has(&Condition::Hindered) {
             modifier -= 0.5;
         }
@@ -4872,13 +8080,22 @@ impl Character {
         if self.conditions.borrow().has(&Condition::Hastened) {
             modifier += 0.25;
         }
-        self.base_move_speed.get() * modifier
+        // Stacking slowing effects must never bring the character to a standstill
+        self.base_move_speed.get() * modifier.max(MIN_MOVE_SPEED_MODIFIER)
     }
 
     pub fn player_controlled(&self) -> bool {
         matches!(self.kind, CharacterKind::Player(..))
     }
 
+    pub fn initiative(&self) -> u32 {
+        self.initiative.get()
+    }
+
+    pub fn is_summon(&self) -> bool {
+        self.expires_at.get().is_some()
+    }
+
     pub fn add_to_strength(&self, amount: i32) {
         let current = self.base_attributes.strength.get();
         let new = current as i32 + amount;
@@ -4912,7 +8129,42 @@ impl Character {
         assert!(new >= 0);
         self.base_attributes.spirit.set(new as u32);
 
-        self.on_attributes_changed();
+        self.on_attributes_changed();
+    }
+
+    pub fn level(&self) -> u32 {
+        self.level.get()
+    }
+
+    pub fn experience(&self) -> u32 {
+        self.experience.get()
+    }
+
+    pub fn experience_for_next_level(&self) -> u32 {
+        self.level.get() * EXPERIENCE_PER_LEVEL
+    }
+
+    pub fn gain_experience(&self, amount: u32) {
+        self.experience.set(self.experience.get() + amount);
+    }
+
+    /// Used when loading a saved character
+    pub fn restore_progress(&self, level: u32, experience: u32) {
+        self.level.set(level);
+        self.experience.set(experience);
+    }
+
+    /// Gains at most one level, if enough experience has been gathered. The caller is responsible
+    /// for letting the player spend the resulting attribute point.
+    pub fn try_level_up(&self) -> bool {
+        let required = self.experience_for_next_level();
+        if self.experience.get() >= required {
+            self.experience.set(self.experience.get() - required);
+            self.level.set(self.level.get() + 1);
+            true
+        } else {
+            false
+        }
     }
 
     fn on_attributes_changed(&self) {
@@ -4930,6 +8182,17 @@ impl Character {
         self.conditions.borrow().has(&Condition::Dead)
     }
 
+    pub fn morale(&self) -> Option<u32> {
+        match &self.kind {
+            CharacterKind::Player(..) => None,
+            CharacterKind::Bot(bot) => bot.morale,
+        }
+    }
+
+    pub fn is_downed(&self) -> bool {
+        self.conditions.borrow().has(&Condition::Downed)
+    }
+
     pub fn listen_to_changed_equipment(&self) -> Rc<Cell<bool>> {
         let signal = Rc::new(Cell::new(false));
         let weak = Rc::downgrade(&signal);
@@ -4948,6 +8211,14 @@ impl Character {
             EquipmentEntry::Weapon(weapon) if role == EquipmentSlotRole::MainHand => {
                 weapon.grip != WeaponGrip::TwoHanded || self.off_hand.get().is_empty()
             }
+            EquipmentEntry::Weapon(weapon) if role == EquipmentSlotRole::OffHand => {
+                // Only light weapons can be dual-wielded
+                weapon.grip == WeaponGrip::Light
+                    && self
+                        .weapon(HandType::MainHand)
+                        .map(|main_hand| main_hand.grip != WeaponGrip::TwoHanded)
+                        .unwrap_or(true)
+            }
             EquipmentEntry::Shield(..) if role == EquipmentSlotRole::OffHand => {
                 if let Some(weapon) = self.weapon(HandType::MainHand) {
                     weapon.grip != WeaponGrip::TwoHanded
@@ -5044,6 +8315,49 @@ impl Character {
         self.hand(hand).get().weapon
     }
 
+    /// The remaining and the max durability of the weapon in the given hand (if it can break)
+    pub fn weapon_durability(&self, hand: HandType) -> Option<(u32, u32)> {
+        let weapon = self.hand(hand).get().weapon?;
+        let max = weapon.durability?;
+        Some((max.saturating_sub(weapon.wear.0), max))
+    }
+
+    pub fn is_weapon_broken(&self, hand: HandType) -> bool {
+        matches!(self.weapon_durability(hand), Some((0, _)))
+    }
+
+    /// Returns true if the weapon broke
+    fn wear_weapon(&self, hand_type: HandType) -> bool {
+        match self.weapon_durability(hand_type) {
+            Some((remaining, _max)) if remaining > 0 => {
+                self.set_weapon_wear(hand_type, self.weapon_wear(hand_type) + 1);
+                remaining == 1
+            }
+            _ => false,
+        }
+    }
+
+    pub fn weapon_wear(&self, hand_type: HandType) -> u32 {
+        self.weapon(hand_type)
+            .map(|weapon| weapon.wear.0)
+            .unwrap_or(0)
+    }
+
+    /// Used when loading a saved character
+    pub fn set_weapon_wear(&self, hand_type: HandType, wear: u32) {
+        let cell = self.hand(hand_type);
+        let mut hand = cell.get();
+        if let Some(weapon) = &mut hand.weapon {
+            weapon.wear = Wear(wear);
+        }
+        cell.set(hand);
+    }
+
+    /// Not to be used during a fight
+    pub fn repair_weapon(&self, hand_type: HandType) {
+        self.set_weapon_wear(hand_type, 0);
+    }
+
     pub fn attack_range(
         &self,
         hand: HandType,
@@ -5071,6 +8385,10 @@ impl Character {
         }
     }
 
+    pub fn is_dual_wielding(&self) -> bool {
+        self.weapon(HandType::MainHand).is_some() && self.weapon(HandType::OffHand).is_some()
+    }
+
     pub fn has_equipped_melee_weapon(&self) -> bool {
         if let Some(weapon) = self.weapon(HandType::MainHand) {
             weapon.is_melee()
@@ -5079,6 +8397,12 @@ impl Character {
         }
     }
 
+    pub fn has_reach_weapon(&self) -> bool {
+        self.weapon(HandType::MainHand).is_some_and(|weapon| {
+            weapon.is_melee() && !matches!(weapon.threat_range, WeaponRange::Melee)
+        })
+    }
+
     pub fn shield(&self) -> Option<Shield> {
         self.hand(HandType::OffHand).get().shield
     }
@@ -5098,18 +8422,68 @@ impl Character {
 
     fn on_battle_start(&self) {
         self.update_player_encumbrance();
-        self.action_points.current.set(ACTION_POINTS_PER_TURN);
+        self.action_points.current.set(self.base_ap_per_turn.get());
         self.regain_full_movement();
         self.on_health_changed();
         self.has_taken_a_turn_this_round.set(false);
+        self.actions_this_turn.set(0);
+        self.abilities_used_this_fight.borrow_mut().clear();
+        self.damaged_this_round_by.borrow_mut().clear();
+        self.is_entering_guard_stance.set(false);
+        self.has_used_free_equipment_change.set(false);
+        self.ability_cooldowns.borrow_mut().clear();
         self.has_used_main_hand_reaction_this_round.set(false);
         self.has_used_off_hand_reaction_this_round.set(false);
     }
 
+    /// Recover between fights. Not to be used during a fight.
+    pub fn rest(&self, rest_type: RestType) -> RestOutcome {
+        let (health_fraction, mana_fraction) = rest_type.recovery();
+
+        let health_gained = self
+            .health
+            .gain((self.health.max() as f32 * health_fraction).ceil() as u32);
+        let mana_gained = self
+            .mana
+            .gain((self.mana.max() as f32 * mana_fraction).ceil() as u32);
+        let stamina_gained = self.stamina.max() - self.stamina.current();
+        self.stamina.set_to_max();
+
+        // Any lingering conditions from the last fight wear off (except for those that depend on
+        // the character's equipment or health, which are re-evaluated below)
+        self.conditions.borrow_mut().clear();
+        self.update_player_encumbrance();
+        self.on_health_changed();
+
+        RestOutcome {
+            health_gained,
+            mana_gained,
+            stamina_gained,
+        }
+    }
+
     fn on_new_round(&self) {
         self.has_taken_a_turn_this_round.set(false);
         self.has_used_main_hand_reaction_this_round.set(false);
         self.has_used_off_hand_reaction_this_round.set(false);
+        self.damaged_this_round_by.borrow_mut().clear();
+    }
+
+    fn record_damage_from(&self, attacker: &Character) {
+        let mut damaged_by = self.damaged_this_round_by.borrow_mut();
+        if !damaged_by.iter().any(|(id, _)| *id == attacker.id()) {
+            damaged_by.push((attacker.id(), attacker.player_controlled()));
+        }
+    }
+
+    /// Whether one of the attacker's allies has already damaged this character during the current round
+    pub fn is_focus_fire_target_for(&self, attacker: &Character) -> bool {
+        self.damaged_this_round_by
+            .borrow()
+            .iter()
+            .any(|(id, player_controlled)| {
+                *id != attacker.id() && *player_controlled == attacker.player_controlled()
+            })
     }
 
     fn update_player_encumbrance(&self) {
@@ -5127,8 +8501,31 @@ impl Character {
             .any(|entry| matches!(entry.get(), Some(EquipmentEntry::Consumable(..))))
     }
 
+    fn update_passive_skills_from_equipment(&self) {
+        {
+            let mut known = self.known_passive_skills.borrow_mut();
+            *known = self.learned_passives.borrow().clone();
+
+            for hand in [&self.main_hand, &self.off_hand] {
+                if let Some(skill) = hand.get().weapon.and_then(|weapon| weapon.grants_passive) {
+                    if !known.contains(&skill) {
+                        known.push(skill);
+                    }
+                }
+            }
+        }
+
+        // Some passive skills take effect via health-dependent conditions
+        self.on_health_changed();
+    }
+
     fn on_changed_equipment(&self) {
+        if self.weapon(HandType::MainHand).is_some() {
+            self.conditions.borrow_mut().remove(&Condition::Disarmed);
+        }
         self.update_player_encumbrance();
+        self.update_passive_skills_from_equipment();
+        self.update_off_hand_attack_action();
 
         self.changed_equipment_listeners
             .borrow_mut()
@@ -5151,13 +8548,41 @@ impl Character {
 
         for action in self.known_actions.borrow_mut().iter_mut() {
             if let BaseAction::Attack(attack) = action {
-                attack.action_point_cost = weapon.action_point_cost;
+                if attack.hand == hand_type {
+                    attack.action_point_cost = weapon.action_point_cost;
+                }
             }
         }
 
         self.on_changed_equipment();
     }
 
+    fn update_off_hand_attack_action(&self) {
+        let mut actions = self.known_actions.borrow_mut();
+        actions.retain(|action| {
+            !matches!(
+                action,
+                BaseAction::Attack(AttackAction {
+                    hand: HandType::OffHand,
+                    ..
+                })
+            )
+        });
+        if let Some(weapon) = self.weapon(HandType::OffHand) {
+            let off_hand_attack = BaseAction::Attack(AttackAction {
+                hand: HandType::OffHand,
+                action_point_cost: weapon.action_point_cost,
+            });
+            // Place it next to the main-hand attack
+            let idx = actions
+                .iter()
+                .position(|action| matches!(action, BaseAction::Attack(..)))
+                .map(|i| i + 1)
+                .unwrap_or(actions.len());
+            actions.insert(idx, off_hand_attack);
+        }
+    }
+
     pub fn set_shield(&self, shield: Shield) {
         assert!(self.can_equipment_fit(EquipmentEntry::Shield(shield), EquipmentSlotRole::OffHand));
         self.off_hand.set(Hand::with_shield(shield));
@@ -5177,6 +8602,9 @@ impl Character {
                 Some(EquipmentEntry::Shield(shield)) => {
                     self.set_shield(shield);
                 }
+                Some(EquipmentEntry::Weapon(weapon)) => {
+                    self.set_weapon(HandType::OffHand, weapon);
+                }
                 None => self.off_hand.set(Hand::default()),
                 _ => panic!(),
             },
@@ -5204,16 +8632,8 @@ impl Character {
         if let (Some(EquipmentEntry::Arrows(from_arrow)), Some(EquipmentEntry::Arrows(to_arrow))) =
             (from_content, to_content)
         {
-            if from_arrow.arrow == to_arrow.arrow {
-                // Merge the stacks
-                let quantity = from_arrow.quantity + to_arrow.quantity;
-                self.set_equipment(
-                    Some(EquipmentEntry::Arrows(ArrowStack::new(
-                        to_arrow.arrow,
-                        quantity,
-                    ))),
-                    to,
-                );
+            if let Some(merged) = to_arrow.merge(from_arrow) {
+                self.set_equipment(Some(EquipmentEntry::Arrows(merged)), to);
                 self.set_equipment(None, from);
                 return;
             }
@@ -5223,6 +8643,24 @@ impl Character {
     }
 
     pub fn try_gain_equipment(&self, entry: EquipmentEntry) -> bool {
+        if let EquipmentEntry::Arrows(gained) = entry {
+            // Add to an existing stack of the same arrows, if there is one
+            if let Some(merged) = self.arrows.get().and_then(|stack| stack.merge(gained)) {
+                self.arrows.set(Some(merged));
+                self.on_changed_equipment();
+                return true;
+            }
+            for slot in &self.inventory {
+                if let Some(EquipmentEntry::Arrows(stack)) = slot.get() {
+                    if let Some(merged) = stack.merge(gained) {
+                        slot.set(Some(EquipmentEntry::Arrows(merged)));
+                        self.on_changed_equipment();
+                        return true;
+                    }
+                }
+            }
+        }
+
         for slot in &self.inventory {
             if slot.get().is_none() {
                 slot.set(Some(entry));
@@ -5244,6 +8682,7 @@ impl Character {
         assert!(quantity > 0);
         if quantity == 1 {
             self.arrows.set(None);
+            self.ran_out_of_arrows.set(true);
         } else {
             self.arrows.set(Some(ArrowStack {
                 arrow: stack.arrow,
@@ -5281,17 +8720,32 @@ impl Character {
                     modifier += enhancement.range_bonus;
                 }
 
+                let within = |range: Option<Range>| {
+                    range.is_some_and(|range| {
+                        target_within_range_squared(range.squared(), self.pos(), target_position)
+                    })
+                };
+
                 if target_within_range_squared(
                     (range + modifier as f32).powf(2.0),
                     self.position.get(),
                     target_position,
                 ) {
-                    if target_within_range_squared(
-                        Range::Melee.squared(),
-                        self.pos(),
-                        target_position,
-                    ) {
-                        (Range::Melee, ActionReach::YesButDisadvantage("Too close"))
+                    let min_range = weapon.min_range;
+                    if within(min_range.impossible) {
+                        (min_range.impossible.unwrap(), ActionReach::No)
+                    } else if within(min_range.disadvantage) {
+                        (
+                            min_range.disadvantage.unwrap(),
+                            ActionReach::YesButDisadvantage("Too close"),
+                        )
+                    } else if weapon.effective_range.is_some_and(|effective| {
+                        !within(Some(Range::Float(effective + modifier as f32)))
+                    }) {
+                        (
+                            weapon_range.into_range(),
+                            ActionReach::YesButDisadvantage("Long range"),
+                        )
                     } else {
                         (weapon_range.into_range(), ActionReach::Yes)
                     }
@@ -5316,6 +8770,23 @@ impl Character {
             AbilityTarget::Area { .. } => {
                 within_range_squared(range.squared(), self.position.get(), target_pos)
             }
+            AbilityTarget::Teleport { .. } => {
+                let is_free = self
+                    .terrain
+                    .borrow()
+                    .as_ref()
+                    .is_some_and(|grid| grid.is_free(Some(self.id()), target_pos));
+                is_free && within_range_squared(range.squared(), self.position.get(), target_pos)
+            }
+            AbilityTarget::Trap { .. } => {
+                let is_open_ground = self
+                    .terrain
+                    .borrow()
+                    .as_ref()
+                    .is_some_and(|grid| grid.is_open_ground(target_pos));
+                is_open_ground
+                    && within_range_squared(range.squared(), self.position.get(), target_pos)
+            }
             AbilityTarget::None { .. } => {
                 panic!("Ability that has no target always reaches. Shouldn't be checked")
             }
@@ -5371,24 +8842,84 @@ impl Character {
     pub fn can_attack(&self, attack: AttackAction) -> bool {
         let ap = self.action_points.current();
         matches!(self.weapon(attack.hand), Some(weapon) if ap >= weapon.action_point_cost)
+            && self.stamina.current() >= self.fatigue_stamina_cost()
+    }
+
+    /// The extra stamina that the next attack or ability costs, from having already acted this turn
+    pub fn fatigue_stamina_cost(&self) -> u32 {
+        if self.actions_this_turn.get() > 0 {
+            FATIGUE_STAMINA_COST
+        } else {
+            0
+        }
+    }
+
+    pub fn has_used_ability_this_fight(&self, ability_id: AbilityId) -> bool {
+        self.abilities_used_this_fight
+            .borrow()
+            .contains(&ability_id)
+    }
+
+    pub fn ability_cooldown_remaining_rounds(&self, ability_id: AbilityId) -> u32 {
+        let game_time = self.current_game_time.get();
+        self.ability_cooldowns
+            .borrow()
+            .iter()
+            .find(|(id, _)| *id == ability_id)
+            .filter(|(_, available_at)| *available_at > game_time)
+            .map(|(_, available_at)| {
+                let remaining = available_at - game_time;
+                (remaining as f32 / self.round_length.get().unwrap() as f32).ceil() as u32
+            })
+            .unwrap_or(0)
+    }
+
+    fn start_ability_cooldown(&self, ability: Ability) {
+        if ability.cooldown_rounds == 0 {
+            return;
+        }
+        let available_at = self.current_game_time.get()
+            + ability.cooldown_rounds * self.round_length.get().unwrap();
+        let mut cooldowns = self.ability_cooldowns.borrow_mut();
+        cooldowns.retain(|(id, _)| *id != ability.id);
+        cooldowns.push((ability.id, available_at));
+    }
+
+    fn on_performed_fatiguing_action(&self) {
+        self.actions_this_turn.set(self.actions_this_turn.get() + 1);
+    }
+
+    pub fn has_free_equipment_change(&self) -> bool {
+        self.known_passive_skills
+            .borrow()
+            .contains(&PassiveSkill::Quickdraw)
+            && !self.has_used_free_equipment_change.get()
+    }
+
+    pub fn change_equipment_ap_cost(&self) -> u32 {
+        if self.has_free_equipment_change() {
+            0
+        } else {
+            BaseAction::ChangeEquipment.action_point_cost() as u32
+        }
     }
 
     pub fn can_use_action(&self, action: BaseAction) -> bool {
         let sta = self.stamina.current();
         let ap = self.action_points.current();
         match action {
-            BaseAction::Attack(attack) => {
-                matches!(self.weapon(attack.hand), Some(weapon) if ap >= weapon.action_point_cost)
-            }
+            BaseAction::Attack(attack) => self.can_attack(attack),
             BaseAction::UseAbility(ability) => self.can_use_ability(ability),
-            BaseAction::Move => self.remaining_movement.get() > 1.0 || sta > 0,
-            BaseAction::ChangeEquipment => {
-                ap as i32 >= BaseAction::ChangeEquipment.action_point_cost()
+            BaseAction::Move => {
+                !self.is_immobilized() && (self.remaining_movement.get() > 1.0 || sta > 0)
             }
+            BaseAction::ChangeEquipment => ap >= self.change_equipment_ap_cost(),
             BaseAction::UseConsumable => {
                 self.has_any_consumable_in_inventory()
                     && ap as i32 >= BaseAction::UseConsumable.action_point_cost()
             }
+            BaseAction::DelayTurn => true,
+            BaseAction::GuardStance => ap > 0,
         }
     }
 
@@ -5400,8 +8931,31 @@ impl Character {
         if ability.requires_melee_weapon() && !self.has_equipped_melee_weapon() {
             return false;
         }
+        if ability.requires_dual_wield() && !self.is_dual_wielding() {
+            return false;
+        }
+        if ability.requires_reach_weapon() && !self.has_reach_weapon() {
+            return false;
+        }
+        if ability.id == AbilityId::StandUp && !self.conditions.borrow().has(&Condition::Prone) {
+            return false;
+        }
+        if ability.id == AbilityId::Hide && self.is_adjacent_to_enemy.get() {
+            return false;
+        }
+        if self.has_used_ability_this_fight(ability.id) {
+            return false;
+        }
+        if ability.id == AbilityId::Aim
+            && self.conditions.borrow().get_stacks(&Condition::Aiming) >= MAX_AIMING_STACKS
+        {
+            return false;
+        }
+        if self.ability_cooldown_remaining_rounds(ability.id) > 0 {
+            return false;
+        }
         ap >= ability.action_point_cost
-            && self.stamina.current() >= ability.stamina_cost
+            && self.stamina.current() >= ability.stamina_cost + self.fatigue_stamina_cost()
             && self.mana.current() >= ability.mana_cost
     }
 
@@ -5413,10 +8967,10 @@ impl Character {
             }
             BaseAction::UseAbility(ability) => ap >= ability.action_point_cost,
             BaseAction::Move => true,
-            BaseAction::ChangeEquipment => {
-                ap as i32 >= BaseAction::ChangeEquipment.action_point_cost()
-            }
+            BaseAction::ChangeEquipment => ap >= self.change_equipment_ap_cost(),
             BaseAction::UseConsumable => ap as i32 >= BaseAction::UseConsumable.action_point_cost(),
+            BaseAction::DelayTurn => true,
+            BaseAction::GuardStance => ap > 0,
         }
     }
 
@@ -5459,7 +9013,7 @@ impl Character {
                 known.push(("".to_owned(), *enhancement))
             }
 
-            if let Some(arrows) = self.arrows.get() {
+            if let Some(arrows) = self.arrows.get().filter(|_| !weapon.thrown) {
                 assert!(arrows.quantity > 0);
                 known.push((
                     arrows.arrow.name.to_string(),
@@ -5497,7 +9051,23 @@ impl Character {
         usable
     }
 
+    /// Whether standing at `from`, this character could make an opportunity attack against `target`
+    pub fn threatens(&self, from: Position, target: Position) -> bool {
+        let threat_range = self
+            .weapon(HandType::MainHand)
+            .map(|weapon| weapon.threat_range)
+            .unwrap_or(WeaponRange::Melee);
+        within_range_squared(threat_range.center_to_center_squared(), from, target)
+    }
+
     pub fn can_use_opportunity_attack(&self, target: CharacterId) -> bool {
+        if self.conditions.borrow().has(&Condition::Frozen)
+            || self.conditions.borrow().has(&Condition::Petrified)
+            || self.conditions.borrow().has(&Condition::Panicked)
+            || self.is_downed()
+        {
+            return false;
+        }
         if !self
             .known_passive_skills
             .borrow()
@@ -5533,10 +9103,24 @@ impl Character {
             && self.action_points.current()
                 >= weapon.action_point_cost + enhancement.action_point_cost
                     - enhancement.effect.action_point_discount
-            && self.stamina.current() >= enhancement.stamina_cost
+            && self.stamina.current() >= enhancement.stamina_cost + self.fatigue_stamina_cost()
             && self.mana.current() >= enhancement.mana_cost
     }
 
+    pub fn known_movement_enhancements(&self) -> Vec<MovementEnhancement> {
+        // Anyone can sprint
+        vec![SPRINT]
+    }
+
+    pub fn can_use_movement_enhancement(&self, enhancement: &MovementEnhancement) -> bool {
+        self.action_points.current() >= enhancement.action_point_cost
+            && self.stamina.current() >= enhancement.stamina_cost
+    }
+
+    pub fn movement_enhancement_distance(&self, enhancement: &MovementEnhancement) -> f32 {
+        self.agility() as f32 * enhancement.bonus_distance_per_agility
+    }
+
     pub fn known_on_attacked_reactions(&self) -> Vec<OnAttackedReaction> {
         let mut known = vec![];
         for reaction in self.known_attacked_reactions.borrow().iter() {
@@ -5560,6 +9144,20 @@ impl Character {
         is_within_melee: bool,
         self_defense: bool,
     ) -> Vec<OnAttackedReaction> {
+        if self.conditions.borrow().has(&Condition::Frozen)
+            || self.conditions.borrow().has(&Condition::Petrified)
+            || self.conditions.borrow().has(&Condition::Panicked)
+            || self.is_downed()
+        {
+            return vec![];
+        }
+        if self
+            .known_passive_skills
+            .borrow()
+            .contains(&PassiveSkill::Berserker)
+        {
+            return vec![];
+        }
         let mut usable = self.known_on_attacked_reactions();
         usable.retain(|reaction| {
             self.can_use_on_attacked_reaction(*reaction, is_within_melee, self_defense)
@@ -5621,6 +9219,13 @@ impl Character {
     }
 
     pub fn usable_on_hit_reactions(&self, is_within_melee: bool) -> Vec<(String, OnHitReaction)> {
+        if self.conditions.borrow().has(&Condition::Frozen)
+            || self.conditions.borrow().has(&Condition::Petrified)
+            || self.conditions.borrow().has(&Condition::Panicked)
+            || self.is_downed()
+        {
+            return vec![];
+        }
         let mut usable = self.known_on_hit_reactions();
         usable.retain(|r| self.can_use_on_hit_reaction(r.1, is_within_melee));
         usable
@@ -5641,6 +9246,7 @@ impl Character {
         let ap = self.action_points.current();
         ap >= reaction.action_point_cost
             && self.stamina.current() >= reaction.stamina_cost
+            && self.mana.current() >= reaction.mana_cost
             && allowed
     }
 
@@ -5678,7 +9284,8 @@ impl Character {
     ) -> bool {
         self.action_points.current() >= ability.action_point_cost + enhancement.action_point_cost
             && self.mana.current() >= ability.mana_cost + enhancement.mana_cost
-            && self.stamina.current() >= ability.stamina_cost + enhancement.stamina_cost
+            && self.stamina.current()
+                >= ability.stamina_cost + enhancement.stamina_cost + self.fatigue_stamina_cost()
     }
 
     fn strength(&self) -> u32 {
@@ -5697,6 +9304,16 @@ impl Character {
         (self.base_attributes.spirit.get() as i32).max(1) as u32
     }
 
+    pub fn spell_damage(&self, damage: AbilityDamage) -> u32 {
+        match damage {
+            AbilityDamage::Static(n) | AbilityDamage::AtLeast(n) => n,
+            AbilityDamage::Scaling {
+                base,
+                per_modifier_tenths,
+            } => base + self.spell_modifier() * per_modifier_tenths / 10,
+        }
+    }
+
     pub fn spell_modifier(&self) -> u32 {
         let mut res = self.intellect() + self.spirit();
 
@@ -5734,6 +9351,7 @@ impl Character {
         if conditions.has(&Condition::Braced) {
             res += BRACED_DEFENSE_BONUS;
         }
+        res += conditions.get_stacks(&Condition::Guarding);
 
         if conditions.has(&Condition::Distracted) {
             res = res.saturating_sub(DISTRACTED_DEFENSE_PENALTY);
@@ -5772,10 +9390,34 @@ impl Character {
     }
 
     pub fn defense(&self, defense_type: DefenseType) -> u32 {
+        self.resolve_defense(defense_type).1
+    }
+
+    /// Returns the concrete defense that applies (relevant for combinators like LowerOf), along
+    /// with its value
+    pub fn resolve_defense(&self, defense_type: DefenseType) -> (DefenseType, u32) {
         match defense_type {
-            DefenseType::Will => self.will(),
-            DefenseType::Evasion => self.evasion(),
-            DefenseType::Toughness => self.toughness(),
+            DefenseType::Will => (defense_type, self.will()),
+            DefenseType::Evasion => (defense_type, self.evasion()),
+            DefenseType::Toughness => (defense_type, self.toughness()),
+            DefenseType::LowerOf(a, b) => {
+                let a = self.resolve_defense(*a);
+                let b = self.resolve_defense(*b);
+                if b.1 < a.1 {
+                    b
+                } else {
+                    a
+                }
+            }
+            DefenseType::HigherOf(a, b) => {
+                let a = self.resolve_defense(*a);
+                let b = self.resolve_defense(*b);
+                if b.1 > a.1 {
+                    b
+                } else {
+                    a
+                }
+            }
         }
     }
 
@@ -5804,20 +9446,31 @@ impl Character {
     }
 
     pub fn protection_from_armor(&self) -> u32 {
+        self.protection_against(None)
+    }
+
+    /// Untyped damage is protected against like physical damage
+    pub fn protection_against(&self, damage_type: Option<DamageType>) -> u32 {
+        let is_physical = damage_type.is_none_or(|t| t.is_physical());
         let mut protection = 0;
         if let Some(armor) = self.armor_piece.get() {
-            protection += armor.protection;
-        }
-        if let Some(shield) = self.shield() {
-            protection += shield.armor;
+            protection += match damage_type {
+                Some(damage_type) => armor.resistance(damage_type),
+                None => armor.protection,
+            };
         }
+        if is_physical {
+            if let Some(shield) = self.shield() {
+                protection += shield.armor;
+            }
 
-        if self
-            .known_passive_skills
-            .borrow()
-            .contains(&PassiveSkill::HardenedSkin)
-        {
-            protection += 1;
+            if self
+                .known_passive_skills
+                .borrow()
+                .contains(&PassiveSkill::HardenedSkin)
+            {
+                protection += 1;
+            }
         }
 
         if self.has_condition(&Condition::Exposed) {
@@ -5926,8 +9579,15 @@ impl Character {
         let target_pos = target.pos();
         let mut bonuses = vec![];
 
-        if is_target_flanked(self.pos(), target) {
-            bonuses.push(("Flanked", RollBonusContributor::FlatAmount(5)));
+        let flankers = count_target_flankers(self.pos(), target);
+        if flankers > 0 {
+            let label = match flankers {
+                1 => "Flanked",
+                2 => "Flanked x2",
+                _ => "Flanked x3",
+            };
+            let bonus = FLANKED_BONUS + FLANKED_BONUS_PER_EXTRA_FLANKER * (flankers as i32 - 1);
+            bonuses.push((label, RollBonusContributor::FlatAmount(bonus)));
         }
 
         let (_range, reach) = self.reaches_with_attack(
@@ -5950,6 +9610,26 @@ impl Character {
                 bonuses.push((name, RollBonusContributor::FlatAmount(effect.roll_modifier)));
             }
         }
+        if hand_type == HandType::OffHand {
+            bonuses.push(("Off-hand", RollBonusContributor::FlatAmount(-3)));
+        }
+        if !self.weapon(hand_type).unwrap().is_melee() {
+            let height_difference = self.elevation_at(self.pos()) - self.elevation_at(target_pos);
+            if height_difference > 0 {
+                bonuses.push((
+                    "High ground",
+                    RollBonusContributor::FlatAmount(HIGH_GROUND_BONUS),
+                ));
+            } else if height_difference < 0 {
+                bonuses.push((
+                    "Low ground",
+                    RollBonusContributor::FlatAmount(-HIGH_GROUND_BONUS),
+                ));
+            }
+        }
+        if matches!(self.taunter(), Some(taunter) if taunter != target.id()) {
+            bonuses.push(("Taunted", RollBonusContributor::Advantage(-1)));
+        }
         let exertion_penalty = self.hand_exertion(hand_type) as i32;
         if exertion_penalty > 0 {
             bonuses.push((
@@ -5960,7 +9640,28 @@ impl Character {
         if self.is_dazed() {
             bonuses.push(("Dazed", RollBonusContributor::Advantage(-1)));
         }
+        if self.is_hidden() {
+            bonuses.push(("Hidden", RollBonusContributor::Advantage(1)));
+        }
+        if target.is_focus_fire_target_for(self) {
+            bonuses.push(("Focus fire", RollBonusContributor::Advantage(1)));
+        }
+        if target.conditions.borrow().has(&Condition::Prone) {
+            // It's easy to strike someone that's lying on the ground, but hard to shoot them
+            if self.weapon(hand_type).unwrap().is_melee() {
+                bonuses.push(("Target prone", RollBonusContributor::Advantage(1)));
+            } else {
+                bonuses.push(("Target prone", RollBonusContributor::Advantage(-1)));
+            }
+        }
         let conditions = self.conditions.borrow();
+        if conditions.has(&Condition::Prone) {
+            bonuses.push(("Prone", RollBonusContributor::Advantage(-1)));
+        }
+        let aim = conditions.get_stacks(&Condition::Aiming) as i32;
+        if aim > 0 {
+            bonuses.push(("Aiming", RollBonusContributor::FlatAmount(aim)));
+        }
         if conditions.has(&Condition::Raging)
             && self.weapon(hand_type).unwrap().range == WeaponRange::Melee
         {
@@ -6060,7 +9761,8 @@ impl Character {
 
     fn incoming_attack_advantage(&self, reaction: Option<OnAttackedReaction>) -> i32 {
         let mut advantage = 0;
-        for (_label, bonus) in self.incoming_attack_bonuses(reaction) {
+        // Cover only affects evasion, not advantage
+        for (_label, bonus) in self.incoming_attack_bonuses(reaction, false) {
             match bonus {
                 RollBonusContributor::Advantage(n) => advantage += n,
                 RollBonusContributor::OtherNegative | RollBonusContributor::OtherPositive => {}
@@ -6085,8 +9787,12 @@ impl Character {
     pub fn incoming_attack_bonuses(
         &self,
         reaction: Option<OnAttackedReaction>,
+        has_cover: bool,
     ) -> Vec<(&'static str, RollBonusContributor)> {
         let mut terms = vec![];
+        if has_cover {
+            terms.push(("Cover", RollBonusContributor::OtherNegative));
+        }
         if self.is_dazed() {
             terms.push(("Dazed", RollBonusContributor::OtherPositive));
         }
@@ -6135,12 +9841,41 @@ impl Character {
         self.conditions.borrow().get(&Condition::Bleeding).is_some()
     }
 
+    pub fn resist_condition(&self, condition: Condition, mut amount: u32) -> u32 {
+        if let Some(percentage) = self
+            .condition_resistance_percentages
+            .borrow()
+            .get(&condition)
+        {
+            amount -= amount * (*percentage).min(100) / 100;
+        }
+        if let Some(flat) = self.condition_resistances.borrow().get(&condition) {
+            amount = amount.saturating_sub(*flat);
+        }
+        amount
+    }
+
+    pub fn is_immune_to(&self, condition: Condition) -> bool {
+        // Nothing gets through to a petrified character
+        self.condition_immunities.borrow().contains(&condition)
+            || self.conditions.borrow().has(&Condition::Petrified)
+    }
+
     pub fn receive_condition(
         &self,
         condition: Condition,
         stacks: Option<u32>,
         ends_at: Option<u32>,
     ) {
+        if self.is_immune_to(condition) {
+            return;
+        }
+
+        let stacks = stacks.map(|stacks| self.resist_condition(condition, stacks));
+        if stacks == Some(0) {
+            return;
+        }
+
         let mut conditions = self.conditions.borrow_mut();
 
         if let Some(state) = conditions.map.get_mut(&condition) {
@@ -6167,6 +9902,13 @@ impl Character {
                 self.action_points.gain(HASTENED_AP_BONUS);
                 self.remaining_movement
                     .set(self.remaining_movement.get() + self.base_move_speed.get() * 0.25);
+            } else if condition == Condition::Prone
+                && !self
+                    .known_actions()
+                    .contains(&BaseAction::UseAbility(STAND_UP))
+            {
+                // Only those who have been knocked down need a way to get back up
+                self.learn_ability(STAND_UP);
             }
 
             conditions
@@ -6175,6 +9917,16 @@ impl Character {
         }
     }
 
+    pub fn channeled_ability(&self) -> Option<ChanneledAbility> {
+        self.channeled_ability.get()
+    }
+
+    fn break_channel(&self) -> Option<ChanneledAbility> {
+        self.is_losing_channel.set(false);
+        self.clear_condition(Condition::Channeling);
+        self.channeled_ability.take()
+    }
+
     fn clear_condition(&self, condition: Condition) -> Option<u32> {
         let mut conditions = self.conditions.borrow_mut();
 
@@ -6182,7 +9934,10 @@ impl Character {
             return None;
         }
 
-        let prev_stacks = conditions.get_stacks(&condition);
+        let prev_stacks = conditions
+            .get(&condition)
+            .and_then(|state| state.stacks)
+            .unwrap_or(0);
 
         conditions.remove(&condition);
 
@@ -6192,9 +9947,108 @@ impl Character {
     fn has_condition(&self, condition: &Condition) -> bool {
         self.conditions.borrow().has(condition)
     }
-}
+
+    pub fn set_terrain(&self, pathfind_grid: &Rc<PathfindGrid>) {
+        *self.terrain.borrow_mut() = Some(Rc::clone(pathfind_grid));
+    }
+
+    pub fn elevation_at(&self, pos: Position) -> i32 {
+        self.terrain
+            .borrow()
+            .as_ref()
+            .map(|grid| grid.elevation(pos))
+            .unwrap_or(0)
+    }
+
+    pub fn consecutive_hits(&self) -> u32 {
+        self.consecutive_hits.get()
+    }
+
+    fn set_consecutive_hits(&self, hits: u32) {
+        self.consecutive_hits.set(hits);
+        if self
+            .known_passive_skills
+            .borrow()
+            .contains(&PassiveSkill::Momentum)
+        {
+            self.conditions
+                .borrow_mut()
+                .set_stacks(Condition::Momentum, hits);
+        }
+    }
+
+    fn dispel(&self, dispel: DispelEffect) -> Vec<Condition> {
+        let removed: Vec<Condition> = self
+            .conditions
+            .borrow()
+            .map
+            .keys()
+            .filter(|condition| {
+                condition.is_dispellable() && condition.is_positive() == dispel.positive
+            })
+            .take(dispel.max_count as usize)
+            .copied()
+            .collect();
+        for condition in &removed {
+            self.clear_condition(*condition);
+            if *condition == Condition::Taunted {
+                self.taunted_by.set(None);
+            }
+        }
+        removed
+    }
+
+    pub fn can_intercept(&self, attacker_pos: Position, victim_pos: Position) -> bool {
+        self.known_passive_skills
+            .borrow()
+            .contains(&PassiveSkill::Guardian)
+            && self.shield().is_some()
+            && !self.conditions.borrow().has(&Condition::Frozen)
+            && !self.conditions.borrow().has(&Condition::Petrified)
+            && !self.conditions.borrow().has(&Condition::Panicked)
+            && !self.is_downed()
+            && self.action_points.current() >= GUARDIAN_AP_COST
+            && are_entities_within_melee(self.pos(), attacker_pos)
+            && are_entities_within_melee(self.pos(), victim_pos)
+    }
+
+    pub fn can_counterspell(&self, caster_pos: Position) -> bool {
+        self.known_passive_skills
+            .borrow()
+            .contains(&PassiveSkill::Counterspell)
+            && !self.conditions.borrow().has(&Condition::Frozen)
+            && !self.conditions.borrow().has(&Condition::Petrified)
+            && !self.conditions.borrow().has(&Condition::Panicked)
+            && !self.is_downed()
+            && self.action_points.current() >= COUNTERSPELL_AP_COST
+            && self.mana.current() >= COUNTERSPELL_MANA_COST
+            && within_range_squared(COUNTERSPELL_RANGE.squared(), self.pos(), caster_pos)
+    }
+
+    pub fn is_hidden(&self) -> bool {
+        self.has_condition(&Condition::Hidden)
+    }
+}
+
+const FLANKED_BONUS: i32 = 5;
+const FLANKED_BONUS_PER_EXTRA_FLANKER: i32 = 2;
+const HIGH_GROUND_BONUS: i32 = 2;
+// Morale checks happen as the enemy side drops below these fractions of its starting count
+const MORALE_THRESHOLDS: [f32; 2] = [0.5, 0.25];
+const MORALE_PRESSURE_PER_THRESHOLD: u32 = 5;
+// The area that a splashing spell also hits, around its target
+const SPLASH_RADIUS: Range = Range::Float(1.5);
+const MAX_COUNTED_FLANKERS: u32 = 3;
+const PARRY_MIN_BONUS_EVASION: u32 = 3;
+const MAX_AIMING_STACKS: u32 = 5;
+const PARRY_MAX_EXTRA_EVASION: u32 = 3;
 
 fn is_target_flanked(attacker_pos: Position, target: &Character) -> bool {
+    count_target_flankers(attacker_pos, target) > 0
+}
+
+/// The number of melee engagers that flank the target together with the attacker (capped)
+fn count_target_flankers(attacker_pos: Position, target: &Character) -> u32 {
     /*
     println!(
         "Check if target {} (pos={:?}) is flanked, from attacker pos {:?} ...",
@@ -6209,18 +10063,54 @@ fn is_target_flanked(attacker_pos: Position, target: &Character) -> bool {
         .contains(&PassiveSkill::ThrillOfBattle);
 
     if target_is_immune_to_flanking {
-        return false;
-    }
-
-    if target.conditions.borrow().has(&Condition::Blinded) {
-        return true;
+        return 0;
     }
 
-    target
+    // An engager that only reaches the target from a distance (with a reach weapon) is not
+    // pressing against it, so it doesn't contribute to flanking.
+    let mut flankers = target
         .is_engaged_by
         .borrow()
         .values()
-        .any(|engager| are_flanking_target(attacker_pos, engager.pos(), target.pos()))
+        .filter(|engager| within_meele(engager.pos(), target.pos()))
+        .filter(|engager| are_flanking_target(attacker_pos, engager.pos(), target.pos()))
+        .count() as u32;
+
+    if target.conditions.borrow().has(&Condition::Blinded) {
+        flankers = flankers.max(1);
+    }
+
+    flankers.min(MAX_COUNTED_FLANKERS)
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Direction {
+    North,
+    East,
+    South,
+    West,
+}
+
+impl Direction {
+    fn toward(from: Position, to: Position) -> Option<Self> {
+        let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+        if (dx, dy) == (0, 0) {
+            None
+        } else if dx.abs() >= dy.abs() {
+            Some(if dx > 0 { Self::East } else { Self::West })
+        } else {
+            Some(if dy > 0 { Self::South } else { Self::North })
+        }
+    }
+
+    fn vector(&self) -> (i32, i32) {
+        match self {
+            Self::North => (0, -1),
+            Self::East => (1, 0),
+            Self::South => (0, 1),
+            Self::West => (-1, 0),
+        }
+    }
 }
 
 fn are_flanking_target(attacker: Position, melee_engager: Position, target: Position) -> bool {
@@ -6304,6 +10194,76 @@ pub enum ActionReach {
     No,
 }
 
+/// The characters that an area effect would be applied to, if centered at `area_pos`. This is
+/// the same selection that is made when the effect is performed, so that the UI can use it to
+/// predict friendly fire while the player is aiming.
+pub fn characters_in_area<'a>(
+    characters: impl Iterator<Item = &'a Rc<Character>>,
+    caster: &Character,
+    area_pos: Position,
+    mut shape: AreaShape,
+    mut acquisition: AreaTargetAcquisition,
+    enhancements: &[AbilityEnhancement],
+) -> Vec<CharacterId> {
+    if acquisition == AreaTargetAcquisition::Enemies && caster.has_condition(&Condition::Confused) {
+        // Can't tell friend from foe
+        acquisition = AreaTargetAcquisition::Everyone;
+    }
+
+    let spare_allies = enhancements
+        .iter()
+        .any(|e| e.spell_effect.is_some_and(|e| e.spare_allies));
+
+    for e in enhancements.iter().filter_map(|e| e.spell_effect) {
+        if e.increased_radius_tenths > 0 {
+            let AreaShape::Circle(radius) = &mut shape else {
+                panic!()
+            };
+            *radius = radius.plusf(e.increased_radius_tenths as f32 * 0.1);
+        }
+    }
+
+    characters
+        .filter(|other_char| {
+            let is_ally = other_char.player_controlled() == caster.player_controlled();
+            match acquisition {
+                AreaTargetAcquisition::Enemies => !is_ally,
+                AreaTargetAcquisition::Allies => is_ally,
+                AreaTargetAcquisition::Everyone => !(is_ally && spare_allies),
+            }
+        })
+        .filter(|other_char| is_target_within_shape(caster.pos(), area_pos, shape, other_char))
+        .map(|other_char| other_char.id())
+        .collect()
+}
+
+pub fn area_cells(caster_pos: Position, area_pos: Position, shape: AreaShape) -> Vec<Position> {
+    let mut cells = vec![];
+    match shape {
+        AreaShape::Circle(radius) => {
+            let radius = f32::from(radius);
+            let r = radius.ceil() as i32;
+            for x in area_pos.0 - r..=area_pos.0 + r {
+                for y in area_pos.1 - r..=area_pos.1 + r {
+                    if within_range_squared(radius.powf(2.0), area_pos, (x, y)) {
+                        cells.push((x, y));
+                    }
+                }
+            }
+        }
+        AreaShape::Line => {
+            line_visitor(caster_pos, area_pos, |x, y| {
+                // Leave the caster's own footprint alone
+                if (x - caster_pos.0).abs() > 1 || (y - caster_pos.1).abs() > 1 {
+                    cells.push((x, y));
+                }
+                false
+            });
+        }
+    }
+    cells
+}
+
 pub fn is_target_within_shape(
     caster_pos: Position,
     area_pos: Position,
@@ -6319,6 +10279,65 @@ pub fn is_target_within_shape(
     }
 }
 
+/// For abilities that make the caster move into melee before striking (see [AbilityReach::MoveIntoMelee]).
+/// Returns the movement path (including the start position) if some cell in melee with the target can
+/// actually be reached within the ability's range. An empty path means that no movement is needed.
+pub fn can_move_into_melee(
+    pathfind_grid: &PathfindGrid,
+    caster: &Character,
+    target: &Character,
+    ability: Ability,
+    enhancements: &[AbilityEnhancement],
+) -> Option<Vec<Position>> {
+    let AbilityTarget::Enemy {
+        reach: AbilityReach::MoveIntoMelee(..),
+        ..
+    } = ability.target
+    else {
+        return None;
+    };
+
+    if within_meele(caster.pos(), target.pos()) {
+        return Some(vec![]);
+    }
+    if caster.is_immobilized() {
+        return None;
+    }
+
+    let range = ability.target.range(enhancements).unwrap();
+    find_path_into_melee(
+        pathfind_grid,
+        caster.id(),
+        caster.pos(),
+        target.pos(),
+        range,
+    )
+    .map(|path| path.positions.iter().map(|(_dist, pos)| *pos).collect())
+}
+
+/// Unlike [PathfindGrid::find_shortest_path_to_adjacent], this never returns a fallback path that
+/// ends up outside of melee (or further away than the given range).
+pub fn find_path_into_melee(
+    pathfind_grid: &PathfindGrid,
+    character_id: CharacterId,
+    start: Position,
+    target_pos: Position,
+    range: Range,
+) -> Option<Path> {
+    let path = pathfind_grid.find_shortest_path_to_adjacent(
+        character_id,
+        start,
+        target_pos,
+        f32::from(range) - 1.0,
+    )?;
+    let (_, destination) = *path.positions.last()?;
+    if within_meele(destination, target_pos) && path.total_distance <= f32::from(range) {
+        Some(path)
+    } else {
+        None
+    }
+}
+
 pub fn within_range_squared(range_squared: f32, source: Position, destination: Position) -> bool {
     sq_distance_between(source, destination) <= range_squared
 }
@@ -6350,6 +10369,43 @@ is therefore sqrt(1^2 + 2^2)
 pub const TOUCHING_MELEE_RANGE_SQUARED: f32 = 5.0;
 pub const CENTER_MELEE_RANGE_SQUARED: f32 = 13.0;
 
+/// The steps that a character takes when pulled toward `toward`, or None if it can't get any
+/// closer
+fn find_pull_path(
+    grid: &PathfindGrid,
+    character: &Character,
+    toward: Position,
+    max_distance: u32,
+) -> Option<Vec<Position>> {
+    if within_meele(character.pos(), toward) {
+        return None;
+    }
+
+    // If the cells next to the puller are taken, this gives us the closest reachable cell instead.
+    // The path may lead further than the pull reaches; only the first part of it is taken.
+    let path = grid.find_shortest_path_to_adjacent(
+        character.id(),
+        character.pos(),
+        toward,
+        distance_between(character.pos(), toward) + max_distance as f32,
+    )?;
+
+    let positions: Vec<Position> = path
+        .positions
+        .iter()
+        .take_while(|(distance, _pos)| *distance <= max_distance as f32)
+        .map(|(_distance, pos)| *pos)
+        .collect();
+
+    let destination = *positions.last().unwrap();
+    if positions.len() < 2
+        || sq_distance_between(destination, toward) >= sq_distance_between(character.pos(), toward)
+    {
+        return None;
+    }
+    Some(positions)
+}
+
 fn within_meele(source: Position, destination: Position) -> bool {
     within_range_squared(CENTER_MELEE_RANGE_SQUARED, source, destination)
 }
@@ -6440,12 +10496,56 @@ impl NumberedResource {
 pub struct ArmorPiece {
     pub name: &'static str,
     pub protection: u32,
+    // Overrides `protection` for the listed damage types
+    pub resistances: &'static [(DamageType, u32)],
     pub limit_evasion_from_agi: Option<u32>,
     pub icon: EquipmentIconId,
     pub weight: u32,
     pub equip: EquipEffect,
 }
 
+impl ArmorPiece {
+    pub fn resistance(&self, damage_type: DamageType) -> u32 {
+        if let Some((_, resistance)) = self.resistances.iter().find(|(t, _)| *t == damage_type) {
+            *resistance
+        } else if damage_type.is_physical() {
+            self.protection
+        } else {
+            0
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum DamageType {
+    Slashing,
+    Piercing,
+    Bludgeoning,
+    Fire,
+    Cold,
+    Necrotic,
+}
+
+impl DamageType {
+    pub const fn name(&self) -> &'static str {
+        match self {
+            DamageType::Slashing => "slashing",
+            DamageType::Piercing => "piercing",
+            DamageType::Bludgeoning => "bludgeoning",
+            DamageType::Fire => "fire",
+            DamageType::Cold => "cold",
+            DamageType::Necrotic => "necrotic",
+        }
+    }
+
+    pub const fn is_physical(&self) -> bool {
+        matches!(
+            self,
+            DamageType::Slashing | DamageType::Piercing | DamageType::Bludgeoning
+        )
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub struct EquipEffect {
     pub bonus_spell_modifier: u32,
@@ -6465,17 +10565,53 @@ pub struct Weapon {
     pub sprite: Option<SpriteId>,
     pub icon: EquipmentIconId,
     pub range: WeaponRange,
+    // The zone within which the wielder can make opportunity attacks. Can exceed `range` for
+    // reach weapons.
+    pub threat_range: WeaponRange,
+    // Ranged weapons are awkward to use against targets that are too close
+    pub min_range: MinRange,
+    // Ranged weapons that are inaccurate beyond this range (but can still reach up to `range`)
+    pub effective_range: Option<f32>,
     pub action_point_cost: u32,
     pub damage: u32,
+    pub damage_type: DamageType,
     pub grip: WeaponGrip,
     pub attack_attribute: AttackAttribute,
     pub attack_enhancement: Option<AttackEnhancement>,
     pub on_attacked_reaction: Option<OnAttackedReaction>,
     // TODO: Not used?
     pub on_true_hit: Option<AttackHitEffect>,
+    // Only triggers on critical hits (in addition to on_true_hit)
+    pub on_crit: Option<AttackHitEffect>,
+    // How much a critical hit increases the damage
+    pub crit_multiplier_percent: u32,
+    // The lowest attack result (after subtracting evasion) that counts as a critical hit
+    pub crit_threshold: i32,
+    // The wielder heals for this percentage of the damage that their attacks deal
+    pub lifesteal_percent: u32,
+    // Counts as a known passive skill for as long as the weapon is equipped
+    pub grants_passive: Option<PassiveSkill>,
+    // Leaves the hand when attacking with it
+    pub thrown: bool,
+    // How many exerting attacks the weapon can make before it breaks. None means it never breaks.
+    pub durability: Option<u32>,
+    // How much of the durability has been used up. Lives on the weapon itself so that it follows
+    // the weapon between hands and inventory.
+    pub wear: Wear,
     pub weight: u32,
 }
 
+/// Wear doesn't make a weapon a different item, so it's ignored when comparing weapons (e.g. when
+/// looking them up in the save file tables)
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Wear(pub u32);
+
+impl PartialEq for Wear {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
 impl Weapon {
     pub fn is_melee(&self) -> bool {
         matches!(self.range, WeaponRange::Melee)
@@ -6536,6 +10672,26 @@ pub enum WeaponGrip {
     TwoHanded,
 }
 
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct MinRange {
+    // Attacking a target within this range is impossible
+    pub impossible: Option<Range>,
+    // Attacking a target within this range has disadvantage
+    pub disadvantage: Option<Range>,
+}
+
+impl MinRange {
+    pub const NONE: Self = Self {
+        impossible: None,
+        disadvantage: None,
+    };
+
+    pub const TOO_CLOSE: Self = Self {
+        impossible: None,
+        disadvantage: Some(Range::Melee),
+    };
+}
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum WeaponRange {
     Melee,
@@ -6703,6 +10859,15 @@ impl ArrowStack {
     pub fn new(arrow: Arrow, quantity: u32) -> Self {
         Self { arrow, quantity }
     }
+
+    /// Returns the combined stack, if both stacks hold the same kind of arrow
+    pub fn merge(self, other: ArrowStack) -> Option<ArrowStack> {
+        if self.arrow == other.arrow {
+            Some(ArrowStack::new(self.arrow, self.quantity + other.quantity))
+        } else {
+            None
+        }
+    }
 }
 
 #[derive(Copy, Clone, Debug, PartialEq)]
@@ -6711,6 +10876,7 @@ pub struct Consumable {
     pub health_gain: u32,
     pub mana_gain: u32,
     pub effect: Option<ApplyEffect>,
+    pub thrown: Option<ThrownEffect>,
     pub icon: EquipmentIconId,
     pub weight: u32,
 }
@@ -6722,12 +10888,20 @@ impl Consumable {
             health_gain: 0,
             mana_gain: 0,
             effect: None,
+            thrown: None,
             icon: EquipmentIconId::Undefined,
             weight: 0,
         }
     }
 }
 
+/// A consumable that is thrown at a position rather than used on oneself
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ThrownEffect {
+    pub range: Range,
+    pub area_effect: AreaEffect,
+}
+
 impl EquipmentEntry {
     pub fn name(&self) -> &'static str {
         match self {
@@ -6778,6 +10952,14 @@ impl EquipmentSlotRole {
         }
     }
 
+    pub fn hand_type(&self) -> Option<HandType> {
+        match self {
+            Self::MainHand => Some(HandType::MainHand),
+            Self::OffHand => Some(HandType::OffHand),
+            _ => None,
+        }
+    }
+
     pub fn is_equipped(&self) -> bool {
         use EquipmentSlotRole::*;
         match self {
@@ -6786,3 +10968,552 @@ impl EquipmentSlotRole {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::d20::FixedDice;
+    use crate::data::{
+        BERSERKER_AXE, BOW, CROSSBOW, FRENZY, LONGBOW, LUNGE_ATTACK, MIND_BLAST, SEARING_LIGHT,
+        SEARING_LIGHT_BURN, WAR_HAMMER,
+    };
+    use crate::pathfind::TerrainType;
+
+    fn make_character() -> Character {
+        let party = Rc::new(Party {
+            money: Cell::new(0),
+            stash: Default::default(),
+        });
+        Character::new(
+            CharacterKind::Player(party, PlayerId::Bob),
+            "Bob",
+            PortraitId::Bob,
+            SpriteId::Bob,
+            Attributes::new(3, 3, 3, 3),
+            (0, 0),
+        )
+    }
+
+    #[test]
+    fn spell_reflect_damages_the_attacker() {
+        let attacker = Rc::new(make_character());
+        let reactor = Rc::new(make_character());
+        attacker.set_id(0);
+        reactor.set_id(1);
+        reactor.position.set((1, 0));
+        let game = make_game(vec![attacker.clone(), reactor.clone()], FixedDice::new(20));
+        let health_before = attacker.health.current();
+
+        let (_lines, damage) = game.perform_spell_reflect(1, 50, 9);
+
+        assert_eq!(Some(5), damage);
+        assert_eq!(health_before - 5, attacker.health.current());
+    }
+
+    fn target_prone_advantage(weapon: Weapon, target_pos: Position) -> Option<i32> {
+        let attacker = make_character();
+        let target = make_character();
+        attacker.set_id(0);
+        target.set_id(1);
+        target.position.set(target_pos);
+        attacker.set_weapon(HandType::MainHand, weapon);
+        target.receive_condition(Condition::Prone, None, None);
+
+        attacker
+            .outgoing_attack_bonuses(HandType::MainHand, &[], &target)
+            .into_iter()
+            .find_map(|(label, contributor)| match (label, contributor) {
+                ("Target prone", RollBonusContributor::Advantage(n)) => Some(n),
+                _ => None,
+            })
+    }
+
+    #[test]
+    fn prone_target_is_easier_to_strike_in_melee() {
+        assert_eq!(Some(1), target_prone_advantage(WAR_HAMMER, (1, 0)));
+    }
+
+    #[test]
+    fn prone_target_is_harder_to_shoot() {
+        assert_eq!(Some(-1), target_prone_advantage(BOW, (8, 0)));
+    }
+
+    fn rest_after_a_rough_fight(rest_type: RestType) -> (Character, RestOutcome) {
+        let character = make_character();
+        character.health.lose(character.health.max() - 1);
+        character.mana.lose(character.mana.max());
+        let outcome = character.rest(rest_type);
+        (character, outcome)
+    }
+
+    #[test]
+    fn short_rest_restores_a_quarter_of_health_and_half_of_mana() {
+        let (character, outcome) = rest_after_a_rough_fight(RestType::Short);
+        let max_health = character.health.max();
+        let max_mana = character.mana.max();
+        assert_eq!(max_health.div_ceil(4), outcome.health_gained);
+        assert_eq!(max_mana.div_ceil(2), outcome.mana_gained);
+        assert_eq!(1 + max_health.div_ceil(4), character.health.current());
+    }
+
+    #[test]
+    fn long_rest_restores_more_than_a_short_rest() {
+        let (short, short_outcome) = rest_after_a_rough_fight(RestType::Short);
+        let (long, long_outcome) = rest_after_a_rough_fight(RestType::Long);
+        assert_eq!(long.health.max().div_ceil(2), long_outcome.health_gained);
+        assert_eq!(long.mana.max(), long.mana.current());
+        assert!(long_outcome.health_gained > short_outcome.health_gained);
+        assert!(long_outcome.mana_gained > short_outcome.mana_gained);
+        assert!(long.health.current() > short.health.current());
+    }
+
+    #[test]
+    fn passive_granted_by_weapon_only_applies_while_wielded() {
+        let character = make_character();
+        character.health.lose(character.health.max() / 2 + 1);
+        let has_blood_rage = || {
+            character
+                .known_passive_skills
+                .borrow()
+                .contains(&PassiveSkill::BloodRage)
+        };
+
+        character.set_equipment(
+            Some(EquipmentEntry::Weapon(BERSERKER_AXE)),
+            EquipmentSlotRole::MainHand,
+        );
+        assert!(has_blood_rage());
+        assert_eq!(
+            character.base_attack_modifier(HandType::MainHand) + 3,
+            character.attack_modifier(HandType::MainHand)
+        );
+
+        character.set_equipment(None, EquipmentSlotRole::MainHand);
+        assert!(!has_blood_rage());
+        assert_eq!(
+            character.base_attack_modifier(HandType::MainHand),
+            character.attack_modifier(HandType::MainHand)
+        );
+    }
+
+    #[test]
+    fn learned_passive_survives_unequipping_a_weapon_that_also_grants_it() {
+        let character = make_character();
+        character.set_equipment(
+            Some(EquipmentEntry::Weapon(BERSERKER_AXE)),
+            EquipmentSlotRole::MainHand,
+        );
+        character.learn_passive(PassiveSkill::BloodRage);
+        assert_eq!(
+            vec![PassiveSkill::BloodRage],
+            character.learned_passive_skills()
+        );
+
+        character.set_equipment(None, EquipmentSlotRole::MainHand);
+        assert_eq!(
+            vec![PassiveSkill::BloodRage],
+            *character.known_passive_skills.borrow()
+        );
+        assert_eq!(
+            vec![PassiveSkill::BloodRage],
+            character.learned_passive_skills()
+        );
+    }
+
+    #[test]
+    fn action_point_economy_can_be_overridden_per_character() {
+        let character = make_character();
+        assert_eq!(ACTION_POINTS_PER_TURN, character.end_of_turn_ap_gain());
+        assert_eq!(MAX_ACTION_POINTS, character.action_points.max());
+
+        character.set_action_point_economy(5, 7);
+        assert_eq!(5, character.end_of_turn_ap_gain());
+        assert_eq!(7, character.action_points.max());
+        assert!(character.action_points.current() <= 7);
+
+        character.conditions.borrow_mut().add(Condition::Adrenalin);
+        assert_eq!(6, character.end_of_turn_ap_gain());
+        character.conditions.borrow_mut().add(Condition::Slowed);
+        assert_eq!(6 - SLOWED_AP_PENALTY, character.end_of_turn_ap_gain());
+    }
+
+    fn make_game(characters: Vec<Rc<Character>>, dice: impl DiceRoller + 'static) -> CoreGame {
+        let init_state = GameInitState {
+            characters,
+            active_character_id: 0,
+            pathfind_grid: Rc::new(PathfindGrid::new((30, 30))),
+            background: Default::default(),
+            terrain_objects: Default::default(),
+            decorations: Default::default(),
+        };
+        CoreGame::new(
+            GameUserInterfaceConnection::uninitialized(),
+            &init_state,
+            Box::new(dice),
+        )
+    }
+
+    #[test]
+    fn fixed_dice_deal_an_exact_amount_of_damage() {
+        let attacker = Rc::new(make_character());
+        let defender = Rc::new(make_character());
+        attacker.set_id(0);
+        defender.set_id(1);
+        defender.position.set((2, 0));
+        attacker.set_weapon(HandType::MainHand, WAR_HAMMER);
+        let game = make_game(vec![attacker.clone(), defender.clone()], FixedDice::new(14));
+        let health_before = defender.health.current();
+
+        let event = CoreGame::perform_attack(
+            &attacker,
+            HandType::MainHand,
+            &[],
+            &defender,
+            None,
+            0,
+            ActionPerformanceMode::Real(&game),
+            None,
+        );
+
+        // 14 + 6 attack - 4 evasion is a crit: 8 damage, +50%
+        assert_eq!(HitType::Critical, event.outcome.hit_type);
+        assert_eq!(12, health_before - defender.health.current());
+    }
+
+    #[test]
+    fn risky_self_buff_backfires_on_a_low_roll() {
+        let caster = Rc::new(make_character());
+        caster.set_id(0);
+        let characters = Characters::new(vec![caster.clone()]);
+        let AbilityTarget::None {
+            self_effect:
+                Some(AbilityPositiveEffect {
+                    backfire: Some(backfire),
+                    ..
+                }),
+            ..
+        } = FRENZY.target
+        else {
+            unreachable!()
+        };
+
+        let applied_effects = |dice: FixedDice| {
+            let event = pollster::FutureExt::block_on(CoreGame::perform_ability(
+                &caster,
+                FRENZY,
+                &[],
+                &ActionTarget::None,
+                ActionPerformanceMode::SimulatedRoll(dice.roll_d20(), &characters),
+            ))
+            .remove(0);
+            let Some((_, AbilityTargetOutcome::AffectedAlly { applied_effects })) =
+                event.target_outcome
+            else {
+                panic!("Unexpected outcome: {:?}", event.target_outcome)
+            };
+            applied_effects
+        };
+
+        assert_eq!(vec![backfire.effect], applied_effects(FixedDice::new(1)));
+        assert!(!applied_effects(FixedDice::new(20)).contains(&backfire.effect));
+    }
+
+    #[test]
+    fn dualcast_spell_resolves_once_per_cast() {
+        let caster = Rc::new(make_character());
+        let target = Rc::new(make_character());
+        caster.set_id(0);
+        target.set_id(1);
+        target.position.set((5, 0));
+        let characters = Characters::new(vec![caster.clone(), target.clone()]);
+        let dualcast = MIND_BLAST.possible_enhancements[0].unwrap();
+
+        let events = pollster::FutureExt::block_on(CoreGame::perform_ability(
+            &caster,
+            MIND_BLAST,
+            &[dualcast],
+            &ActionTarget::Character(1, None),
+            ActionPerformanceMode::SimulatedRoll(10, &characters),
+        ));
+
+        assert_eq!(
+            vec![0, 1],
+            events
+                .iter()
+                .map(|event| event.cast_index)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn dry_run_attack_leaves_the_characters_unchanged() {
+        let attacker = Rc::new(make_character());
+        let defender = Rc::new(make_character());
+        attacker.set_id(0);
+        defender.set_id(1);
+        defender.position.set((2, 0));
+        attacker.set_weapon(HandType::MainHand, WAR_HAMMER);
+        defender.receive_condition(Condition::Burning, Some(2), None);
+        let characters = Characters::new(vec![attacker.clone(), defender.clone()]);
+
+        let snapshot = |ch: &Character| {
+            (
+                ch.health.current(),
+                ch.action_points.current(),
+                format!("{:?}", ch.conditions.borrow()),
+            )
+        };
+        let attacker_before = snapshot(&attacker);
+        let defender_before = snapshot(&defender);
+
+        let event = CoreGame::dry_run_attack(
+            &characters,
+            &attacker,
+            HandType::MainHand,
+            &[],
+            &defender,
+            None,
+        );
+
+        assert!(event.detail_lines[0].starts_with("Rolled: 10 "));
+        assert_eq!(attacker_before, snapshot(&attacker));
+        assert_eq!(defender_before, snapshot(&defender));
+    }
+
+    #[test]
+    fn minimum_range_can_make_a_shot_impossible_or_disadvantaged() {
+        let archer = make_character();
+        archer.set_weapon(HandType::MainHand, LONGBOW);
+        assert_eq!(
+            ActionReach::No,
+            archer
+                .reaches_with_attack(HandType::MainHand, (2, 0), std::iter::empty())
+                .1
+        );
+        assert_eq!(
+            ActionReach::YesButDisadvantage("Too close"),
+            archer
+                .reaches_with_attack(HandType::MainHand, (5, 0), std::iter::empty())
+                .1
+        );
+        assert_eq!(
+            ActionReach::Yes,
+            archer
+                .reaches_with_attack(HandType::MainHand, (10, 0), std::iter::empty())
+                .1
+        );
+
+        archer.set_weapon(HandType::MainHand, CROSSBOW);
+        assert_eq!(
+            ActionReach::Yes,
+            archer
+                .reaches_with_attack(HandType::MainHand, (2, 0), std::iter::empty())
+                .1
+        );
+    }
+
+    #[test]
+    fn attack_prediction_uses_the_weapons_minimum_range() {
+        let archer = Rc::new(make_character());
+        let target = Rc::new(make_character());
+        archer.set_id(0);
+        target.set_id(1);
+        target.position.set((5, 0));
+        let characters = Characters::new(vec![archer.clone(), target.clone()]);
+
+        let too_close = |weapon: Weapon| {
+            archer.set_weapon(HandType::MainHand, weapon);
+            predict_attack(
+                &characters,
+                &archer,
+                HandType::MainHand,
+                &[],
+                &target,
+                None,
+                0,
+            )
+            .details
+            .contains(&("Too close", Goodness::Bad))
+        };
+        assert!(too_close(LONGBOW));
+        assert!(!too_close(CROSSBOW));
+    }
+
+    fn place(grid: &PathfindGrid, character: &Character, id: CharacterId, pos: Position) {
+        character.set_id(id);
+        character.position.set(pos);
+        grid.set_occupied(pos, Some(Occupation::Character(id)));
+    }
+
+    #[test]
+    fn engaging_requires_a_reachable_cell_next_to_the_target() {
+        let grid = PathfindGrid::new((30, 30));
+        let caster = make_character();
+        let target = make_character();
+        place(&grid, &caster, 0, (4, 15));
+        place(&grid, &target, 1, (15, 15));
+
+        let path = can_move_into_melee(&grid, &caster, &target, LUNGE_ATTACK, &[]).unwrap();
+        assert!(within_meele(*path.last().unwrap(), target.pos()));
+
+        // Walled in on all sides
+        for x in [12, 15, 18] {
+            for y in [12, 15, 18] {
+                if (x, y) != target.pos() {
+                    grid.set_occupied((x, y), Some(Occupation::Terrain(TerrainType::Tall)));
+                }
+            }
+        }
+        assert_eq!(
+            None,
+            can_move_into_melee(&grid, &caster, &target, LUNGE_ATTACK, &[])
+        );
+    }
+
+    #[test]
+    fn prone_characters_learn_to_stand_up_and_cant_engage() {
+        let grid = PathfindGrid::new((30, 30));
+        let caster = make_character();
+        let target = make_character();
+        place(&grid, &caster, 0, (4, 15));
+        place(&grid, &target, 1, (15, 15));
+        assert!(!caster
+            .known_actions()
+            .contains(&BaseAction::UseAbility(STAND_UP)));
+
+        caster.receive_condition(Condition::Prone, None, None);
+        assert!(caster.can_use_ability(STAND_UP));
+        assert_eq!(
+            None,
+            can_move_into_melee(&grid, &caster, &target, LUNGE_ATTACK, &[])
+        );
+    }
+
+    #[test]
+    fn pulled_ally_ends_up_closer_to_the_caster_and_out_of_melee() {
+        let grid = PathfindGrid::new((30, 30));
+        let caster = make_character();
+        let ally = make_character();
+        let enemy = make_character();
+        place(&grid, &caster, 0, (4, 15));
+        place(&grid, &ally, 1, (15, 15));
+        place(&grid, &enemy, 2, (18, 15));
+        assert!(within_meele(ally.pos(), enemy.pos()));
+
+        let path = find_pull_path(&grid, &ally, caster.pos(), 6).unwrap();
+        let destination = *path.last().unwrap();
+        assert!(
+            sq_distance_between(destination, caster.pos())
+                < sq_distance_between(ally.pos(), caster.pos())
+        );
+        assert!(!within_meele(destination, enemy.pos()));
+    }
+
+    #[test]
+    fn engaging_is_limited_by_the_ability_range() {
+        let grid = PathfindGrid::new((40, 30));
+        let caster = make_character();
+        let target = make_character();
+        place(&grid, &caster, 0, (2, 15));
+        place(&grid, &target, 1, (30, 15));
+
+        assert_eq!(
+            None,
+            can_move_into_melee(&grid, &caster, &target, LUNGE_ATTACK, &[])
+        );
+    }
+
+    fn burning_stacks(character: &Character) -> Option<u32> {
+        character
+            .conditions
+            .borrow()
+            .get(&Condition::Burning)
+            .and_then(|state| state.stacks)
+    }
+
+    fn burning_from_enhanced_searing_light(result_above_defense: i32) -> Option<u32> {
+        let caster = Rc::new(make_character());
+        let target = Rc::new(make_character());
+        caster.set_id(0);
+        target.set_id(1);
+        target.position.set((3, 0));
+        let game = make_game(vec![caster.clone(), target.clone()], FixedDice::new(10));
+        let AbilityTarget::Enemy {
+            effect: AbilityNegativeEffect::Spell(spell_effect),
+            ..
+        } = SEARING_LIGHT.target
+        else {
+            unreachable!()
+        };
+        let ability_roll = AbilityRoll::RolledWithSpellModifier {
+            dice_roll: 10,
+            result: target.defense(DefenseType::Toughness) as i32 + result_above_defense,
+            line: String::new(),
+        };
+
+        CoreGame::perform_spell_enemy_effect(
+            &caster,
+            SEARING_LIGHT.name,
+            &ability_roll,
+            &[SEARING_LIGHT_BURN],
+            spell_effect,
+            &target,
+            &mut vec![],
+            None,
+            ActionPerformanceMode::Real(&game),
+        );
+        burning_stacks(&target)
+    }
+
+    #[test]
+    fn enhancement_on_hit_effects_scale_with_the_hit_type() {
+        // The enhancement applies 2 stacks of Burning on a regular hit
+        assert_eq!(Some(1), burning_from_enhanced_searing_light(0));
+        assert_eq!(Some(2), burning_from_enhanced_searing_light(10));
+        assert_eq!(Some(3), burning_from_enhanced_searing_light(20));
+    }
+
+    #[test]
+    fn flat_condition_resistance_reduces_burning() {
+        let character = make_character();
+        character
+            .condition_resistances
+            .borrow_mut()
+            .insert(Condition::Burning, 2);
+
+        character.receive_condition(Condition::Burning, Some(5), None);
+        assert_eq!(Some(3), burning_stacks(&character));
+
+        // Fully resisted
+        character.receive_condition(Condition::Burning, Some(2), None);
+        assert_eq!(Some(3), burning_stacks(&character));
+    }
+
+    #[test]
+    fn percentage_condition_resistance_reduces_burning() {
+        let character = make_character();
+        character
+            .condition_resistance_percentages
+            .borrow_mut()
+            .insert(Condition::Burning, 50);
+
+        character.receive_condition(Condition::Burning, Some(4), None);
+        assert_eq!(Some(2), burning_stacks(&character));
+    }
+
+    #[test]
+    fn percentage_condition_resistance_applies_before_flat() {
+        let character = make_character();
+        character
+            .condition_resistance_percentages
+            .borrow_mut()
+            .insert(Condition::Burning, 50);
+        character
+            .condition_resistances
+            .borrow_mut()
+            .insert(Condition::Burning, 1);
+
+        character.receive_condition(Condition::Burning, Some(6), None);
+        assert_eq!(Some(2), burning_stacks(&character));
+    }
+}
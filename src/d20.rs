@@ -1,4 +1,6 @@
-use rand::{self, Rng};
+use std::cell::RefCell;
+
+use rand::{self, rngs::StdRng, Rng, SeedableRng};
 
 pub fn probability_of_d20_reaching(mut target: u32, bonus: DiceRollBonus) -> f32 {
     target = (target as i32 - bonus.flat_amount).min(21).max(1) as u32;
@@ -13,16 +15,100 @@ pub fn probability_of_d20_reaching(mut target: u32, bonus: DiceRollBonus) -> f32
     }
 }
 
+/// The probability of each final result 0..=20, where the die that's kept (highest with advantage,
+/// lowest with disadvantage) has the flat bonus added. Results beyond either end are clamped into
+/// the first or last bucket.
+pub fn outcome_distribution(bonus: DiceRollBonus) -> [f32; 21] {
+    let num_dice = bonus.advantage.abs() + 1;
+    // The probability that the kept die shows at most this face
+    let p_at_most = |face: i32| -> f32 {
+        let p_single = face as f32 / 20f32;
+        if bonus.advantage >= 0 {
+            p_single.powi(num_dice)
+        } else {
+            1f32 - (1f32 - p_single).powi(num_dice)
+        }
+    };
+
+    let mut distribution = [0f32; 21];
+    for face in 1..=20 {
+        let p = p_at_most(face) - p_at_most(face - 1);
+        let result = (face + bonus.flat_amount).clamp(0, 20);
+        distribution[result as usize] += p;
+    }
+    distribution
+}
+
+/// A source of d20 rolls. The game owns one, so that a battle can be replayed from a seed.
+pub trait DiceRoller {
+    fn roll_d20(&self) -> u32;
+}
+
+/// Backed by the thread-local RNG
+#[derive(Default)]
+pub struct RandomDice;
+
+impl DiceRoller for RandomDice {
+    fn roll_d20(&self) -> u32 {
+        roll_d20()
+    }
+}
+
+/// Produces the same sequence of rolls for a given seed
+pub struct SeededDice {
+    rng: RefCell<StdRng>,
+}
+
+impl SeededDice {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: RefCell::new(StdRng::seed_from_u64(seed)),
+        }
+    }
+}
+
+impl DiceRoller for SeededDice {
+    fn roll_d20(&self) -> u32 {
+        self.rng.borrow_mut().random_range(1..=20)
+    }
+}
+
+/// Every roll lands on the same face (so advantage makes no difference). Useful for deterministic
+/// tests.
+pub struct FixedDice(u32);
+
+impl FixedDice {
+    pub fn new(face: u32) -> Self {
+        assert!((1..=20).contains(&face), "Not a d20 face: {}", face);
+        Self(face)
+    }
+
+    /// Every roll lands on the expected value of a d20 (rounded up)
+    pub fn expected() -> Self {
+        Self(11)
+    }
+}
+
+impl DiceRoller for FixedDice {
+    fn roll_d20(&self) -> u32 {
+        self.0
+    }
+}
+
 pub fn roll_d20_with_advantage(advantage_level: i32) -> u32 {
+    roll_d20_with_advantage_using(&RandomDice, advantage_level)
+}
+
+pub fn roll_d20_with_advantage_using(dice: &dyn DiceRoller, advantage_level: i32) -> u32 {
     // 0 => roll once
     // 1 => roll twice, take highest (i.e. 1x advantage)
     // -1 => roll twice, take lowest (i.e. 1x disadvantage)
     // etc
 
-    let mut res = roll_d20();
+    let mut res = dice.roll_d20();
     let additional_rolls = advantage_level.abs();
     for _ in 0..additional_rolls {
-        let new = roll_d20();
+        let new = dice.roll_d20();
         res = if advantage_level < 0 {
             res.min(new)
         } else {
@@ -59,3 +145,78 @@ impl DiceRollBonus {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(expected: f32, actual: f32) {
+        assert!(
+            (expected - actual).abs() < 0.0001,
+            "expected {}, got {}",
+            expected,
+            actual
+        );
+    }
+
+    #[test]
+    fn same_seed_rolls_the_same_sequence() {
+        let first = SeededDice::new(1234);
+        let second = SeededDice::new(1234);
+        for advantage in [0, 1, -1, 2, 0, -2, 0, 0] {
+            assert_eq!(
+                roll_d20_with_advantage_using(&first, advantage),
+                roll_d20_with_advantage_using(&second, advantage)
+            );
+        }
+    }
+
+    #[test]
+    fn outcome_distribution_sums_to_one() {
+        for advantage in -3..=3 {
+            for flat_amount in [-25, -5, 0, 5, 25] {
+                let distribution = outcome_distribution(DiceRollBonus {
+                    advantage,
+                    flat_amount,
+                });
+                assert_close(1.0, distribution.iter().sum());
+            }
+        }
+    }
+
+    #[test]
+    fn outcome_distribution_matches_known_advantage_odds() {
+        let flat = outcome_distribution(DiceRollBonus::none());
+        assert_close(0.05, flat[20]);
+        assert_close(0.05, flat[1]);
+
+        // Rolling two dice, a natural 20 shows up on either: 1 - (19/20)^2
+        let advantage = outcome_distribution(DiceRollBonus::from_advantage(1));
+        assert_close(0.0975, advantage[20]);
+        assert_close(0.0025, advantage[1]);
+        // Reaching 11 or higher fails only if both dice are 10 or lower: 1 - (10/20)^2
+        assert_close(0.75, advantage[11..].iter().sum());
+
+        let disadvantage = outcome_distribution(DiceRollBonus::from_advantage(-1));
+        assert_close(0.0025, disadvantage[20]);
+        assert_close(0.0975, disadvantage[1]);
+        assert_close(0.25, disadvantage[11..].iter().sum());
+    }
+
+    #[test]
+    fn outcome_distribution_agrees_with_probability_of_reaching() {
+        for advantage in -2..=2 {
+            let bonus = || DiceRollBonus {
+                advantage,
+                flat_amount: 3,
+            };
+            let distribution = outcome_distribution(bonus());
+            for target in 4..=20 {
+                assert_close(
+                    probability_of_d20_reaching(target, bonus()),
+                    distribution[target as usize..].iter().sum(),
+                );
+            }
+        }
+    }
+}
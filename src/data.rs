@@ -10,19 +10,22 @@ use crate::{
         AbilityRollType, AbilityTarget, ApplyCondition, ApplyEffect, AreaEffect, AreaShape,
         AreaTargetAcquisition, ArmorPiece, Arrow, AttackAttribute, AttackEnhancement,
         AttackEnhancementEffect, AttackEnhancementOnHitEffect, AttackHitEffect, AttackType,
-        Condition, Consumable, DefenseType, EquipEffect, EquipmentRequirement, Fraction, HandType,
-        OnAttackedReaction, OnAttackedReactionEffect, OnAttackedReactionId,
+        Backfire, Backlash, Condition, Consumable, DamageType, DefenseType, DispelEffect,
+        EquipEffect, EquipmentRequirement, Fraction, GroundEffect, HandType, MinRange,
+        MovementEnhancement, OnAttackedReaction, OnAttackedReactionEffect, OnAttackedReactionId,
         OnAttackedReactionTarget, OnHitReaction, OnHitReactionEffect, Range, Shield,
-        SpellEnhancementEffect, SpellNegativeEffect, Weapon, WeaponGrip, WeaponRange, WeaponType,
+        SpellEnhancementEffect, SpellGrazeBehavior, SpellNegativeEffect, SummonEffect,
+        ThrownEffect, Weapon, WeaponGrip, WeaponRange, WeaponType, Wear,
     },
     grid::ParticleShape,
     sounds::SoundId,
-    textures::{EquipmentIconId, IconId, SpriteId},
+    textures::{EquipmentIconId, IconId, PortraitId, SpriteId},
 };
 
 pub const SHIRT: ArmorPiece = ArmorPiece {
     name: "Shirt",
     protection: 1,
+    resistances: &[],
     limit_evasion_from_agi: None,
     icon: EquipmentIconId::Shirt,
     weight: 1,
@@ -32,6 +35,7 @@ pub const SHIRT: ArmorPiece = ArmorPiece {
 pub const ROBE: ArmorPiece = ArmorPiece {
     name: "Robe",
     protection: 1,
+    resistances: &[],
     limit_evasion_from_agi: None,
     icon: EquipmentIconId::Robe,
     weight: 1,
@@ -44,6 +48,7 @@ pub const ROBE: ArmorPiece = ArmorPiece {
 pub const LEATHER_ARMOR: ArmorPiece = ArmorPiece {
     name: "Leather armor",
     protection: 2,
+    resistances: &[],
     limit_evasion_from_agi: None,
     icon: EquipmentIconId::LeatherArmor,
     weight: 2,
@@ -53,6 +58,7 @@ pub const LEATHER_ARMOR: ArmorPiece = ArmorPiece {
 pub const CHAIN_MAIL: ArmorPiece = ArmorPiece {
     name: "Chain mail",
     protection: 3,
+    resistances: &[],
     limit_evasion_from_agi: Some(4),
     icon: EquipmentIconId::ChainMail,
     weight: 3,
@@ -62,6 +68,7 @@ pub const CHAIN_MAIL: ArmorPiece = ArmorPiece {
 pub const GOOD_CHAIN_MAIL: ArmorPiece = ArmorPiece {
     name: "Good chain mail",
     protection: 4,
+    resistances: &[],
     limit_evasion_from_agi: None,
     icon: EquipmentIconId::ChainMail,
     weight: 3,
@@ -71,6 +78,7 @@ pub const GOOD_CHAIN_MAIL: ArmorPiece = ArmorPiece {
 pub const LIGHT_CHAIN_MAIL: ArmorPiece = ArmorPiece {
     name: "Light chain mail",
     protection: 3,
+    resistances: &[],
     limit_evasion_from_agi: Some(4),
     icon: EquipmentIconId::ChainMail,
     weight: 2,
@@ -91,30 +99,54 @@ pub const STABBING: AttackEnhancement = AttackEnhancement {
 pub const BAD_DAGGER: Weapon = Weapon {
     name: "Bad dagger",
     range: WeaponRange::Melee,
+    threat_range: WeaponRange::Melee,
+    min_range: MinRange::NONE,
+    effective_range: None,
     action_point_cost: 4,
     damage: 3,
+    damage_type: DamageType::Piercing,
     grip: WeaponGrip::Light,
     attack_attribute: AttackAttribute::Finesse,
     attack_enhancement: None,
     on_attacked_reaction: None,
     on_true_hit: None,
+    on_crit: None,
+    crit_multiplier_percent: 50,
+    crit_threshold: 16,
+    lifesteal_percent: 0,
+    grants_passive: None,
+    thrown: false,
     sprite: Some(SpriteId::Dagger),
     icon: EquipmentIconId::Dagger,
+    durability: None,
+    wear: Wear(0),
     weight: 1,
 };
 
 pub const DAGGER: Weapon = Weapon {
     name: "Dagger",
     range: WeaponRange::Melee,
+    threat_range: WeaponRange::Melee,
+    min_range: MinRange::NONE,
+    effective_range: None,
     action_point_cost: 3,
     damage: 5,
+    damage_type: DamageType::Piercing,
     grip: WeaponGrip::Light,
     attack_attribute: AttackAttribute::Finesse,
     attack_enhancement: None, //Some(STABBING),
     on_attacked_reaction: None,
     on_true_hit: None,
+    on_crit: None,
+    crit_multiplier_percent: 50,
+    crit_threshold: 16,
+    lifesteal_percent: 0,
+    grants_passive: None,
+    thrown: false,
     sprite: Some(SpriteId::Dagger),
     icon: EquipmentIconId::Dagger,
+    durability: Some(30),
+    wear: Wear(0),
     weight: 1,
 };
 
@@ -138,6 +170,19 @@ pub const SLASHING: AttackEnhancement = AttackEnhancement {
     ..AttackEnhancement::default()
 };
 
+pub const DISARM: AttackEnhancement = AttackEnhancement {
+    name: "Disarm",
+    description: "Knock the target's weapon out of their hand",
+    icon: IconId::MeleeAttack,
+    stamina_cost: 2,
+    weapon_requirement: Some(WeaponType::Melee),
+    effect: AttackEnhancementEffect {
+        on_damage_effect: Some(AttackEnhancementOnHitEffect::Disarm(DefenseType::Toughness)),
+        ..AttackEnhancementEffect::default()
+    },
+    ..AttackEnhancement::default()
+};
+
 pub const ENEMY_SLASHING: AttackEnhancement = AttackEnhancement {
     name: "Slashing",
     icon: IconId::Slashing,
@@ -160,63 +205,119 @@ pub const ENEMY_SLASHING: AttackEnhancement = AttackEnhancement {
 pub const BAD_SWORD: Weapon = Weapon {
     name: "Bad Sword",
     range: WeaponRange::Melee,
+    threat_range: WeaponRange::Melee,
+    min_range: MinRange::NONE,
+    effective_range: None,
     action_point_cost: 4,
     damage: 4,
+    damage_type: DamageType::Slashing,
     grip: WeaponGrip::Versatile,
     attack_attribute: AttackAttribute::Finesse,
     attack_enhancement: Some(SLASHING),
     on_attacked_reaction: Some(PARRY),
     on_true_hit: None,
+    on_crit: None,
+    crit_multiplier_percent: 50,
+    crit_threshold: 16,
+    lifesteal_percent: 0,
+    grants_passive: None,
+    thrown: false,
     sprite: Some(SpriteId::Sword),
     icon: EquipmentIconId::Sword,
+    durability: None,
+    wear: Wear(0),
     weight: 2,
 };
 
 pub const ENSLAVED_SWORD: Weapon = Weapon {
     name: "Enslaved Sword",
     range: WeaponRange::Melee,
+    threat_range: WeaponRange::Melee,
+    min_range: MinRange::NONE,
+    effective_range: None,
     action_point_cost: 4,
     damage: 6,
+    damage_type: DamageType::Slashing,
     grip: WeaponGrip::Versatile,
     attack_attribute: AttackAttribute::Finesse,
     attack_enhancement: Some(SLASHING),
     on_attacked_reaction: Some(PARRY),
     on_true_hit: None,
+    on_crit: None,
+    crit_multiplier_percent: 50,
+    crit_threshold: 16,
+    lifesteal_percent: 0,
+    grants_passive: None,
+    thrown: false,
     sprite: Some(SpriteId::Sword),
     icon: EquipmentIconId::Sword,
+    durability: None,
+    wear: Wear(0),
     weight: 2,
 };
 
 pub const ENSLAVED_RAPIER: Weapon = Weapon {
     name: "Enslaved Rapier",
     range: WeaponRange::Melee,
+    threat_range: WeaponRange::Melee,
+    min_range: MinRange::NONE,
+    effective_range: None,
     action_point_cost: 4,
     damage: 7,
+    damage_type: DamageType::Piercing,
     grip: WeaponGrip::Versatile,
     attack_attribute: AttackAttribute::Finesse,
     attack_enhancement: Some(SLASHING),
     on_attacked_reaction: Some(PARRY),
     on_true_hit: None,
+    on_crit: None,
+    crit_multiplier_percent: 50,
+    crit_threshold: 16,
+    lifesteal_percent: 0,
+    grants_passive: None,
+    thrown: false,
     sprite: Some(SpriteId::Rapier),
     icon: EquipmentIconId::Rapier,
+    durability: None,
+    wear: Wear(0),
     weight: 2,
 };
 
 pub const SWORD: Weapon = Weapon {
     name: "Sword",
     range: WeaponRange::Melee,
+    threat_range: WeaponRange::Melee,
+    min_range: MinRange::NONE,
+    effective_range: None,
     action_point_cost: 3,
     damage: 6,
+    damage_type: DamageType::Slashing,
     grip: WeaponGrip::Versatile,
     attack_attribute: AttackAttribute::Finesse,
     attack_enhancement: Some(SLASHING),
     on_attacked_reaction: Some(PARRY),
     on_true_hit: None,
+    on_crit: None,
+    crit_multiplier_percent: 50,
+    crit_threshold: 16,
+    lifesteal_percent: 0,
+    grants_passive: None,
+    thrown: false,
     sprite: Some(SpriteId::Sword),
     icon: EquipmentIconId::Sword,
+    durability: Some(25),
+    wear: Wear(0),
     weight: 2,
 };
 
+pub const VAMPIRIC_SWORD: Weapon = Weapon {
+    name: "Vampiric sword",
+    lifesteal_percent: 30,
+    durability: Some(20),
+    wear: Wear(0),
+    ..SWORD
+};
+
 const FEINT: AttackEnhancement = AttackEnhancement {
     name: "Feint",
     description: "Reduce the target's defense by 6 against the next attack",
@@ -236,30 +337,54 @@ const FEINT: AttackEnhancement = AttackEnhancement {
 pub const BAD_RAPIER: Weapon = Weapon {
     name: "Bad rapier",
     range: WeaponRange::Melee,
+    threat_range: WeaponRange::Melee,
+    min_range: MinRange::NONE,
+    effective_range: None,
     action_point_cost: 3,
     damage: 5,
+    damage_type: DamageType::Piercing,
     grip: WeaponGrip::MainHand,
     attack_attribute: AttackAttribute::Finesse,
     attack_enhancement: Some(FEINT),
     on_attacked_reaction: Some(PARRY),
     on_true_hit: None,
+    on_crit: None,
+    crit_multiplier_percent: 50,
+    crit_threshold: 16,
+    lifesteal_percent: 0,
+    grants_passive: None,
+    thrown: false,
     sprite: Some(SpriteId::Rapier),
     icon: EquipmentIconId::Rapier,
+    durability: None,
+    wear: Wear(0),
     weight: 2,
 };
 
 pub const RAPIER: Weapon = Weapon {
     name: "Rapier",
     range: WeaponRange::Melee,
+    threat_range: WeaponRange::Melee,
+    min_range: MinRange::NONE,
+    effective_range: None,
     action_point_cost: 3,
     damage: 7,
+    damage_type: DamageType::Piercing,
     grip: WeaponGrip::MainHand,
     attack_attribute: AttackAttribute::Finesse,
     attack_enhancement: Some(FEINT),
     on_attacked_reaction: Some(PARRY),
     on_true_hit: None,
+    on_crit: None,
+    crit_multiplier_percent: 50,
+    crit_threshold: 16,
+    lifesteal_percent: 0,
+    grants_passive: None,
+    thrown: false,
     sprite: Some(SpriteId::Rapier),
     icon: EquipmentIconId::Rapier,
+    durability: Some(20),
+    wear: Wear(0),
     weight: 2,
 };
 
@@ -279,40 +404,74 @@ const ALL_IN: AttackEnhancement = AttackEnhancement {
 pub const BAD_WAR_HAMMER: Weapon = Weapon {
     name: "Bad war hammer",
     range: WeaponRange::Melee,
+    threat_range: WeaponRange::Melee,
+    min_range: MinRange::NONE,
+    effective_range: None,
     action_point_cost: 4,
     damage: 6,
+    damage_type: DamageType::Bludgeoning,
     grip: WeaponGrip::TwoHanded,
     attack_attribute: AttackAttribute::Strength,
     attack_enhancement: Some(ALL_IN),
     on_attacked_reaction: Some(PARRY),
     on_true_hit: None,
+    on_crit: None,
+    crit_multiplier_percent: 50,
+    crit_threshold: 16,
+    lifesteal_percent: 0,
+    grants_passive: None,
+    thrown: false,
     sprite: Some(SpriteId::Warhammer),
     icon: EquipmentIconId::Warhammer,
+    durability: None,
+    wear: Wear(0),
     weight: 5,
 };
 
 pub const WAR_HAMMER: Weapon = Weapon {
     name: "War hammer",
     range: WeaponRange::Melee,
+    threat_range: WeaponRange::Melee,
+    min_range: MinRange::NONE,
+    effective_range: None,
     action_point_cost: 3,
     // Note: sword held in 2h deals the same as this
     damage: 8,
+    damage_type: DamageType::Bludgeoning,
     grip: WeaponGrip::TwoHanded,
     attack_attribute: AttackAttribute::Strength,
     attack_enhancement: Some(ALL_IN),
     on_attacked_reaction: Some(PARRY),
     on_true_hit: None,
+    on_crit: Some(AttackHitEffect::Apply(ApplyEffect::Condition(
+        ApplyCondition {
+            condition: Condition::Dazed,
+            stacks: None,
+            duration_rounds: Some(1),
+        },
+    ))),
+    crit_multiplier_percent: 50,
+    crit_threshold: 16,
+    lifesteal_percent: 0,
+    grants_passive: None,
+    thrown: false,
     sprite: Some(SpriteId::Warhammer),
     icon: EquipmentIconId::Warhammer,
+    durability: Some(30),
+    wear: Wear(0),
     weight: 5,
 };
 
 pub const BONE_CRUSHER: Weapon = Weapon {
     name: "Bone crusher",
     range: WeaponRange::Melee,
+    threat_range: WeaponRange::Melee,
+    min_range: MinRange::NONE,
+    effective_range: None,
     action_point_cost: 3,
     // Note: sword held in 2h deals the same as this
     damage: 8,
+    damage_type: DamageType::Bludgeoning,
     grip: WeaponGrip::TwoHanded,
     attack_attribute: AttackAttribute::Strength,
     attack_enhancement: Some(ALL_IN),
@@ -324,68 +483,265 @@ pub const BONE_CRUSHER: Weapon = Weapon {
             duration_rounds: Some(1),
         },
     ))),
+    on_crit: None,
+    crit_multiplier_percent: 50,
+    crit_threshold: 16,
+    lifesteal_percent: 0,
+    grants_passive: None,
+    thrown: false,
     sprite: Some(SpriteId::Warhammer),
     icon: EquipmentIconId::Warhammer,
+    durability: Some(25),
+    wear: Wear(0),
     weight: 7,
 };
 
+pub const BERSERKER_AXE: Weapon = Weapon {
+    name: "Berserker axe",
+    range: WeaponRange::Melee,
+    threat_range: WeaponRange::Melee,
+    min_range: MinRange::NONE,
+    effective_range: None,
+    action_point_cost: 3,
+    damage: 7,
+    damage_type: DamageType::Slashing,
+    grip: WeaponGrip::TwoHanded,
+    attack_attribute: AttackAttribute::Strength,
+    attack_enhancement: Some(ALL_IN),
+    on_attacked_reaction: None,
+    on_true_hit: None,
+    on_crit: None,
+    crit_multiplier_percent: 50,
+    crit_threshold: 16,
+    lifesteal_percent: 0,
+    grants_passive: Some(PassiveSkill::BloodRage),
+    thrown: false,
+    sprite: Some(SpriteId::Warhammer),
+    icon: EquipmentIconId::Warhammer,
+    durability: Some(20),
+    wear: Wear(0),
+    weight: 6,
+};
+
+pub const THROWING_AXE: Weapon = Weapon {
+    name: "Throwing axe",
+    range: WeaponRange::Ranged(9.5),
+    threat_range: WeaponRange::Melee,
+    min_range: MinRange::TOO_CLOSE,
+    effective_range: None,
+    action_point_cost: 2,
+    damage: 5,
+    damage_type: DamageType::Slashing,
+    grip: WeaponGrip::MainHand,
+    attack_attribute: AttackAttribute::Strength,
+    attack_enhancement: None,
+    on_attacked_reaction: None,
+    on_true_hit: None,
+    on_crit: None,
+    crit_multiplier_percent: 50,
+    crit_threshold: 16,
+    lifesteal_percent: 0,
+    grants_passive: None,
+    thrown: true,
+    sprite: Some(SpriteId::Dagger),
+    icon: EquipmentIconId::Dagger,
+    durability: None,
+    wear: Wear(0),
+    weight: 2,
+};
+
+pub const SPEAR: Weapon = Weapon {
+    name: "Spear",
+    range: WeaponRange::Melee,
+    // Reaches one step further than other melee weapons when punishing enemies that move away
+    threat_range: WeaponRange::Ranged(4.5),
+    min_range: MinRange::NONE,
+    effective_range: None,
+    action_point_cost: 2,
+    damage: 5,
+    damage_type: DamageType::Piercing,
+    grip: WeaponGrip::TwoHanded,
+    attack_attribute: AttackAttribute::Strength,
+    attack_enhancement: None,
+    on_attacked_reaction: Some(PARRY),
+    on_true_hit: None,
+    on_crit: None,
+    crit_multiplier_percent: 50,
+    crit_threshold: 16,
+    lifesteal_percent: 0,
+    grants_passive: None,
+    thrown: false,
+    sprite: Some(SpriteId::Sword),
+    icon: EquipmentIconId::Sword,
+    durability: Some(25),
+    wear: Wear(0),
+    weight: 4,
+};
+
 pub const BAD_BOW: Weapon = Weapon {
     name: "Bad bow",
     range: WeaponRange::Ranged(15.5),
+    threat_range: WeaponRange::Melee,
+    min_range: MinRange::TOO_CLOSE,
+    effective_range: None,
     action_point_cost: 4,
     damage: 4,
+    damage_type: DamageType::Piercing,
     grip: WeaponGrip::TwoHanded,
     attack_attribute: AttackAttribute::Agility,
     attack_enhancement: Some(CAREFUL_AIM),
     on_attacked_reaction: None,
     on_true_hit: None,
+    on_crit: None,
+    crit_multiplier_percent: 50,
+    crit_threshold: 16,
+    lifesteal_percent: 0,
+    grants_passive: None,
+    thrown: false,
     sprite: Some(SpriteId::Bow),
     icon: EquipmentIconId::Bow,
+    durability: None,
+    wear: Wear(0),
     weight: 2,
 };
 
 pub const CHEAT_BOW: Weapon = Weapon {
     name: "Bow",
     range: WeaponRange::Ranged(50.0),
+    threat_range: WeaponRange::Melee,
+    min_range: MinRange::TOO_CLOSE,
+    effective_range: None,
     action_point_cost: 0,
     damage: 99,
+    damage_type: DamageType::Piercing,
     grip: WeaponGrip::TwoHanded,
     attack_attribute: AttackAttribute::Agility,
     attack_enhancement: Some(CAREFUL_AIM),
     on_attacked_reaction: None,
     on_true_hit: None,
+    on_crit: None,
+    crit_multiplier_percent: 50,
+    crit_threshold: 16,
+    lifesteal_percent: 0,
+    grants_passive: None,
+    thrown: false,
     sprite: Some(SpriteId::Bow),
     icon: EquipmentIconId::Bow,
+    durability: None,
+    wear: Wear(0),
     weight: 2,
 };
 
 pub const BOW: Weapon = Weapon {
     name: "Bow",
     range: WeaponRange::Ranged(15.5),
+    threat_range: WeaponRange::Melee,
+    min_range: MinRange::TOO_CLOSE,
+    effective_range: None,
     action_point_cost: 3,
     damage: 7,
+    damage_type: DamageType::Piercing,
     grip: WeaponGrip::TwoHanded,
     attack_attribute: AttackAttribute::Agility,
     attack_enhancement: Some(CAREFUL_AIM),
     on_attacked_reaction: None,
     on_true_hit: None,
+    on_crit: None,
+    crit_multiplier_percent: 50,
+    crit_threshold: 16,
+    lifesteal_percent: 0,
+    grants_passive: None,
+    thrown: false,
     sprite: Some(SpriteId::Bow),
     icon: EquipmentIconId::Bow,
+    durability: Some(25),
+    wear: Wear(0),
     weight: 2,
 };
 
+pub const LONGBOW: Weapon = Weapon {
+    name: "Longbow",
+    range: WeaponRange::Ranged(22.5),
+    threat_range: WeaponRange::Melee,
+    // Too unwieldy to shoot point-blank, and awkward even a few steps away
+    min_range: MinRange {
+        impossible: Some(Range::Melee),
+        disadvantage: Some(Range::Float(6.5)),
+    },
+    effective_range: None,
+    action_point_cost: 3,
+    damage: 8,
+    damage_type: DamageType::Piercing,
+    grip: WeaponGrip::TwoHanded,
+    attack_attribute: AttackAttribute::Agility,
+    attack_enhancement: Some(CAREFUL_AIM),
+    on_attacked_reaction: None,
+    on_true_hit: None,
+    on_crit: None,
+    crit_multiplier_percent: 50,
+    crit_threshold: 16,
+    lifesteal_percent: 0,
+    grants_passive: None,
+    thrown: false,
+    sprite: Some(SpriteId::Bow),
+    icon: EquipmentIconId::Bow,
+    durability: Some(25),
+    wear: Wear(0),
+    weight: 3,
+};
+
+pub const CROSSBOW: Weapon = Weapon {
+    name: "Crossbow",
+    range: WeaponRange::Ranged(12.5),
+    threat_range: WeaponRange::Melee,
+    // Can be aimed and shot just as well up close
+    min_range: MinRange::NONE,
+    effective_range: None,
+    action_point_cost: 4,
+    damage: 8,
+    damage_type: DamageType::Piercing,
+    grip: WeaponGrip::TwoHanded,
+    attack_attribute: AttackAttribute::Agility,
+    attack_enhancement: None,
+    on_attacked_reaction: None,
+    on_true_hit: None,
+    on_crit: None,
+    crit_multiplier_percent: 50,
+    crit_threshold: 16,
+    lifesteal_percent: 0,
+    grants_passive: None,
+    thrown: false,
+    sprite: Some(SpriteId::Bow),
+    icon: EquipmentIconId::Bow,
+    durability: Some(25),
+    wear: Wear(0),
+    weight: 3,
+};
+
 pub const ELUSIVE_BOW: Weapon = Weapon {
     name: "Elusive bow",
     range: WeaponRange::Ranged(18.5),
+    threat_range: WeaponRange::Melee,
+    min_range: MinRange::TOO_CLOSE,
+    effective_range: None,
     action_point_cost: 3,
     damage: 7,
+    damage_type: DamageType::Piercing,
     grip: WeaponGrip::TwoHanded,
     attack_attribute: AttackAttribute::Agility,
     attack_enhancement: Some(CAREFUL_AIM),
     on_attacked_reaction: None,
     on_true_hit: None,
+    on_crit: None,
+    crit_multiplier_percent: 50,
+    crit_threshold: 16,
+    lifesteal_percent: 0,
+    grants_passive: None,
+    thrown: false,
     sprite: Some(SpriteId::Bow),
     icon: EquipmentIconId::Bow,
+    durability: Some(20),
+    wear: Wear(0),
     weight: 2,
 };
 
@@ -411,6 +767,19 @@ pub const BARBED_ARROWS: Arrow = Arrow {
     area_effect: None,
 };
 
+pub const POISON_ARROWS: Arrow = Arrow {
+    name: "Poison arrows",
+    sprite: None,
+    icon: EquipmentIconId::BarbedArrow,
+    bonus_penetration: 0,
+    on_damage_apply: Some(ApplyEffect::Condition(ApplyCondition {
+        condition: Condition::Poisoned,
+        stacks: Some(3),
+        duration_rounds: None,
+    })),
+    area_effect: None,
+};
+
 pub const COLD_ARROWS: Arrow = Arrow {
     name: "Cold arrows",
     sprite: None,
@@ -436,7 +805,9 @@ pub const EXPLODING_ARROWS: Arrow = Arrow {
         effect: AbilityEffect::Negative(AbilityNegativeEffect::Spell(SpellNegativeEffect {
             defense_type: Some(DefenseType::Toughness),
             damage: Some(AbilityDamage::Static(2)),
+            damage_type: None,
             on_hit: Some([Some(ApplyEffect::Pushed(2)), None]),
+            graze_behavior: SpellGrazeBehavior::Reduced,
         })),
     }),
 };
@@ -474,9 +845,11 @@ pub const SHIELD_BASH: Ability = Ability {
     stamina_cost: 1,
     mana_cost: 0,
     requirement: Some(EquipmentRequirement::Shield),
+    cooldown_rounds: 0,
     possible_enhancements: [Some(SHIELD_BASH_KNOCKBACK), None, None],
 
     roll: Some(AbilityRollType::RollAbilityWithAttackModifier),
+    backlash: None,
     target: AbilityTarget::Enemy {
         reach: AbilityReach::Range(Range::Melee),
         /*
@@ -493,6 +866,7 @@ pub const SHIELD_BASH: Ability = Ability {
         effect: AbilityNegativeEffect::Spell(SpellNegativeEffect {
             defense_type: Some(DefenseType::Toughness),
             damage: Some(AbilityDamage::AtLeast(3)),
+            damage_type: None,
             on_hit: Some([
                 Some(ApplyEffect::Condition(ApplyCondition {
                     condition: Condition::Dazed,
@@ -501,6 +875,7 @@ pub const SHIELD_BASH: Ability = Ability {
                 })),
                 None,
             ]),
+            graze_behavior: SpellGrazeBehavior::Reduced,
         }),
         impact_circle: None,
     },
@@ -519,6 +894,7 @@ pub const ENEMY_TACKLE: Ability = Ability {
     stamina_cost: 0,
     mana_cost: 0,
     requirement: None,
+    cooldown_rounds: 0,
     possible_enhancements: [None, None, None],
 
     target: AbilityTarget::Enemy {
@@ -526,6 +902,7 @@ pub const ENEMY_TACKLE: Ability = Ability {
         effect: AbilityNegativeEffect::Spell(SpellNegativeEffect {
             defense_type: Some(DefenseType::Toughness),
             damage: Some(AbilityDamage::AtLeast(3)),
+            damage_type: None,
             on_hit: Some([
                 Some(ApplyEffect::Condition(ApplyCondition {
                     condition: Condition::Dazed,
@@ -535,16 +912,161 @@ pub const ENEMY_TACKLE: Ability = Ability {
                 // Better keep this somewhat low. Critting while knocking into an obstacle can lead to crazy numbers.
                 Some(ApplyEffect::Pushed(2)),
             ]),
+            graze_behavior: SpellGrazeBehavior::Reduced,
         }),
         impact_circle: None,
     },
     animation_color: GRAY,
     roll: Some(AbilityRollType::RollAbilityWithAttackModifier),
+    backlash: None,
     initiate_sound: None,
     resolve_sound: Some(SoundId::Explosion),
     charge_fx: None,
 };
 
+pub const TRIP: Ability = Ability {
+    id: AbilityId::Trip,
+    name: "Trip",
+    description: "Knock the target to the ground",
+    icon: IconId::Tackle,
+    action_point_cost: 2,
+    stamina_cost: 1,
+    mana_cost: 0,
+    requirement: Some(EquipmentRequirement::Weapon(WeaponType::Melee)),
+    cooldown_rounds: 0,
+    possible_enhancements: [None, None, None],
+
+    target: AbilityTarget::Enemy {
+        reach: AbilityReach::Range(Range::Melee),
+        effect: AbilityNegativeEffect::Spell(SpellNegativeEffect {
+            defense_type: Some(DefenseType::Toughness),
+            damage: None,
+            damage_type: None,
+            on_hit: Some([
+                Some(ApplyEffect::Condition(ApplyCondition::new(
+                    Condition::Prone,
+                ))),
+                None,
+            ]),
+            graze_behavior: SpellGrazeBehavior::Reduced,
+        }),
+        impact_circle: None,
+    },
+    animation_color: GRAY,
+    roll: Some(AbilityRollType::RollAbilityWithAttackModifier),
+    backlash: None,
+    initiate_sound: None,
+    resolve_sound: Some(SoundId::Debuff),
+    charge_fx: None,
+};
+
+pub const ENTANGLE: Ability = Ability {
+    id: AbilityId::Entangle,
+    name: "Entangle",
+    description: "|<keyword>Root| an enemy in place",
+    icon: IconId::ShackledMind,
+    action_point_cost: 2,
+    mana_cost: 1,
+    stamina_cost: 0,
+    requirement: None,
+    cooldown_rounds: 0,
+    roll: Some(AbilityRollType::Spell),
+    backlash: None,
+    target: AbilityTarget::Enemy {
+        reach: AbilityReach::Range(Range::Float(12.5)),
+        effect: AbilityNegativeEffect::Spell(SpellNegativeEffect {
+            defense_type: Some(DefenseType::Evasion),
+            damage: None,
+            damage_type: None,
+            on_hit: Some([
+                Some(ApplyEffect::Condition(ApplyCondition {
+                    condition: Condition::Rooted,
+                    stacks: None,
+                    duration_rounds: Some(2),
+                })),
+                None,
+            ]),
+            graze_behavior: SpellGrazeBehavior::Reduced,
+        }),
+        impact_circle: None,
+    },
+    possible_enhancements: [None; 3],
+    animation_color: GREEN,
+    initiate_sound: Some(SoundId::ShootSpell),
+    resolve_sound: Some(SoundId::Debuff),
+    charge_fx: None,
+};
+
+pub const TAUNT: Ability = Ability {
+    id: AbilityId::Taunt,
+    name: "Taunt",
+    description: "Provoke an enemy into attacking you",
+    icon: IconId::Scream,
+    action_point_cost: 1,
+    mana_cost: 0,
+    stamina_cost: 1,
+    requirement: None,
+    cooldown_rounds: 0,
+    roll: Some(AbilityRollType::Spell),
+    backlash: None,
+    target: AbilityTarget::Enemy {
+        reach: AbilityReach::Range(Range::Float(12.5)),
+        effect: AbilityNegativeEffect::Spell(SpellNegativeEffect {
+            defense_type: Some(DefenseType::Will),
+            damage: None,
+            damage_type: None,
+            on_hit: Some([
+                Some(ApplyEffect::Condition(ApplyCondition {
+                    condition: Condition::Taunted,
+                    stacks: None,
+                    duration_rounds: Some(2),
+                })),
+                None,
+            ]),
+            graze_behavior: SpellGrazeBehavior::Reduced,
+        }),
+        impact_circle: None,
+    },
+    possible_enhancements: [None; 3],
+    animation_color: RED,
+    initiate_sound: None,
+    resolve_sound: Some(SoundId::Debuff),
+    charge_fx: None,
+};
+
+pub const STAND_UP: Ability = Ability {
+    id: AbilityId::StandUp,
+    name: "Stand up",
+    description: "Get back on your feet (only usable while |<keyword>Prone|)",
+    icon: IconId::Go,
+    action_point_cost: 1,
+    mana_cost: 0,
+    stamina_cost: 0,
+    requirement: None,
+    cooldown_rounds: 0,
+    roll: None,
+    backlash: None,
+    possible_enhancements: [None; 3],
+    target: AbilityTarget::None {
+        summon: None,
+        self_area: None,
+        self_effect: Some(AbilityPositiveEffect {
+            healing: 0,
+            apply: Some([
+                Some(ApplyEffect::ConsumeCondition {
+                    condition: Condition::Prone,
+                }),
+                None,
+            ]),
+            backfire: None,
+        }),
+    },
+    animation_color: GRAY,
+    initiate_sound: None,
+    resolve_sound: None,
+    charge_fx: None,
+};
+
 pub const ENEMY_SLASHING_ATTACK: Ability = Ability {
     id: AbilityId::EnemySlashingAttack,
     name: "Slashing attack",
@@ -554,9 +1076,11 @@ pub const ENEMY_SLASHING_ATTACK: Ability = Ability {
     stamina_cost: 0,
     mana_cost: 0,
     requirement: Some(EquipmentRequirement::Weapon(WeaponType::Melee)),
+    cooldown_rounds: 0,
     possible_enhancements: [None, None, None],
 
     roll: Some(AbilityRollType::RollDuringAttack(0)),
+    backlash: None,
     target: AbilityTarget::Enemy {
         reach: AbilityReach::Range(Range::Melee),
         effect: AbilityNegativeEffect::PerformAttack(AbilityAttackEffect {
@@ -699,6 +1223,57 @@ pub const CRIPPLING_SHOT: AttackEnhancement = AttackEnhancement {
     ..AttackEnhancement::default()
 };
 
+pub const CALLED_SHOT_LEGS: AttackEnhancement = AttackEnhancement {
+    name: "Aim for the legs",
+    description: "Trade accuracy for a guaranteed |<keyword>Slowed| on a damaging hit",
+    // TODO: unique icon
+    icon: IconId::CripplingShot,
+    stamina_cost: 1,
+    effect: AttackEnhancementEffect {
+        roll_modifier: -4,
+        on_damage_effect: Some(AttackEnhancementOnHitEffect::Target(
+            None,
+            ApplyEffect::Condition(ApplyCondition {
+                condition: Condition::Slowed,
+                stacks: None,
+                duration_rounds: Some(1),
+            }),
+        )),
+        ..AttackEnhancementEffect::default()
+    },
+    ..AttackEnhancement::default()
+};
+
+pub const CALLED_SHOT_ARMS: AttackEnhancement = AttackEnhancement {
+    name: "Aim for the arms",
+    description: "Trade accuracy for guaranteed main-hand exertion on a damaging hit",
+    // TODO: unique icon
+    icon: IconId::CripplingShot,
+    stamina_cost: 1,
+    effect: AttackEnhancementEffect {
+        roll_modifier: -4,
+        on_damage_effect: Some(AttackEnhancementOnHitEffect::Target(
+            None,
+            ApplyEffect::Condition(ApplyCondition {
+                condition: Condition::MainHandExertion,
+                stacks: Some(2),
+                duration_rounds: None,
+            }),
+        )),
+        ..AttackEnhancementEffect::default()
+    },
+    ..AttackEnhancement::default()
+};
+
+pub const SPRINT: MovementEnhancement = MovementEnhancement {
+    name: "Sprint",
+    description: "Gain extra movement this turn",
+    icon: IconId::Move,
+    action_point_cost: 1,
+    stamina_cost: 0,
+    bonus_distance_per_agility: 0.5,
+};
+
 pub const PARRY: OnAttackedReaction = OnAttackedReaction {
     id: OnAttackedReactionId::Parry,
     name: "Parry",
@@ -709,26 +1284,31 @@ pub const PARRY: OnAttackedReaction = OnAttackedReaction {
     effect: OnAttackedReactionEffect {
         bonus_evasion: 7,
         bonus_armor: 0,
+        counterattack: false,
     },
     required_attack_type: Some(AttackType::Melee),
     used_hand: Some(HandType::MainHand),
     target: OnAttackedReactionTarget::OnlySelf,
+    only_from_front: false,
 };
 
 pub const BLOCK: OnAttackedReaction = OnAttackedReaction {
     id: OnAttackedReactionId::Block,
     name: "Block",
-    description: "Protect yourself or adjacent ally (1 attack per round)",
+    description:
+        "Protect yourself or adjacent ally against an attack from the front (1 attack per round)",
     icon: IconId::Block,
     action_point_cost: 0,
     stamina_cost: 1,
     effect: OnAttackedReactionEffect {
         bonus_evasion: 0,
         bonus_armor: 3,
+        counterattack: false,
     },
     required_attack_type: None,
     used_hand: Some(HandType::OffHand),
     target: OnAttackedReactionTarget::SelfOrAdjacentAlly,
+    only_from_front: true,
 };
 
 pub const SIDE_STEP: OnAttackedReaction = OnAttackedReaction {
@@ -741,10 +1321,30 @@ pub const SIDE_STEP: OnAttackedReaction = OnAttackedReaction {
     effect: OnAttackedReactionEffect {
         bonus_evasion: 10,
         bonus_armor: 0,
+        counterattack: false,
     },
     required_attack_type: None,
     used_hand: None,
     target: OnAttackedReactionTarget::OnlySelf,
+    only_from_front: false,
+};
+
+pub const RIPOSTE: OnAttackedReaction = OnAttackedReaction {
+    id: OnAttackedReactionId::Riposte,
+    name: "Riposte",
+    description: "Protect against melee attack, and strike back if it's reduced to a graze",
+    icon: IconId::Parry,
+    action_point_cost: 0,
+    stamina_cost: 3,
+    effect: OnAttackedReactionEffect {
+        bonus_evasion: 5,
+        bonus_armor: 0,
+        counterattack: true,
+    },
+    required_attack_type: Some(AttackType::Melee),
+    used_hand: Some(HandType::MainHand),
+    target: OnAttackedReactionTarget::OnlySelf,
+    only_from_front: false,
 };
 
 pub const RAGE: OnHitReaction = OnHitReaction {
@@ -753,10 +1353,22 @@ pub const RAGE: OnHitReaction = OnHitReaction {
     icon: IconId::Rage,
     action_point_cost: 1,
     stamina_cost: 1,
+    mana_cost: 0,
     effect: OnHitReactionEffect::Rage,
     required_attack_type: None,
 };
 
+pub const SPELL_REFLECT: OnHitReaction = OnHitReaction {
+    name: "Spell reflect",
+    description: "Reflect some of the damage back at the attacker",
+    icon: IconId::Banshee,
+    action_point_cost: 1,
+    stamina_cost: 0,
+    mana_cost: 1,
+    effect: OnHitReactionEffect::SpellReflect(50),
+    required_attack_type: None,
+};
+
 pub const SWEEP_ATTACK_PRECISE: AbilityEnhancement = AbilityEnhancement {
     ability_id: AbilityId::SweepAttack,
     name: "Precise",
@@ -778,10 +1390,13 @@ pub const SWEEP_ATTACK: Ability = Ability {
     mana_cost: 0,
     stamina_cost: 2,
     requirement: Some(EquipmentRequirement::Weapon(WeaponType::Melee)),
+    cooldown_rounds: 0,
 
     roll: Some(AbilityRollType::RollDuringAttack(0)),
+    backlash: None,
     possible_enhancements: [Some(SWEEP_ATTACK_PRECISE), None, None],
     target: AbilityTarget::None {
+        summon: None,
         self_area: Some(AreaEffect {
             // Melee
             shape: AreaShape::Circle(Range::Float(2.5)),
@@ -801,16 +1416,45 @@ pub const SWEEP_ATTACK: Ability = Ability {
     }),
 };
 
-pub const LUNGE_ATTACK_HEAVY_IMPACT: AbilityEnhancement = AbilityEnhancement {
-    ability_id: AbilityId::LungeAttack,
-    name: "Heavy impact",
-    description: "",
-    icon: IconId::CrushingStrike,
+pub const FLURRY: Ability = Ability {
+    id: AbilityId::Flurry,
+    name: "Flurry",
+    description: "Strike an enemy with both weapons in quick succession",
+    // TODO: unique icon
+    icon: IconId::MeleeAttack,
+    action_point_cost: 4,
+    mana_cost: 0,
     stamina_cost: 1,
-    attack_effect: Some(AttackEnhancementEffect {
-        on_damage_effect: Some(AttackEnhancementOnHitEffect::Target(
-            Some(DefenseType::Toughness),
-            ApplyEffect::RemoveActionPoints(1),
+    requirement: Some(EquipmentRequirement::DualWield),
+    cooldown_rounds: 0,
+
+    roll: Some(AbilityRollType::RollDuringAttack(0)),
+    backlash: None,
+    possible_enhancements: [None; 3],
+    target: AbilityTarget::Enemy {
+        reach: AbilityReach::Range(Range::Melee),
+        effect: AbilityNegativeEffect::PerformAttack(AbilityAttackEffect {
+            flurry: true,
+            ..AbilityAttackEffect::default()
+        }),
+        impact_circle: None,
+    },
+    animation_color: GRAY,
+    initiate_sound: None,
+    resolve_sound: Some(SoundId::Explosion),
+    charge_fx: None,
+};
+
+pub const LUNGE_ATTACK_HEAVY_IMPACT: AbilityEnhancement = AbilityEnhancement {
+    ability_id: AbilityId::LungeAttack,
+    name: "Heavy impact",
+    description: "",
+    icon: IconId::CrushingStrike,
+    stamina_cost: 1,
+    attack_effect: Some(AttackEnhancementEffect {
+        on_damage_effect: Some(AttackEnhancementOnHitEffect::Target(
+            Some(DefenseType::Toughness),
+            ApplyEffect::RemoveActionPoints(1),
         )),
         ..AttackEnhancementEffect::default()
     }),
@@ -843,8 +1487,10 @@ pub const LUNGE_ATTACK: Ability = Ability {
     mana_cost: 0,
     stamina_cost: 2,
     requirement: Some(EquipmentRequirement::Weapon(WeaponType::Melee)),
+    cooldown_rounds: 0,
 
     roll: Some(AbilityRollType::RollDuringAttack(0)),
+    backlash: None,
     possible_enhancements: [
         Some(LUNGE_ATTACK_HEAVY_IMPACT),
         Some(LUNGE_ATTACK_REACH),
@@ -874,9 +1520,12 @@ pub const ENEMY_BRACE: Ability = Ability {
     mana_cost: 0,
     stamina_cost: 0,
     requirement: Some(EquipmentRequirement::Shield),
+    cooldown_rounds: 0,
     roll: None,
+    backlash: None,
     possible_enhancements: [None; 3],
     target: AbilityTarget::None {
+        summon: None,
         self_area: None,
         self_effect: Some(AbilityPositiveEffect {
             healing: 0,
@@ -888,6 +1537,7 @@ pub const ENEMY_BRACE: Ability = Ability {
                 })),
                 None,
             ]),
+            backfire: None,
         }),
     },
     animation_color: GRAY,
@@ -905,9 +1555,12 @@ pub const BRACE: Ability = Ability {
     mana_cost: 0,
     stamina_cost: 1,
     requirement: Some(EquipmentRequirement::Shield),
+    cooldown_rounds: 0,
     roll: None,
+    backlash: None,
     possible_enhancements: [None; 3],
     target: AbilityTarget::None {
+        summon: None,
         self_area: None,
         self_effect: Some(AbilityPositiveEffect {
             healing: 0,
@@ -919,6 +1572,7 @@ pub const BRACE: Ability = Ability {
                 })),
                 None,
             ]),
+            backfire: None,
         }),
     },
     animation_color: GRAY,
@@ -930,6 +1584,189 @@ pub const BRACE: Ability = Ability {
     }),
 };
 
+pub const SET_WEAPON: Ability = Ability {
+    id: AbilityId::SetWeapon,
+    name: "Set weapon",
+    description: "Brace a reach weapon against enemies that charge in",
+    // TODO: unique icon
+    icon: IconId::Brace,
+    action_point_cost: 1,
+    mana_cost: 0,
+    stamina_cost: 1,
+    requirement: Some(EquipmentRequirement::ReachWeapon),
+    cooldown_rounds: 0,
+    roll: None,
+    backlash: None,
+    possible_enhancements: [None; 3],
+    target: AbilityTarget::None {
+        summon: None,
+        self_area: None,
+        self_effect: Some(AbilityPositiveEffect {
+            healing: 0,
+            apply: Some([
+                Some(ApplyEffect::Condition(ApplyCondition {
+                    condition: Condition::Braced,
+                    stacks: None,
+                    duration_rounds: Some(1),
+                })),
+                None,
+            ]),
+            backfire: None,
+        }),
+    },
+    animation_color: GRAY,
+    initiate_sound: None,
+    resolve_sound: Some(SoundId::BuffBrace),
+    charge_fx: None,
+};
+
+pub const OVERWATCH: Ability = Ability {
+    id: AbilityId::Overwatch,
+    name: "Overwatch",
+    description:
+        "Set aside the remaining AP to shoot the first enemy that moves into range and sight",
+    // TODO: unique icon
+    icon: IconId::CarefulAim,
+    action_point_cost: 1,
+    mana_cost: 0,
+    stamina_cost: 0,
+    requirement: Some(EquipmentRequirement::Weapon(WeaponType::Ranged)),
+    cooldown_rounds: 0,
+    roll: None,
+    backlash: None,
+    possible_enhancements: [None; 3],
+    target: AbilityTarget::None {
+        summon: None,
+        self_area: None,
+        self_effect: Some(AbilityPositiveEffect {
+            healing: 0,
+            apply: Some([
+                Some(ApplyEffect::Condition(ApplyCondition {
+                    condition: Condition::Overwatch,
+                    // Replaced by the reserved AP once the ability has resolved
+                    stacks: Some(1),
+                    duration_rounds: Some(1),
+                })),
+                None,
+            ]),
+            backfire: None,
+        }),
+    },
+    animation_color: GRAY,
+    initiate_sound: None,
+    resolve_sound: Some(SoundId::BuffBrace),
+    charge_fx: None,
+};
+
+pub const AIM: Ability = Ability {
+    id: AbilityId::Aim,
+    name: "Aim",
+    description: "Take careful aim, improving the next attack. Can be built up over several turns, but is lost when moving",
+    icon: IconId::CarefulAim,
+    action_point_cost: 1,
+    mana_cost: 0,
+    stamina_cost: 0,
+    requirement: None,
+    cooldown_rounds: 0,
+    roll: None,
+    backlash: None,
+    possible_enhancements: [None; 3],
+    target: AbilityTarget::None {
+        summon: None,
+        self_area: None,
+        self_effect: Some(AbilityPositiveEffect {
+            healing: 0,
+            apply: Some([
+                Some(ApplyEffect::Condition(ApplyCondition {
+                    condition: Condition::Aiming,
+                    stacks: Some(1),
+                    duration_rounds: None,
+                })),
+                None,
+            ]),
+            backfire: None,
+        }),
+    },
+    animation_color: GRAY,
+    initiate_sound: None,
+    resolve_sound: Some(SoundId::BuffBrace),
+    charge_fx: None,
+};
+
+pub const HIDE: Ability = Ability {
+    id: AbilityId::Hide,
+    name: "Hide",
+    description: "Slip out of sight to set up a devastating first strike (only usable while not adjacent to an enemy)",
+    // TODO: unique icon
+    icon: IconId::Sidestep,
+    action_point_cost: 2,
+    mana_cost: 0,
+    stamina_cost: 1,
+    requirement: None,
+    cooldown_rounds: 0,
+    roll: None,
+    backlash: None,
+    possible_enhancements: [None; 3],
+    target: AbilityTarget::None {
+        summon: None,
+        self_area: None,
+        self_effect: Some(AbilityPositiveEffect {
+            healing: 0,
+            apply: Some([
+                Some(ApplyEffect::Condition(ApplyCondition::new(Condition::Hidden))),
+                None,
+            ]),
+            backfire: None,
+        }),
+    },
+    animation_color: GRAY,
+    initiate_sound: None,
+    resolve_sound: Some(SoundId::Powerup),
+    charge_fx: None,
+};
+
+pub const FRENZY: Ability = Ability {
+    id: AbilityId::Frenzy,
+    name: "Frenzy",
+    description: "Work yourself into a frenzy. Might leave you dazed instead.",
+    icon: IconId::Rage,
+    action_point_cost: 1,
+    mana_cost: 0,
+    stamina_cost: 2,
+    requirement: None,
+    cooldown_rounds: 0,
+    roll: Some(AbilityRollType::RollAbilityWithAttackModifier),
+    backlash: None,
+    possible_enhancements: [None; 3],
+    target: AbilityTarget::None {
+        summon: None,
+        self_area: None,
+        self_effect: Some(AbilityPositiveEffect {
+            healing: 0,
+            apply: Some([
+                Some(ApplyEffect::Condition(ApplyCondition {
+                    condition: Condition::Hastened,
+                    stacks: None,
+                    duration_rounds: Some(1),
+                })),
+                Some(ApplyEffect::GainStamina(2)),
+            ]),
+            backfire: Some(Backfire {
+                roll_below: 10,
+                effect: ApplyEffect::Condition(ApplyCondition {
+                    condition: Condition::Dazed,
+                    stacks: None,
+                    duration_rounds: Some(1),
+                }),
+            }),
+        }),
+    },
+    animation_color: RED,
+    initiate_sound: None,
+    resolve_sound: Some(SoundId::Powerup),
+    charge_fx: None,
+};
+
 pub const SCREAM_SHRIEK: AbilityEnhancement = AbilityEnhancement {
     ability_id: AbilityId::Scream,
     name: "Shriek",
@@ -952,15 +1789,19 @@ pub const SCREAM: Ability = Ability {
     mana_cost: 1,
     stamina_cost: 0,
     requirement: None,
+    cooldown_rounds: 0,
 
     roll: Some(AbilityRollType::Spell),
+    backlash: None,
     target: AbilityTarget::None {
+        summon: None,
         self_area: Some(AreaEffect {
             shape: AreaShape::Circle(Range::Ranged(7)),
             acquisition: AreaTargetAcquisition::Enemies,
             effect: AbilityEffect::Negative(AbilityNegativeEffect::Spell(SpellNegativeEffect {
                 defense_type: Some(DefenseType::Will),
                 damage: None,
+                damage_type: None,
                 on_hit: Some([
                     Some(ApplyEffect::Condition(ApplyCondition {
                         condition: Condition::Dazed,
@@ -969,6 +1810,7 @@ pub const SCREAM: Ability = Ability {
                     })),
                     None,
                 ]),
+                graze_behavior: SpellGrazeBehavior::Reduced,
             })),
         }),
         self_effect: None,
@@ -993,13 +1835,16 @@ pub const SHACKLED_MIND: Ability = Ability {
     mana_cost: 2,
     stamina_cost: 0,
     requirement: None,
+    cooldown_rounds: 0,
 
     roll: Some(AbilityRollType::Spell),
+    backlash: None,
     target: AbilityTarget::Enemy {
         reach: AbilityReach::Range(Range::Float(12.5)),
         effect: AbilityNegativeEffect::Spell(SpellNegativeEffect {
             defense_type: Some(DefenseType::Will),
             damage: None,
+            damage_type: None,
             on_hit: Some([
                 Some(ApplyEffect::Condition(ApplyCondition {
                     condition: Condition::Slowed,
@@ -1012,6 +1857,7 @@ pub const SHACKLED_MIND: Ability = Ability {
                     duration_rounds: Some(2),
                 })),
             ]),
+            graze_behavior: SpellGrazeBehavior::Reduced,
         }),
         impact_circle: None,
     },
@@ -1054,44 +1900,40 @@ pub const SHACKLED_MIND: Ability = Ability {
     }),
 };
 
-pub const MIND_BLAST: Ability = Ability {
-    id: AbilityId::MindBlast,
-    name: "Mind blast",
-    description: "Damage an enemy and make them lose AP",
-    icon: IconId::Mindblast,
-    action_point_cost: 3,
-    mana_cost: 1,
+pub const FREEZE: Ability = Ability {
+    id: AbilityId::Freeze,
+    name: "Freeze",
+    description: "Encase an enemy in ice, making them skip their next turn",
+    // TODO: unique icon
+    icon: IconId::ShackledMind,
+    action_point_cost: 4,
+    mana_cost: 3,
     stamina_cost: 0,
     requirement: None,
+    cooldown_rounds: 0,
 
     roll: Some(AbilityRollType::Spell),
-    possible_enhancements: [
-        Some(AbilityEnhancement {
-            ability_id: AbilityId::MindBlast,
-            name: "Dualcast",
-            description: "Spell is cast twice",
-            icon: IconId::Dualcast,
-            action_point_cost: 1,
-            mana_cost: 1,
-            spell_effect: Some(SpellEnhancementEffect {
-                cast_twice: true,
-                ..SpellEnhancementEffect::default()
-            }),
-            ..AbilityEnhancement::default()
-        }),
-        None,
-        None,
-    ],
+    backlash: None,
+    possible_enhancements: [None; 3],
     target: AbilityTarget::Enemy {
-        reach: AbilityReach::Range(Range::Float(13.5)),
+        reach: AbilityReach::Range(Range::Float(8.5)),
         effect: AbilityNegativeEffect::Spell(SpellNegativeEffect {
-            defense_type: Some(DefenseType::Will),
-            damage: Some(AbilityDamage::Static(4)),
-            on_hit: Some([Some(ApplyEffect::RemoveActionPoints(1)), None]),
+            defense_type: Some(DefenseType::Toughness),
+            damage: None,
+            damage_type: Some(DamageType::Cold),
+            on_hit: Some([
+                Some(ApplyEffect::Condition(ApplyCondition {
+                    condition: Condition::Frozen,
+                    stacks: Some(1),
+                    duration_rounds: None,
+                })),
+                None,
+            ]),
+            graze_behavior: SpellGrazeBehavior::Reduced,
         }),
         impact_circle: None,
     },
-    animation_color: PURPLE,
+    animation_color: BLUE,
     initiate_sound: Some(SoundId::ShootSpell),
     resolve_sound: Some(SoundId::Debuff),
     charge_fx: Some(AbilityChargeFx {
@@ -1100,43 +1942,471 @@ pub const MIND_BLAST: Ability = Ability {
     }),
 };
 
-pub const INFLICT_WOUNDS_NECROTIC_INFLUENCE: AbilityEnhancement = AbilityEnhancement {
-    ability_id: AbilityId::InflictWounds,
-    name: "Necrotic influence",
-    description: "Heal and grow stronger for each affected target",
-    icon: IconId::NecroticInfluence,
-    mana_cost: 1,
-    spell_effect: Some(SpellEnhancementEffect::default()),
-    apply_on_self_per_area_target_hit: Some([
-        Some(ApplyEffect::GainHealth(1)),
-        Some(ApplyEffect::Condition(ApplyCondition {
-            condition: Condition::ArcaneSurge,
-            stacks: Some(1),
-            duration_rounds: None,
-        })),
-    ]),
-    ..AbilityEnhancement::default()
+pub const PETRIFY: Ability = Ability {
+    id: AbilityId::Petrify,
+    name: "Petrify",
+    description: "Turn an enemy to stone. They can't act, but can't be harmed either",
+    // TODO: unique icon
+    icon: IconId::ShackledMind,
+    action_point_cost: 4,
+    mana_cost: 3,
+    stamina_cost: 0,
+    requirement: None,
+    cooldown_rounds: 0,
+
+    roll: Some(AbilityRollType::Spell),
+    backlash: None,
+    possible_enhancements: [None; 3],
+    target: AbilityTarget::Enemy {
+        reach: AbilityReach::Range(Range::Float(8.5)),
+        effect: AbilityNegativeEffect::Spell(SpellNegativeEffect {
+            defense_type: Some(DefenseType::Toughness),
+            damage: None,
+            damage_type: None,
+            on_hit: Some([
+                Some(ApplyEffect::Condition(ApplyCondition {
+                    condition: Condition::Petrified,
+                    stacks: None,
+                    duration_rounds: Some(2),
+                })),
+                None,
+            ]),
+            graze_behavior: SpellGrazeBehavior::Reduced,
+        }),
+        impact_circle: None,
+    },
+    animation_color: GRAY,
+    initiate_sound: Some(SoundId::ShootSpell),
+    resolve_sound: Some(SoundId::Debuff),
+    charge_fx: None,
 };
-pub const INFLICT_WOUNDS: Ability = Ability {
-    id: AbilityId::InflictWounds,
-    name: "Inflict wounds",
-    description: "Deal damage and cause |<keyword>Bleeding| in an area",
-    icon: IconId::NecroticInfluence,
-    action_point_cost: 3,
-    mana_cost: 1,
+
+pub const CONFUSE: Ability = Ability {
+    id: AbilityId::Confuse,
+    name: "Confuse",
+    description: "Cloud an enemy's mind, making them attack friend and foe alike",
+    // TODO: unique icon
+    icon: IconId::ShackledMind,
+    action_point_cost: 4,
+    mana_cost: 3,
     stamina_cost: 0,
     requirement: None,
+    cooldown_rounds: 0,
 
     roll: Some(AbilityRollType::Spell),
-    possible_enhancements: [Some(INFLICT_WOUNDS_NECROTIC_INFLUENCE), None, None],
-    target: AbilityTarget::Area {
-        range: Range::Float(7.5),
-        area_effect: AreaEffect {
+    backlash: None,
+    possible_enhancements: [None; 3],
+    target: AbilityTarget::Enemy {
+        reach: AbilityReach::Range(Range::Float(8.5)),
+        effect: AbilityNegativeEffect::Spell(SpellNegativeEffect {
+            defense_type: Some(DefenseType::Will),
+            damage: None,
+            damage_type: None,
+            on_hit: Some([
+                Some(ApplyEffect::Condition(ApplyCondition {
+                    condition: Condition::Confused,
+                    stacks: None,
+                    duration_rounds: Some(1),
+                })),
+                None,
+            ]),
+            graze_behavior: SpellGrazeBehavior::Reduced,
+        }),
+        impact_circle: None,
+    },
+    animation_color: PURPLE,
+    initiate_sound: Some(SoundId::ShootSpell),
+    resolve_sound: Some(SoundId::Debuff),
+    charge_fx: Some(AbilityChargeFx {
+        particle_shape: ParticleShape::Circle,
+        sound: SoundId::MechanicNoise,
+    }),
+};
+
+pub const WALL_OF_FIRE: Ability = Ability {
+    id: AbilityId::WallOfFire,
+    name: "Wall of fire",
+    description: "Raise a line of flames that keeps burning anyone who walks through it",
+    // TODO: unique icon
+    icon: IconId::Inferno,
+    action_point_cost: 4,
+    mana_cost: 3,
+    stamina_cost: 0,
+    requirement: None,
+    cooldown_rounds: 0,
+
+    roll: Some(AbilityRollType::Spell),
+    backlash: Some(Backlash {
+        dice_roll_at_most: 1,
+        effect: ApplyEffect::Condition(ApplyCondition {
+            condition: Condition::Dazed,
+            stacks: None,
+            duration_rounds: Some(1),
+        }),
+    }),
+    possible_enhancements: [None; 3],
+    target: AbilityTarget::Area {
+        range: Range::Float(8.5),
+        ground_effect: Some(GroundEffect {
+            remaining_rounds: 3,
+            on_enter: Some(ApplyEffect::Condition(ApplyCondition {
+                condition: Condition::Burning,
+                stacks: Some(2),
+                duration_rounds: None,
+            })),
+            on_end_turn: Some(ApplyEffect::Condition(ApplyCondition {
+                condition: Condition::Burning,
+                stacks: Some(2),
+                duration_rounds: None,
+            })),
+        }),
+        area_effect: AreaEffect {
+            shape: AreaShape::Line,
+            acquisition: AreaTargetAcquisition::Enemies,
+            effect: AbilityEffect::Negative(AbilityNegativeEffect::Spell(SpellNegativeEffect {
+                defense_type: Some(DefenseType::Evasion),
+                damage: Some(AbilityDamage::AtLeast(2)),
+                damage_type: Some(DamageType::Fire),
+                on_hit: None,
+                graze_behavior: SpellGrazeBehavior::Reduced,
+            })),
+        },
+    },
+    animation_color: RED,
+    initiate_sound: Some(SoundId::ShootSpell),
+    resolve_sound: Some(SoundId::Explosion),
+    charge_fx: Some(AbilityChargeFx {
+        particle_shape: ParticleShape::Circle,
+        sound: SoundId::MechanicNoise,
+    }),
+};
+
+pub const SOUL_REND: Ability = Ability {
+    id: AbilityId::SoulRend,
+    name: "Soul rend",
+    description: "Tear at an enemy's very being. Hard to land, but devastating",
+    icon: IconId::NecroticInfluence,
+    action_point_cost: 3,
+    mana_cost: 3,
+    stamina_cost: 0,
+    requirement: None,
+    cooldown_rounds: 0,
+
+    roll: Some(AbilityRollType::Spell),
+    backlash: None,
+    possible_enhancements: [None; 3],
+    target: AbilityTarget::Enemy {
+        reach: AbilityReach::Range(Range::Float(9.5)),
+        effect: AbilityNegativeEffect::Spell(SpellNegativeEffect {
+            defense_type: Some(DefenseType::HigherOf(
+                &DefenseType::Will,
+                &DefenseType::Toughness,
+            )),
+            damage: Some(AbilityDamage::AtLeast(10)),
+            damage_type: None,
+            on_hit: Some([
+                Some(ApplyEffect::Condition(ApplyCondition {
+                    condition: Condition::Weakened,
+                    stacks: Some(2),
+                    duration_rounds: None,
+                })),
+                None,
+            ]),
+            graze_behavior: SpellGrazeBehavior::DamageOnly,
+        }),
+        impact_circle: None,
+    },
+    animation_color: PURPLE,
+    initiate_sound: Some(SoundId::ShootSpell),
+    resolve_sound: Some(SoundId::Debuff),
+    charge_fx: None,
+};
+
+pub const MIND_CRUSH: Ability = Ability {
+    id: AbilityId::MindCrush,
+    name: "Mind crush",
+    description: "Assault an enemy's body and mind at once. Strikes wherever they are weakest",
+    icon: IconId::Mindblast,
+    action_point_cost: 3,
+    mana_cost: 2,
+    stamina_cost: 0,
+    requirement: None,
+    cooldown_rounds: 0,
+
+    roll: Some(AbilityRollType::Spell),
+    backlash: None,
+    possible_enhancements: [None; 3],
+    target: AbilityTarget::Enemy {
+        reach: AbilityReach::Range(Range::Float(9.5)),
+        effect: AbilityNegativeEffect::Spell(SpellNegativeEffect {
+            defense_type: Some(DefenseType::LowerOf(
+                &DefenseType::Will,
+                &DefenseType::Toughness,
+            )),
+            damage: Some(AbilityDamage::AtLeast(4)),
+            damage_type: None,
+            on_hit: Some([
+                Some(ApplyEffect::Condition(ApplyCondition {
+                    condition: Condition::Dazed,
+                    stacks: None,
+                    duration_rounds: Some(1),
+                })),
+                None,
+            ]),
+            graze_behavior: SpellGrazeBehavior::DamageOnly,
+        }),
+        impact_circle: None,
+    },
+    animation_color: PURPLE,
+    initiate_sound: Some(SoundId::ShootSpell),
+    resolve_sound: Some(SoundId::Debuff),
+    charge_fx: None,
+};
+
+pub const MIND_BLAST: Ability = Ability {
+    id: AbilityId::MindBlast,
+    name: "Mind blast",
+    description: "Damage an enemy and make them lose AP",
+    icon: IconId::Mindblast,
+    action_point_cost: 3,
+    mana_cost: 1,
+    stamina_cost: 0,
+    requirement: None,
+    cooldown_rounds: 0,
+
+    roll: Some(AbilityRollType::Spell),
+    backlash: None,
+    possible_enhancements: [
+        Some(AbilityEnhancement {
+            ability_id: AbilityId::MindBlast,
+            name: "Dualcast",
+            description: "Spell is cast twice",
+            icon: IconId::Dualcast,
+            action_point_cost: 1,
+            mana_cost: 1,
+            spell_effect: Some(SpellEnhancementEffect {
+                cast_twice: true,
+                ..SpellEnhancementEffect::default()
+            }),
+            ..AbilityEnhancement::default()
+        }),
+        None,
+        None,
+    ],
+    target: AbilityTarget::Enemy {
+        reach: AbilityReach::Range(Range::Float(13.5)),
+        effect: AbilityNegativeEffect::Spell(SpellNegativeEffect {
+            defense_type: Some(DefenseType::Will),
+            damage: Some(AbilityDamage::Static(4)),
+            damage_type: None,
+            on_hit: Some([Some(ApplyEffect::RemoveActionPoints(1)), None]),
+            graze_behavior: SpellGrazeBehavior::Reduced,
+        }),
+        impact_circle: None,
+    },
+    animation_color: PURPLE,
+    initiate_sound: Some(SoundId::ShootSpell),
+    resolve_sound: Some(SoundId::Debuff),
+    charge_fx: Some(AbilityChargeFx {
+        particle_shape: ParticleShape::Circle,
+        sound: SoundId::MechanicNoise,
+    }),
+};
+
+pub const RAISE_SKELETON: Ability = Ability {
+    id: AbilityId::RaiseSkeleton,
+    name: "Raise skeleton",
+    description: "Raise a skeleton that fights by your side for a few rounds",
+    icon: IconId::NecroticInfluence,
+    action_point_cost: 3,
+    mana_cost: 2,
+    stamina_cost: 0,
+    requirement: None,
+    cooldown_rounds: 0,
+
+    roll: None,
+    backlash: None,
+    possible_enhancements: [None, None, None],
+    target: AbilityTarget::None {
+        self_area: None,
+        self_effect: None,
+        summon: Some(SummonEffect {
+            name: "Skeleton",
+            portrait: PortraitId::Skeleton,
+            sprite: SpriteId::Skeleton,
+            attributes: (3, 3, 1, 1),
+            max_health: 8,
+            weapon: Some(BAD_SWORD),
+            duration_rounds: 3,
+        }),
+    },
+    animation_color: PURPLE,
+    initiate_sound: None,
+    resolve_sound: Some(SoundId::Powerup),
+    charge_fx: Some(AbilityChargeFx {
+        particle_shape: ParticleShape::Circle,
+        sound: SoundId::MechanicNoise,
+    }),
+};
+
+pub const MANA_BURN: Ability = Ability {
+    id: AbilityId::ManaBurn,
+    name: "Mana burn",
+    description: "Drain an enemy's mana, damaging them for each point drained",
+    // TODO: unique icon
+    icon: IconId::Mindblast,
+    action_point_cost: 2,
+    mana_cost: 1,
+    stamina_cost: 0,
+    requirement: None,
+    cooldown_rounds: 0,
+
+    roll: Some(AbilityRollType::Spell),
+    backlash: None,
+    possible_enhancements: [None, None, None],
+    target: AbilityTarget::Enemy {
+        reach: AbilityReach::Range(Range::Float(13.5)),
+        effect: AbilityNegativeEffect::Spell(SpellNegativeEffect {
+            defense_type: Some(DefenseType::Will),
+            damage: None,
+            damage_type: None,
+            on_hit: Some([
+                Some(ApplyEffect::DrainMana {
+                    amount: 3,
+                    damage_per_point: 1,
+                }),
+                None,
+            ]),
+            graze_behavior: SpellGrazeBehavior::Reduced,
+        }),
+        impact_circle: None,
+    },
+    animation_color: PURPLE,
+    initiate_sound: Some(SoundId::ShootSpell),
+    resolve_sound: Some(SoundId::Debuff),
+    charge_fx: Some(AbilityChargeFx {
+        particle_shape: ParticleShape::Circle,
+        sound: SoundId::MechanicNoise,
+    }),
+};
+
+pub const DISPEL_MAGIC: Ability = Ability {
+    id: AbilityId::DispelMagic,
+    name: "Dispel magic",
+    description: "Strip an enemy of its beneficial conditions",
+    // TODO: unique icon
+    icon: IconId::Mindblast,
+    action_point_cost: 2,
+    mana_cost: 1,
+    stamina_cost: 0,
+    requirement: None,
+    cooldown_rounds: 0,
+
+    roll: Some(AbilityRollType::Spell),
+    backlash: None,
+    possible_enhancements: [None; 3],
+    target: AbilityTarget::Enemy {
+        reach: AbilityReach::Range(Range::Float(11.5)),
+        effect: AbilityNegativeEffect::Spell(SpellNegativeEffect {
+            defense_type: Some(DefenseType::Will),
+            damage: None,
+            damage_type: None,
+            on_hit: Some([
+                Some(ApplyEffect::Dispel(DispelEffect {
+                    positive: true,
+                    max_count: 2,
+                })),
+                None,
+            ]),
+            graze_behavior: SpellGrazeBehavior::Reduced,
+        }),
+        impact_circle: None,
+    },
+    animation_color: PURPLE,
+    initiate_sound: Some(SoundId::ShootSpell),
+    resolve_sound: Some(SoundId::Debuff),
+    charge_fx: Some(AbilityChargeFx {
+        particle_shape: ParticleShape::Circle,
+        sound: SoundId::MechanicNoise,
+    }),
+};
+
+pub const GUST: Ability = Ability {
+    id: AbilityId::Gust,
+    name: "Gust",
+    description: "A blast of wind that |<keyword>Pushes| an enemy away",
+    // TODO: unique icon
+    icon: IconId::Extend,
+    action_point_cost: 2,
+    mana_cost: 1,
+    stamina_cost: 0,
+    requirement: None,
+    cooldown_rounds: 0,
+
+    roll: Some(AbilityRollType::Spell),
+    backlash: None,
+    possible_enhancements: [None, None, None],
+    target: AbilityTarget::Enemy {
+        reach: AbilityReach::Range(Range::Float(9.5)),
+        effect: AbilityNegativeEffect::Spell(SpellNegativeEffect {
+            defense_type: Some(DefenseType::Toughness),
+            damage: Some(AbilityDamage::Static(1)),
+            damage_type: None,
+            on_hit: Some([Some(ApplyEffect::Pushed(3)), None]),
+            graze_behavior: SpellGrazeBehavior::Reduced,
+        }),
+        impact_circle: None,
+    },
+    animation_color: GRAY,
+    initiate_sound: Some(SoundId::ShootSpell),
+    resolve_sound: Some(SoundId::Explosion),
+    charge_fx: Some(AbilityChargeFx {
+        particle_shape: ParticleShape::Circle,
+        sound: SoundId::MechanicNoise,
+    }),
+};
+
+pub const INFLICT_WOUNDS_NECROTIC_INFLUENCE: AbilityEnhancement = AbilityEnhancement {
+    ability_id: AbilityId::InflictWounds,
+    name: "Necrotic influence",
+    description: "Heal and grow stronger for each affected target",
+    icon: IconId::NecroticInfluence,
+    mana_cost: 1,
+    spell_effect: Some(SpellEnhancementEffect::default()),
+    apply_on_self_per_area_target_hit: Some([
+        Some(ApplyEffect::GainHealth(1)),
+        Some(ApplyEffect::Condition(ApplyCondition {
+            condition: Condition::ArcaneSurge,
+            stacks: Some(1),
+            duration_rounds: None,
+        })),
+    ]),
+    ..AbilityEnhancement::default()
+};
+pub const INFLICT_WOUNDS: Ability = Ability {
+    id: AbilityId::InflictWounds,
+    name: "Inflict wounds",
+    description: "Deal damage and cause |<keyword>Bleeding| in an area",
+    icon: IconId::NecroticInfluence,
+    action_point_cost: 3,
+    mana_cost: 1,
+    stamina_cost: 0,
+    requirement: None,
+    cooldown_rounds: 0,
+
+    roll: Some(AbilityRollType::Spell),
+    backlash: None,
+    possible_enhancements: [Some(INFLICT_WOUNDS_NECROTIC_INFLUENCE), None, None],
+    target: AbilityTarget::Area {
+        range: Range::Float(7.5),
+        ground_effect: None,
+        area_effect: AreaEffect {
             shape: AreaShape::Circle(Range::Float(2.5)),
             acquisition: AreaTargetAcquisition::Enemies,
             effect: AbilityEffect::Negative(AbilityNegativeEffect::Spell(SpellNegativeEffect {
                 defense_type: Some(DefenseType::Toughness),
                 damage: Some(AbilityDamage::AtLeast(4)),
+                damage_type: None,
                 on_hit: Some([
                     Some(ApplyEffect::Condition(ApplyCondition {
                         condition: Condition::Bleeding,
@@ -1145,6 +2415,7 @@ pub const INFLICT_WOUNDS: Ability = Ability {
                     })),
                     None,
                 ]),
+                graze_behavior: SpellGrazeBehavior::Reduced,
             })),
         },
     },
@@ -1166,13 +2437,16 @@ pub const HULDRA_INFLICT_WOUNDS: Ability = Ability {
     mana_cost: 0,
     stamina_cost: 0,
     requirement: None,
+    cooldown_rounds: 0,
 
     roll: Some(AbilityRollType::Spell),
+    backlash: None,
     possible_enhancements: [None, None, None],
     target: AbilityTarget::Enemy {
         effect: AbilityNegativeEffect::Spell(SpellNegativeEffect {
             defense_type: Some(DefenseType::Toughness),
             damage: None,
+            damage_type: None,
             on_hit: Some([
                 Some(ApplyEffect::Condition(ApplyCondition {
                     condition: Condition::Bleeding,
@@ -1181,6 +2455,7 @@ pub const HULDRA_INFLICT_WOUNDS: Ability = Ability {
                 })),
                 None,
             ]),
+            graze_behavior: SpellGrazeBehavior::Reduced,
         }),
         impact_circle: None,
         reach: AbilityReach::Range(Range::Melee),
@@ -1198,163 +2473,392 @@ pub const HULDRA_INFLICT_HORRORS: Ability = Ability {
     icon: IconId::Mindblast,
     action_point_cost: 2,
     mana_cost: 0,
-    stamina_cost: 0,
+    stamina_cost: 0,
+    requirement: None,
+    cooldown_rounds: 0,
+
+    roll: Some(AbilityRollType::Spell),
+    backlash: None,
+    possible_enhancements: [None, None, None],
+    target: AbilityTarget::Enemy {
+        reach: AbilityReach::Range(Range::Ranged(22)),
+        effect: AbilityNegativeEffect::Spell(SpellNegativeEffect {
+            defense_type: Some(DefenseType::Will),
+            damage: None,
+            damage_type: None,
+            on_hit: Some([
+                Some(ApplyEffect::Condition(ApplyCondition {
+                    condition: Condition::Slowed,
+                    stacks: None,
+                    duration_rounds: Some(1),
+                })),
+                None,
+            ]),
+            graze_behavior: SpellGrazeBehavior::Reduced,
+        }),
+        impact_circle: None,
+    },
+    animation_color: PURPLE,
+    initiate_sound: Some(SoundId::ShootSpell),
+    resolve_sound: Some(SoundId::Debuff),
+    charge_fx: None,
+};
+
+pub static HULDRA_HEAL: Ability = Ability {
+    id: AbilityId::MagiHeal,
+    name: "Bless",
+    description: "",
+    icon: IconId::Heal,
+    action_point_cost: 3,
+    mana_cost: 0,
+    stamina_cost: 0,
+    requirement: None,
+    cooldown_rounds: 0,
+
+    roll: Some(AbilityRollType::Spell),
+    backlash: None,
+    target: AbilityTarget::Ally {
+        range: Range::Ranged(15),
+        effect: AbilityPositiveEffect {
+            healing: 8,
+            apply: Some([
+                Some(ApplyEffect::Condition(ApplyCondition {
+                    condition: Condition::Protected,
+                    stacks: None,
+                    duration_rounds: Some(1),
+                })),
+                None,
+            ]),
+            backfire: None,
+        },
+        pull_toward_caster: None,
+    },
+    possible_enhancements: [None, None, None],
+    animation_color: LIME,
+    initiate_sound: Some(SoundId::ShootSpell),
+    resolve_sound: Some(SoundId::Powerup),
+    charge_fx: None,
+};
+
+pub const HEAL_ENERGIZE: AbilityEnhancement = AbilityEnhancement {
+    ability_id: AbilityId::Heal,
+    name: "Energize",
+    description: "",
+    icon: IconId::Energize,
+    mana_cost: 1,
+    spell_effect: Some(SpellEnhancementEffect {
+        target_on_hit: Some([Some(ApplyEffect::GainStamina(4)), None]),
+        ..SpellEnhancementEffect::default()
+    }),
+    ..AbilityEnhancement::default()
+};
+pub const HEAL: Ability = Ability {
+    id: AbilityId::Heal,
+    name: "Heal",
+    description: "Restore target's health. Remove Bleeding and Burning.",
+    icon: IconId::Heal,
+    action_point_cost: 2,
+    mana_cost: 1,
+    stamina_cost: 0,
+    requirement: None,
+    cooldown_rounds: 0,
+
+    roll: Some(AbilityRollType::Spell),
+    backlash: None,
+    target: AbilityTarget::Ally {
+        range: Range::Float(9.5),
+        effect: AbilityPositiveEffect {
+            healing: 3,
+            apply: Some([
+                Some(ApplyEffect::ConsumeCondition {
+                    condition: Condition::Bleeding,
+                }),
+                Some(ApplyEffect::ConsumeCondition {
+                    condition: Condition::Burning,
+                }),
+            ]),
+            backfire: None,
+        },
+        pull_toward_caster: None,
+    },
+    possible_enhancements: [
+        Some(AbilityEnhancement {
+            ability_id: AbilityId::Heal,
+            name: "Reach",
+            description: "",
+            icon: IconId::Extend,
+            mana_cost: 1,
+            spell_effect: Some(SpellEnhancementEffect {
+                increased_range_tenths: 60,
+                ..SpellEnhancementEffect::default()
+            }),
+            ..AbilityEnhancement::default()
+        }),
+        // TODO add enhancement that heals over time (1 per round for 3 turns?)
+        Some(HEAL_ENERGIZE),
+        None,
+    ],
+
+    animation_color: GREEN,
+    initiate_sound: Some(SoundId::ShootSpell),
+    resolve_sound: Some(SoundId::Heal),
+    charge_fx: Some(AbilityChargeFx {
+        particle_shape: ParticleShape::Circle,
+        sound: SoundId::MechanicNoise,
+    }),
+};
+
+pub const REGENERATE: Ability = Ability {
+    id: AbilityId::Regenerate,
+    name: "Regenerate",
+    description: "Make an ally's wounds close over time",
+    // TODO: unique icon
+    icon: IconId::Heal,
+    action_point_cost: 2,
+    mana_cost: 2,
+    stamina_cost: 0,
+    requirement: None,
+    cooldown_rounds: 0,
+
+    roll: Some(AbilityRollType::Spell),
+    backlash: None,
+    target: AbilityTarget::Ally {
+        range: Range::Float(9.5),
+        effect: AbilityPositiveEffect {
+            healing: 0,
+            apply: Some([
+                Some(ApplyEffect::Condition(ApplyCondition {
+                    condition: Condition::Regenerating,
+                    stacks: Some(3),
+                    duration_rounds: None,
+                })),
+                None,
+            ]),
+            backfire: None,
+        },
+        pull_toward_caster: None,
+    },
+    possible_enhancements: [None; 3],
+
+    animation_color: GREEN,
+    initiate_sound: Some(SoundId::ShootSpell),
+    resolve_sound: Some(SoundId::Heal),
+    charge_fx: Some(AbilityChargeFx {
+        particle_shape: ParticleShape::Circle,
+        sound: SoundId::MechanicNoise,
+    }),
+};
+
+pub const HASTE: Ability = Ability {
+    id: AbilityId::Haste,
+    name: "Haste",
+    description: "Grant an ally bonus AP and movement",
+    icon: IconId::Haste,
+    action_point_cost: 2,
+    mana_cost: 2,
+    stamina_cost: 0,
+    requirement: None,
+    cooldown_rounds: 0,
+
+    roll: Some(AbilityRollType::Spell),
+    backlash: None,
+    target: AbilityTarget::Ally {
+        range: Range::Float(9.5),
+        effect: AbilityPositiveEffect {
+            healing: 0,
+            apply: Some([
+                Some(ApplyEffect::Condition(ApplyCondition {
+                    condition: Condition::Hastened,
+                    stacks: None,
+                    duration_rounds: Some(4),
+                })),
+                None,
+            ]),
+            backfire: None,
+        },
+        pull_toward_caster: None,
+    },
+    possible_enhancements: [None, None, None],
+
+    animation_color: GREEN,
+    initiate_sound: Some(SoundId::ShootSpell),
+    resolve_sound: Some(SoundId::Powerup),
+    charge_fx: Some(AbilityChargeFx {
+        particle_shape: ParticleShape::Circle,
+        sound: SoundId::MechanicNoise,
+    }),
+};
+
+pub const BLINK: Ability = Ability {
+    id: AbilityId::Blink,
+    name: "Blink",
+    description: "Instantly appear somewhere else nearby, without provoking opportunity attacks",
+    icon: IconId::Go,
+    action_point_cost: 2,
+    mana_cost: 2,
+    stamina_cost: 0,
+    requirement: None,
+    cooldown_rounds: 0,
+
+    roll: None,
+    backlash: None,
+    target: AbilityTarget::Teleport {
+        range: Range::Float(7.5),
+    },
+    possible_enhancements: [None; 3],
+
+    animation_color: PURPLE,
+    initiate_sound: None,
+    resolve_sound: Some(SoundId::Powerup),
+    charge_fx: None,
+};
+
+pub const SPIKE_TRAP: Ability = Ability {
+    id: AbilityId::SpikeTrap,
+    name: "Spike trap",
+    description: "Hide a trap that wounds the first enemy to step on it",
+    icon: IconId::CripplingShot,
+    action_point_cost: 2,
+    mana_cost: 0,
+    stamina_cost: 2,
     requirement: None,
+    cooldown_rounds: 0,
 
     roll: Some(AbilityRollType::Spell),
-    possible_enhancements: [None, None, None],
-    target: AbilityTarget::Enemy {
-        reach: AbilityReach::Range(Range::Ranged(22)),
-        effect: AbilityNegativeEffect::Spell(SpellNegativeEffect {
-            defense_type: Some(DefenseType::Will),
-            damage: None,
+    backlash: None,
+    target: AbilityTarget::Trap {
+        range: Range::Float(4.5),
+        effect: SpellNegativeEffect {
+            defense_type: Some(DefenseType::Evasion),
+            damage: Some(AbilityDamage::Static(3)),
+            damage_type: Some(DamageType::Piercing),
             on_hit: Some([
                 Some(ApplyEffect::Condition(ApplyCondition {
-                    condition: Condition::Slowed,
-                    stacks: None,
-                    duration_rounds: Some(1),
+                    condition: Condition::Bleeding,
+                    stacks: Some(3),
+                    duration_rounds: None,
                 })),
                 None,
             ]),
-        }),
-        impact_circle: None,
+            graze_behavior: SpellGrazeBehavior::Reduced,
+        },
     },
-    animation_color: PURPLE,
-    initiate_sound: Some(SoundId::ShootSpell),
-    resolve_sound: Some(SoundId::Debuff),
+    possible_enhancements: [None; 3],
+
+    animation_color: BROWN,
+    initiate_sound: None,
+    resolve_sound: None,
     charge_fx: None,
 };
 
-pub static HULDRA_HEAL: Ability = Ability {
-    id: AbilityId::MagiHeal,
-    name: "Bless",
-    description: "",
-    icon: IconId::Heal,
-    action_point_cost: 3,
-    mana_cost: 0,
+pub const THORNS: Ability = Ability {
+    id: AbilityId::Thorns,
+    name: "Thorns",
+    description: "Make melee attackers hurt themselves when they strike an ally",
+    icon: IconId::HardenedSkin,
+    action_point_cost: 2,
+    mana_cost: 2,
     stamina_cost: 0,
     requirement: None,
+    cooldown_rounds: 0,
 
     roll: Some(AbilityRollType::Spell),
+    backlash: None,
     target: AbilityTarget::Ally {
-        range: Range::Ranged(15),
+        range: Range::Float(9.5),
         effect: AbilityPositiveEffect {
-            healing: 8,
+            healing: 0,
             apply: Some([
                 Some(ApplyEffect::Condition(ApplyCondition {
-                    condition: Condition::Protected,
-                    stacks: None,
-                    duration_rounds: Some(1),
+                    condition: Condition::Thorns,
+                    stacks: Some(50),
+                    duration_rounds: Some(3),
                 })),
                 None,
             ]),
+            backfire: None,
         },
+        pull_toward_caster: None,
     },
-    possible_enhancements: [None, None, None],
-    animation_color: LIME,
+    possible_enhancements: [None; 3],
+
+    animation_color: GREEN,
     initiate_sound: Some(SoundId::ShootSpell),
     resolve_sound: Some(SoundId::Powerup),
-    charge_fx: None,
-};
-
-pub const HEAL_ENERGIZE: AbilityEnhancement = AbilityEnhancement {
-    ability_id: AbilityId::Heal,
-    name: "Energize",
-    description: "",
-    icon: IconId::Energize,
-    mana_cost: 1,
-    spell_effect: Some(SpellEnhancementEffect {
-        target_on_hit: Some([Some(ApplyEffect::GainStamina(4)), None]),
-        ..SpellEnhancementEffect::default()
+    charge_fx: Some(AbilityChargeFx {
+        particle_shape: ParticleShape::Circle,
+        sound: SoundId::MechanicNoise,
     }),
-    ..AbilityEnhancement::default()
 };
-pub const HEAL: Ability = Ability {
-    id: AbilityId::Heal,
-    name: "Heal",
-    description: "Restore target's health. Remove Bleeding and Burning.",
+
+pub const CLEANSE: Ability = Ability {
+    id: AbilityId::Cleanse,
+    name: "Cleanse",
+    description: "Rid an ally of harmful conditions",
+    // TODO: unique icon
     icon: IconId::Heal,
     action_point_cost: 2,
     mana_cost: 1,
     stamina_cost: 0,
     requirement: None,
+    cooldown_rounds: 0,
 
     roll: Some(AbilityRollType::Spell),
+    backlash: None,
     target: AbilityTarget::Ally {
         range: Range::Float(9.5),
         effect: AbilityPositiveEffect {
-            healing: 3,
+            healing: 0,
             apply: Some([
-                Some(ApplyEffect::ConsumeCondition {
-                    condition: Condition::Bleeding,
-                }),
-                Some(ApplyEffect::ConsumeCondition {
-                    condition: Condition::Burning,
-                }),
+                Some(ApplyEffect::Dispel(DispelEffect {
+                    positive: false,
+                    max_count: 2,
+                })),
+                None,
             ]),
+            backfire: None,
         },
+        pull_toward_caster: None,
     },
-    possible_enhancements: [
-        Some(AbilityEnhancement {
-            ability_id: AbilityId::Heal,
-            name: "Reach",
-            description: "",
-            icon: IconId::Extend,
-            mana_cost: 1,
-            spell_effect: Some(SpellEnhancementEffect {
-                increased_range_tenths: 60,
-                ..SpellEnhancementEffect::default()
-            }),
-            ..AbilityEnhancement::default()
-        }),
-        // TODO add enhancement that heals over time (1 per round for 3 turns?)
-        Some(HEAL_ENERGIZE),
-        None,
-    ],
+    possible_enhancements: [None; 3],
 
     animation_color: GREEN,
     initiate_sound: Some(SoundId::ShootSpell),
-    resolve_sound: Some(SoundId::Heal),
+    resolve_sound: Some(SoundId::Powerup),
     charge_fx: Some(AbilityChargeFx {
         particle_shape: ParticleShape::Circle,
         sound: SoundId::MechanicNoise,
     }),
 };
 
-pub const HASTE: Ability = Ability {
-    id: AbilityId::Haste,
-    name: "Haste",
-    description: "Grant an ally bonus AP and movement",
-    icon: IconId::Haste,
+pub const RESCUE: Ability = Ability {
+    id: AbilityId::Rescue,
+    name: "Rescue",
+    description: "Pull an ally toward you, out of harm's way",
+    icon: IconId::Go,
     action_point_cost: 2,
-    mana_cost: 2,
+    mana_cost: 1,
     stamina_cost: 0,
     requirement: None,
+    cooldown_rounds: 0,
 
     roll: Some(AbilityRollType::Spell),
+    backlash: None,
     target: AbilityTarget::Ally {
         range: Range::Float(9.5),
         effect: AbilityPositiveEffect {
             healing: 0,
-            apply: Some([
-                Some(ApplyEffect::Condition(ApplyCondition {
-                    condition: Condition::Hastened,
-                    stacks: None,
-                    duration_rounds: Some(4),
-                })),
-                None,
-            ]),
+            apply: None,
+            backfire: None,
         },
+        pull_toward_caster: Some(6),
     },
     possible_enhancements: [None, None, None],
 
-    animation_color: GREEN,
+    animation_color: BLUE,
     initiate_sound: Some(SoundId::ShootSpell),
-    resolve_sound: Some(SoundId::Powerup),
-    charge_fx: Some(AbilityChargeFx {
-        particle_shape: ParticleShape::Circle,
-        sound: SoundId::MechanicNoise,
-    }),
+    resolve_sound: Some(SoundId::Walk),
+    charge_fx: None,
 };
 
 pub const INSPIRE: Ability = Ability {
@@ -1366,10 +2870,13 @@ pub const INSPIRE: Ability = Ability {
     mana_cost: 1,
     stamina_cost: 0,
     requirement: None,
+    cooldown_rounds: 0,
 
     roll: Some(AbilityRollType::Spell),
+    backlash: None,
     possible_enhancements: [None, None, None],
     target: AbilityTarget::None {
+        summon: None,
         self_area: Some(AreaEffect {
             shape: AreaShape::Circle(Range::Float(12.5)),
             acquisition: AreaTargetAcquisition::Allies,
@@ -1383,6 +2890,7 @@ pub const INSPIRE: Ability = Ability {
                     })),
                     None,
                 ]),
+                backfire: None,
             }),
         }),
         self_effect: None,
@@ -1396,6 +2904,36 @@ pub const INSPIRE: Ability = Ability {
     }),
 };
 
+pub const COMMAND_STRIKE: Ability = Ability {
+    id: AbilityId::CommandStrike,
+    name: "Command: Strike!",
+    description: "Urge an ally into action, letting them act more this turn",
+    icon: IconId::Go,
+    action_point_cost: 2,
+    mana_cost: 0,
+    stamina_cost: 2,
+    requirement: None,
+    cooldown_rounds: 2,
+
+    roll: Some(AbilityRollType::Spell),
+    backlash: None,
+    target: AbilityTarget::Ally {
+        range: Range::Float(9.5),
+        effect: AbilityPositiveEffect {
+            healing: 0,
+            apply: Some([Some(ApplyEffect::GainActionPoints(1)), None]),
+            backfire: None,
+        },
+        pull_toward_caster: None,
+    },
+    possible_enhancements: [None; 3],
+
+    animation_color: GREEN,
+    initiate_sound: None,
+    resolve_sound: Some(SoundId::Powerup),
+    charge_fx: None,
+};
+
 pub const ENEMY_INSPIRE: Ability = Ability {
     id: AbilityId::Inspire,
     name: "Inspire",
@@ -1405,10 +2943,13 @@ pub const ENEMY_INSPIRE: Ability = Ability {
     mana_cost: 0,
     stamina_cost: 0,
     requirement: None,
+    cooldown_rounds: 0,
 
     roll: Some(AbilityRollType::Spell),
+    backlash: None,
     possible_enhancements: [None, None, None],
     target: AbilityTarget::None {
+        summon: None,
         self_area: Some(AreaEffect {
             shape: AreaShape::Circle(Range::Float(12.5)),
             acquisition: AreaTargetAcquisition::Allies,
@@ -1422,6 +2963,7 @@ pub const ENEMY_INSPIRE: Ability = Ability {
                     })),
                     None,
                 ]),
+                backfire: None,
             }),
         }),
         self_effect: None,
@@ -1441,16 +2983,20 @@ pub const HEALING_NOVA: Ability = Ability {
     mana_cost: 1,
     stamina_cost: 0,
     requirement: None,
+    cooldown_rounds: 2,
 
     roll: Some(AbilityRollType::Spell),
+    backlash: None,
     possible_enhancements: [None, None, None],
     target: AbilityTarget::None {
+        summon: None,
         self_area: Some(AreaEffect {
             shape: AreaShape::Circle(Range::Ranged(12)),
             acquisition: AreaTargetAcquisition::Allies,
             effect: AbilityEffect::Positive(AbilityPositiveEffect {
                 healing: 1,
                 apply: None,
+                backfire: None,
             }),
         }),
         self_effect: None,
@@ -1464,6 +3010,54 @@ pub const HEALING_NOVA: Ability = Ability {
     }),
 };
 
+pub const SOOTHING_AURA: Ability = Ability {
+    id: AbilityId::SoothingAura,
+    name: "Soothing aura",
+    description:
+        "Restore health to nearby allies, again at the end of each turn while |<keyword>Channeling|",
+    icon: IconId::Heal,
+    action_point_cost: 3,
+    mana_cost: 2,
+    stamina_cost: 0,
+    requirement: None,
+    cooldown_rounds: 0,
+
+    roll: Some(AbilityRollType::Spell),
+    backlash: None,
+    possible_enhancements: [None, None, None],
+    target: AbilityTarget::None {
+        summon: None,
+        self_area: Some(AreaEffect {
+            shape: AreaShape::Circle(Range::Ranged(6)),
+            acquisition: AreaTargetAcquisition::Allies,
+            effect: AbilityEffect::Positive(AbilityPositiveEffect {
+                healing: 1,
+                apply: None,
+                backfire: None,
+            }),
+        }),
+        self_effect: Some(AbilityPositiveEffect {
+            healing: 0,
+            apply: Some([
+                Some(ApplyEffect::Condition(ApplyCondition {
+                    condition: Condition::Channeling,
+                    duration_rounds: None,
+                    stacks: None,
+                })),
+                None,
+            ]),
+            backfire: None,
+        }),
+    },
+    animation_color: GREEN,
+    initiate_sound: None,
+    resolve_sound: Some(SoundId::Powerup),
+    charge_fx: Some(AbilityChargeFx {
+        particle_shape: ParticleShape::Circle,
+        sound: SoundId::MechanicNoise,
+    }),
+};
+
 pub const ENEMY_SELF_HEAL: Ability = Ability {
     id: AbilityId::SelfHeal,
     name: "Self heal",
@@ -1473,10 +3067,13 @@ pub const ENEMY_SELF_HEAL: Ability = Ability {
     mana_cost: 0,
     stamina_cost: 0,
     requirement: None,
+    cooldown_rounds: 0,
 
     roll: Some(AbilityRollType::Spell),
+    backlash: None,
     possible_enhancements: [None, None, None],
     target: AbilityTarget::None {
+        summon: None,
         self_area: None,
         self_effect: Some(AbilityPositiveEffect {
             healing: 1,
@@ -1488,6 +3085,7 @@ pub const ENEMY_SELF_HEAL: Ability = Ability {
                 })),
                 None,
             ]),
+            backfire: None,
         }),
     },
     animation_color: GREEN,
@@ -1505,10 +3103,13 @@ pub const SELF_HEAL: Ability = Ability {
     mana_cost: 1,
     stamina_cost: 0,
     requirement: None,
+    cooldown_rounds: 0,
 
     roll: Some(AbilityRollType::Spell),
+    backlash: None,
     possible_enhancements: [None, None, None],
     target: AbilityTarget::None {
+        summon: None,
         self_area: None,
         self_effect: Some(AbilityPositiveEffect {
             healing: 1,
@@ -1520,6 +3121,7 @@ pub const SELF_HEAL: Ability = Ability {
                 })),
                 None,
             ]),
+            backfire: None,
         }),
     },
     animation_color: GREEN,
@@ -1531,6 +3133,35 @@ pub const SELF_HEAL: Ability = Ability {
     }),
 };
 
+pub const SECOND_WIND: Ability = Ability {
+    id: AbilityId::SecondWind,
+    name: "Second wind",
+    description: "Dig deep and recover health. Heals more the more wounded you are",
+    icon: IconId::Heal,
+    action_point_cost: 2,
+    mana_cost: 0,
+    stamina_cost: 2,
+    requirement: None,
+    cooldown_rounds: 0,
+
+    roll: None,
+    backlash: None,
+    possible_enhancements: [None; 3],
+    target: AbilityTarget::None {
+        summon: None,
+        self_area: None,
+        self_effect: Some(AbilityPositiveEffect {
+            healing: 2,
+            apply: None,
+            backfire: None,
+        }),
+    },
+    animation_color: GREEN,
+    initiate_sound: None,
+    resolve_sound: Some(SoundId::Powerup),
+    charge_fx: None,
+};
+
 pub const HEALING_RAIN: Ability = Ability {
     id: AbilityId::HealingRain,
     name: "Healing rain",
@@ -1540,11 +3171,14 @@ pub const HEALING_RAIN: Ability = Ability {
     mana_cost: 2,
     stamina_cost: 0,
     requirement: None,
+    cooldown_rounds: 0,
 
     roll: Some(AbilityRollType::Spell),
+    backlash: None,
     possible_enhancements: [None, None, None],
     target: AbilityTarget::Area {
         range: Range::Float(15.5),
+        ground_effect: None,
         area_effect: AreaEffect {
             //shape: AreaShape::Circle(Range::Float(5.5)),
             shape: AreaShape::Line,
@@ -1552,6 +3186,7 @@ pub const HEALING_RAIN: Ability = Ability {
             effect: AbilityEffect::Positive(AbilityPositiveEffect {
                 healing: 1,
                 apply: None,
+                backfire: None,
             }),
         },
     },
@@ -1573,11 +3208,14 @@ pub const PIERCING_SHOT: Ability = Ability {
     mana_cost: 0,
     stamina_cost: 2,
     requirement: Some(EquipmentRequirement::Weapon(WeaponType::Ranged)),
+    cooldown_rounds: 0,
 
     roll: Some(AbilityRollType::RollDuringAttack(0)),
+    backlash: None,
     possible_enhancements: [None, None, None],
     target: AbilityTarget::Area {
         range: Range::Float(15.5),
+        ground_effect: None,
         area_effect: AreaEffect {
             shape: AreaShape::Line,
             acquisition: AreaTargetAcquisition::Enemies,
@@ -1595,19 +3233,6 @@ pub const PIERCING_SHOT: Ability = Ability {
     }),
 };
 
-pub const FIREBALL_REACH: AbilityEnhancement = AbilityEnhancement {
-    ability_id: AbilityId::Fireball,
-    name: "Reach",
-    description: "",
-    icon: IconId::Extend,
-    action_point_cost: 1,
-    spell_effect: Some(SpellEnhancementEffect {
-        increased_range_tenths: 100,
-        ..SpellEnhancementEffect::default()
-    }),
-    ..AbilityEnhancement::default()
-};
-
 pub const FIREBALL_MASSIVE: AbilityEnhancement = AbilityEnhancement {
     ability_id: AbilityId::Fireball,
     name: "Massive",
@@ -1639,6 +3264,19 @@ pub const FIREBALL_INFERNO: AbilityEnhancement = AbilityEnhancement {
     }),
     ..AbilityEnhancement::default()
 };
+pub const FIREBALL_CAREFUL: AbilityEnhancement = AbilityEnhancement {
+    ability_id: AbilityId::Fireball,
+    name: "Careful",
+    description: "Allies are spared by the impact",
+    // TODO: unique icon
+    icon: IconId::Radius,
+    mana_cost: 1,
+    spell_effect: Some(SpellEnhancementEffect {
+        spare_allies: true,
+        ..SpellEnhancementEffect::default()
+    }),
+    ..AbilityEnhancement::default()
+};
 pub const FIREBALL: Ability = Ability {
     id: AbilityId::Fireball,
     name: "Fireball",
@@ -1648,14 +3286,25 @@ pub const FIREBALL: Ability = Ability {
     mana_cost: 1,
     stamina_cost: 0,
     requirement: None,
+    cooldown_rounds: 0,
 
     roll: Some(AbilityRollType::Spell),
+    backlash: Some(Backlash {
+        dice_roll_at_most: 1,
+        effect: ApplyEffect::Condition(ApplyCondition {
+            condition: Condition::Dazed,
+            stacks: None,
+            duration_rounds: Some(1),
+        }),
+    }),
     target: AbilityTarget::Enemy {
         reach: AbilityReach::Range(Range::Float(12.5)),
         effect: AbilityNegativeEffect::Spell(SpellNegativeEffect {
             defense_type: Some(DefenseType::Evasion),
             damage: Some(AbilityDamage::AtLeast(4)),
+            damage_type: Some(DamageType::Fire),
             on_hit: None,
+            graze_behavior: SpellGrazeBehavior::Reduced,
         }),
         impact_circle: Some((
             Range::Float(3.5),
@@ -1663,12 +3312,14 @@ pub const FIREBALL: Ability = Ability {
             AbilityNegativeEffect::Spell(SpellNegativeEffect {
                 defense_type: Some(DefenseType::Toughness),
                 damage: Some(AbilityDamage::AtLeast(4)),
+                damage_type: Some(DamageType::Fire),
                 on_hit: None,
+                graze_behavior: SpellGrazeBehavior::Reduced,
             }),
         )),
     },
     possible_enhancements: [
-        Some(FIREBALL_REACH),
+        Some(FIREBALL_CAREFUL),
         Some(FIREBALL_MASSIVE),
         Some(FIREBALL_INFERNO),
     ],
@@ -1691,17 +3342,22 @@ pub const KILL: Ability = Ability {
     mana_cost: 0,
     stamina_cost: 0,
     requirement: None,
+    cooldown_rounds: 3,
 
     roll: Some(AbilityRollType::Spell),
+    backlash: None,
     possible_enhancements: [None; 3],
     target: AbilityTarget::None {
+        summon: None,
         self_area: Some(AreaEffect {
             shape: AreaShape::Circle(Range::Ranged(50)),
             acquisition: AreaTargetAcquisition::Enemies,
             effect: AbilityEffect::Negative(AbilityNegativeEffect::Spell(SpellNegativeEffect {
                 defense_type: None,
                 damage: Some(AbilityDamage::Static(99)),
+                damage_type: None,
                 on_hit: None,
+                graze_behavior: SpellGrazeBehavior::Reduced,
             })),
         }),
         self_effect: None,
@@ -1734,6 +3390,19 @@ pub const SEARING_LIGHT_BURN: AbilityEnhancement = AbilityEnhancement {
     }),
     ..AbilityEnhancement::default()
 };
+pub const SEARING_LIGHT_SPLASH: AbilityEnhancement = AbilityEnhancement {
+    ability_id: AbilityId::SearingLight,
+    name: "Splash",
+    description: "",
+    icon: IconId::Radius,
+    mana_cost: 2,
+    spell_effect: Some(SpellEnhancementEffect {
+        splash: true,
+        bonus_area_damage: 2,
+        ..SpellEnhancementEffect::default()
+    }),
+    ..AbilityEnhancement::default()
+};
 pub const SEARING_LIGHT: Ability = Ability {
     id: AbilityId::SearingLight,
     name: "Searing light",
@@ -1743,14 +3412,17 @@ pub const SEARING_LIGHT: Ability = Ability {
     mana_cost: 1,
     stamina_cost: 0,
     requirement: None,
+    cooldown_rounds: 0,
 
     roll: Some(AbilityRollType::Spell),
-    possible_enhancements: [Some(SEARING_LIGHT_BURN), None, None],
+    backlash: None,
+    possible_enhancements: [Some(SEARING_LIGHT_BURN), Some(SEARING_LIGHT_SPLASH), None],
     target: AbilityTarget::Enemy {
         reach: AbilityReach::Range(Range::Ranged(9)),
         effect: AbilityNegativeEffect::Spell(SpellNegativeEffect {
             defense_type: Some(DefenseType::Toughness),
             damage: Some(AbilityDamage::AtLeast(3)),
+            damage_type: None,
             on_hit: Some([
                 Some(ApplyEffect::Condition(ApplyCondition {
                     condition: Condition::Blinded,
@@ -1759,6 +3431,7 @@ pub const SEARING_LIGHT: Ability = Ability {
                 })),
                 None,
             ]),
+            graze_behavior: SpellGrazeBehavior::Reduced,
         }),
         impact_circle: None,
     },
@@ -1818,6 +3491,34 @@ pub const ARCANE_POTION: Consumable = Consumable {
     ..Consumable::default()
 };
 
+pub const ALCHEMISTS_FIRE: Consumable = Consumable {
+    name: "Alchemist's fire",
+    // TODO: unique icon
+    icon: EquipmentIconId::ExplodingArrow,
+    thrown: Some(ThrownEffect {
+        range: Range::Ranged(5),
+        area_effect: AreaEffect {
+            shape: AreaShape::Circle(Range::Float(1.5)),
+            acquisition: AreaTargetAcquisition::Everyone,
+            effect: AbilityEffect::Negative(AbilityNegativeEffect::Spell(SpellNegativeEffect {
+                defense_type: Some(DefenseType::Evasion),
+                damage: Some(AbilityDamage::Static(1)),
+                damage_type: Some(DamageType::Fire),
+                on_hit: Some([
+                    Some(ApplyEffect::Condition(ApplyCondition {
+                        condition: Condition::Burning,
+                        stacks: Some(3),
+                        duration_rounds: None,
+                    })),
+                    None,
+                ]),
+                graze_behavior: SpellGrazeBehavior::Reduced,
+            })),
+        },
+    }),
+    ..Consumable::default()
+};
+
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum PassiveSkill {
     HardenedSkin,
@@ -1828,6 +3529,12 @@ pub enum PassiveSkill {
     ThrillOfBattle,
     Honorless,
     Vigilant,
+    Cleave,
+    Guardian,
+    Berserker,
+    Momentum,
+    Counterspell,
+    Quickdraw,
 }
 
 impl PassiveSkill {
@@ -1842,6 +3549,12 @@ impl PassiveSkill {
             ThrillOfBattle => "Thrill of battle",
             Honorless => "Honorless",
             Vigilant => "Vigilant",
+            Cleave => "Cleave",
+            Guardian => "Guardian",
+            Berserker => "Berserker",
+            Momentum => "Momentum",
+            Counterspell => "Counterspell",
+            Quickdraw => "Quickdraw",
         }
     }
 
@@ -1860,6 +3573,18 @@ impl PassiveSkill {
             Honorless => IconId::RangedAttack,
             // TODO: unique icon
             Vigilant => IconId::MeleeAttack,
+            // TODO: unique icon
+            Cleave => IconId::MeleeAttack,
+            // TODO: unique icon
+            Guardian => IconId::Block,
+            // TODO: unique icon
+            Berserker => IconId::Rage,
+            // TODO: unique icon
+            Momentum => IconId::MeleeAttack,
+            // TODO: unique icon
+            Counterspell => IconId::Mindblast,
+            // TODO: unique icon
+            Quickdraw => IconId::Equip,
         }
     }
 
@@ -1873,7 +3598,13 @@ impl PassiveSkill {
             BloodRage => "|<value>+3| |<dice>| Attack, while at/below 50% |<heart>| health. Immune to |<keyword>Near-death|",
             ThrillOfBattle => "|<value>+3| |<dice>||<stat>Attack/Spell|, while adjacent to more than one enemy. Immune to Flanked.",
             Honorless => "Attacks deal |<value>+1| damage against Flanked targets",
-            Vigilant => "Can opportunity attack an adjacent enemy even if you are not engaging them"
+            Vigilant => "Can opportunity attack an adjacent enemy even if you are not engaging them",
+            Cleave => "Melee hits also deal |<value>50%| of the damage to another enemy adjacent to the target",
+            Guardian => "When an adjacent ally is attacked by an enemy that is also adjacent to you, you may take the attack instead (|<value>1| AP, requires a shield)",
+            Berserker => "|<value>+1| AP per turn. Can't react when attacked, and takes |<value>+1| damage from attacks",
+            Momentum => "Attacks deal |<value>+1| damage for each hit you've landed earlier this turn. A graze resets the count",
+            Counterspell => "When an enemy within range and line of sight casts a spell, you may try to interrupt it (|<value>1| AP, |<value>2| |<mana>| mana). Your spell roll must beat the caster's",
+            Quickdraw => "The first equipment change each turn costs |<value>0| AP",
         }
     }
 
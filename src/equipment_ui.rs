@@ -32,7 +32,8 @@ use crate::{
     character_sheet::MoneyText,
     core::{
         ApplyEffect, ArmorPiece, ArrowStack, AttackHitEffect, Character, Consumable,
-        EquipmentEntry, EquipmentSlotRole, HandType, Shield, Weapon, WeaponGrip, WeaponRange,
+        EquipmentEntry, EquipmentSlotRole, HandType, Position, Shield, Weapon, WeaponGrip,
+        WeaponRange,
     },
     drawing::{draw_dashed_line, draw_dashed_rectangle_lines},
     sounds::{SoundId, SoundPlayer},
@@ -88,6 +89,9 @@ fn consumable_tooltip(consumable: &Consumable) -> Tooltip {
         t.technical_description.push("Self:".to_string());
         describe_apply_effect(apply_effect, &mut t);
     }
+    if let Some(thrown) = consumable.thrown {
+        describe_area_effect(Some(thrown.range), thrown.area_effect, &mut t);
+    }
     //lines.push("<Right-click to use>".to_string());
     if consumable.weight > 0 {
         t.technical_description
@@ -100,20 +104,53 @@ fn consumable_tooltip(consumable: &Consumable) -> Tooltip {
 fn weapon_tooltip(weapon: &Weapon) -> Tooltip {
     let mut t = Tooltip::new(weapon.name);
     t.technical_description.push(format!(
-        "|<sword>| |<value>{}|  ({} AP)",
-        weapon.damage, weapon.action_point_cost
+        "|<sword>| |<value>{}| {}  ({} AP)",
+        weapon.damage,
+        weapon.damage_type.name(),
+        weapon.action_point_cost
+    ));
+
+    t.technical_description.push(format!(
+        "Crit: +{}% damage |<faded>({} or higher)|",
+        weapon.crit_multiplier_percent, weapon.crit_threshold
     ));
 
     if weapon.grip == WeaponGrip::TwoHanded {
         t.technical_description.push("Two-handed".to_string());
     }
 
+    if let Some(durability) = weapon.durability {
+        t.technical_description
+            .push(format!("|<faded>Max durability: {}|", durability));
+    }
+
     if weapon.range != WeaponRange::Melee {
         t.technical_description
             .push(format!("Range: {}", weapon.range));
     }
-    if let Some(effect) = weapon.on_true_hit {
-        t.technical_description.push(format!("[true hit] {effect}"));
+    if let Some(range) = weapon.min_range.impossible {
+        t.technical_description
+            .push(format!("Can't attack within: {}", range));
+    }
+    if let Some(range) = weapon.min_range.disadvantage {
+        t.technical_description
+            .push(format!("Disadvantage within: {}", range));
+    }
+    if weapon.lifesteal_percent > 0 {
+        t.technical_description.push(format!(
+            "Lifesteal: heals for {}% of the damage dealt",
+            weapon.lifesteal_percent
+        ));
+    }
+    if let Some(range) = weapon.effective_range {
+        t.technical_description
+            .push(format!("Disadvantage beyond: {}", range));
+    }
+    for (effect, label) in [(weapon.on_true_hit, "true hit"), (weapon.on_crit, "crit")] {
+        let Some(effect) = effect else {
+            continue;
+        };
+        t.technical_description.push(format!("[{label}] {effect}"));
         if let AttackHitEffect::Apply(apply_effect) = effect {
             match apply_effect {
                 ApplyEffect::Condition(apply_condition) => {
@@ -134,6 +171,14 @@ fn weapon_tooltip(weapon: &Weapon) -> Tooltip {
         t.technical_description
             .push(format!("Unlocks: |<keyword>{}|", enhancement.name));
     }
+    if let Some(skill) = weapon.grants_passive {
+        t.technical_description
+            .push(format!("Grants: |<keyword>{}|", skill.name()));
+    }
+    if weapon.thrown {
+        t.technical_description
+            .push("Thrown (leaves the hand when attacking)".to_string());
+    }
     t.technical_description
         .push(format!("|<faded>Weight: {}|", weapon.weight));
 
@@ -168,6 +213,10 @@ fn armor_tooltip(armor: &ArmorPiece) -> Tooltip {
     let mut t = Tooltip::new(armor.name);
     t.technical_description
         .push(format!("|<value>{}| armor", armor.protection));
+    for (damage_type, resistance) in armor.resistances {
+        t.technical_description
+            .push(format!("|<value>{}| vs {}", resistance, damage_type.name()));
+    }
     if let Some(limit) = armor.limit_evasion_from_agi {
         t.technical_description
             .push(format!("Max {} evasion from agi", limit));
@@ -191,6 +240,7 @@ pub struct EquipmentDrag {
 pub struct EquipmentConsumption {
     pub equipment_idx: usize,
     pub consumable: Consumable,
+    pub target: Option<Position>,
 }
 
 pub struct EquipmentSection {
@@ -345,7 +395,25 @@ impl EquipmentSection {
                 .borrow_mut()
                 .content = self.character.equipment(*role).map(|entry| {
                 let texture = self.equipment_icons[&entry.icon()].clone();
-                EquipmentSlotContent::new(texture, entry)
+                let mut content = EquipmentSlotContent::new(texture, entry);
+                if let Some(hand) = role.hand_type() {
+                    if let Some((remaining, max)) = self.character.weapon_durability(hand) {
+                        content.is_broken = remaining == 0;
+                        let line = if content.is_broken {
+                            "|<value>Broken|: -1 damage, no special effect".to_string()
+                        } else {
+                            format!("Durability: {}/{}", remaining, max)
+                        };
+                        content.tooltip.technical_description.push(line);
+                        if self.include_stash && remaining < max {
+                            content
+                                .tooltip
+                                .technical_description
+                                .push("|<faded>Right-click to repair|".to_string());
+                        }
+                    }
+                }
+                content
             });
         }
 
@@ -408,6 +476,7 @@ impl EquipmentSection {
 
         let previous_requested_consumption = requested_consumption;
         let previous_drag = drag;
+        let mut repaired_weapon = false;
 
         let mouse_pos = (mouse_x, mouse_y);
 
@@ -453,11 +522,23 @@ impl EquipmentSection {
 
             if is_hovered {
                 if is_mouse_button_pressed(MouseButton::Right) {
+                    if let Some(hand) = slot.role().hand_type().filter(|_| self.include_stash) {
+                        if self
+                            .character
+                            .weapon_durability(hand)
+                            .is_some_and(|(remaining, max)| remaining < max)
+                        {
+                            // Between fights, there's time to repair weapons
+                            self.character.repair_weapon(hand);
+                            repaired_weapon = true;
+                        }
+                    }
                     if let Some(content) = &slot.content {
                         if let EquipmentEntry::Consumable(consumable) = content.equipment {
                             requested_consumption = Some(EquipmentConsumption {
                                 equipment_idx: idx,
                                 consumable,
+                                target: None,
                             });
                         }
                     }
@@ -558,6 +639,11 @@ impl EquipmentSection {
             }
         }
 
+        if repaired_weapon {
+            self.sound_player.play(SoundId::DropEquipment);
+            self.repopulate_character_equipment();
+        }
+
         let changed =
             drag != previous_drag || requested_consumption != previous_requested_consumption;
 
@@ -882,6 +968,7 @@ pub struct EquipmentSlotContent {
     pub equipment: EquipmentEntry,
     pub texture: Texture2D,
     tooltip: Tooltip,
+    is_broken: bool,
 }
 
 impl EquipmentSlotContent {
@@ -890,6 +977,7 @@ impl EquipmentSlotContent {
             texture,
             tooltip: equipment_tooltip(&equipment),
             equipment,
+            is_broken: false,
         }
     }
 }
@@ -945,6 +1033,10 @@ impl Drawable for EquipmentSlot {
         if let Some(content) = &self.content {
             draw_texture_ex(&content.texture, x, y, WHITE, params);
 
+            if content.is_broken {
+                draw_rectangle_lines(x, y, self.size.0, self.size.1, 2.0, RED);
+            }
+
             let quantity = match content.equipment {
                 EquipmentEntry::Arrows(arrow_stack) => Some(arrow_stack.quantity),
                 _ => None,
@@ -27,13 +27,14 @@ use crate::{
     character_sheet::{CharacterSheet, CHARACTER_SHEET_BG_COLOR},
     conditions_ui::ConditionsList,
     core::{
-        distance_between, predict_ability, predict_attack, Ability, AbilityAreaOutcome,
-        AbilityEnhancement, AbilityId, AbilityResolvedEvent, AbilityRollType, AbilityTarget,
-        AbilityTargetOutcome, Action, ActionReach, ActionTarget, ApplyEffect, AreaShape,
-        AttackAction, AttackEnhancement, AttackEnhancementEffect, AttackOutcome, AttackedEvent,
-        BaseAction, Character, CharacterId, Characters, Condition, CoreGame, DamageSource,
-        GameEvent, Goodness, HandType, HitType, MovementType, OnAttackedReaction, OnHitReaction,
-        Position, TargetPrediction,
+        can_move_into_melee, distance_between, predict_ability, predict_attack,
+        within_range_squared, Ability, AbilityAreaOutcome, AbilityEnhancement, AbilityId,
+        AbilityReach, AbilityResolvedEvent, AbilityRollType, AbilityTarget, AbilityTargetOutcome,
+        Action, ActionReach, ActionTarget, ApplyEffect, AreaShape, AttackAction, AttackEnhancement,
+        AttackEnhancementEffect, AttackOutcome, AttackedEvent, BaseAction, Character, CharacterId,
+        Characters, Condition, Consumable, CoreGame, DamageSource, GameEvent, Goodness, HandType,
+        HitType, MovementEnhancement, MovementType, OnAttackedReaction, OnHitReaction, Position,
+        TargetPrediction,
     },
     equipment_ui::{EquipmentConsumption, EquipmentDrag},
     game_ui_components::{
@@ -85,6 +86,24 @@ pub enum UiState {
         victim: CharacterId,
         selected: bool,
     },
+    ReactingToOverwatch {
+        reactor: CharacterId,
+        target: CharacterId,
+        movement: ((i32, i32), (i32, i32)),
+        selected: bool,
+    },
+    ReactingToIntercept {
+        reactor: CharacterId,
+        attacker: CharacterId,
+        victim: CharacterId,
+        selected: bool,
+    },
+    ReactingToCounterspell {
+        reactor: CharacterId,
+        caster: CharacterId,
+        ability: Ability,
+        selected: bool,
+    },
     Idle,
 }
 
@@ -111,6 +130,10 @@ impl UiState {
                     .map(|id| ActionTarget::Character(id, None))
                     .unwrap_or(ActionTarget::None),
                 ConfiguredAction::UseAbility { target, .. } => target.clone(),
+                ConfiguredAction::UseConsumable(Some(consumption)) => consumption
+                    .target
+                    .map(ActionTarget::Position)
+                    .unwrap_or(ActionTarget::None),
                 _ => ActionTarget::None,
             },
             _ => ActionTarget::None,
@@ -128,6 +151,15 @@ impl UiState {
                     };
                 }
                 ConfiguredAction::UseAbility { target, .. } => *target = new_target,
+                ConfiguredAction::UseConsumable(Some(consumption))
+                    if consumption.consumable.thrown.is_some() =>
+                {
+                    consumption.target = match new_target {
+                        ActionTarget::Position(pos) => Some(pos),
+                        ActionTarget::None => None,
+                        _ => panic!(),
+                    };
+                }
 
                 action => panic!("Action has no target: {:?}", action),
             },
@@ -152,11 +184,14 @@ pub enum ConfiguredAction {
         // Including the actor's current location, going all the way to the destination. Each pos is annotated with "total dist from start"
         selected_movement_path: Vec<(f32, Position)>,
         cost: u32,
+        selected_enhancements: Vec<MovementEnhancement>,
     },
     ChangeEquipment {
         drag: Rc<RefCell<Option<EquipmentDrag>>>,
     },
     UseConsumable(Option<EquipmentConsumption>),
+    DelayTurn,
+    GuardStance,
 }
 
 const OUT_OF_REACH: &str = "Out of reach";
@@ -169,7 +204,11 @@ impl ConfiguredAction {
             ConfiguredAction::UseAbility { target, .. } => !matches!(target, ActionTarget::None),
             ConfiguredAction::Move { .. } => false,
             ConfiguredAction::ChangeEquipment { .. } => false,
-            ConfiguredAction::UseConsumable(..) => false,
+            ConfiguredAction::UseConsumable(consumption) => {
+                consumption.is_some_and(|c| c.target.is_some())
+            }
+            ConfiguredAction::DelayTurn => false,
+            ConfiguredAction::GuardStance => false,
         }
     }
 
@@ -225,6 +264,26 @@ impl ConfiguredAction {
                     }
                     let target_char = characters.get(*target_id);
 
+                    let is_engage = matches!(
+                        ability.target,
+                        AbilityTarget::Enemy {
+                            reach: AbilityReach::MoveIntoMelee(..),
+                            ..
+                        }
+                    );
+                    if is_engage
+                        && can_move_into_melee(
+                            pathfind_grid,
+                            relevant_character,
+                            target_char,
+                            *ability,
+                            selected_enhancements,
+                        )
+                        .is_none()
+                    {
+                        return Some(OUT_OF_REACH);
+                    }
+
                     if relevant_character.reaches_with_ability(
                         *ability,
                         selected_enhancements,
@@ -243,7 +302,12 @@ impl ConfiguredAction {
                 }
 
                 ActionTarget::Position(target_pos) => {
-                    assert!(matches!(ability.target, AbilityTarget::Area { .. }));
+                    assert!(matches!(
+                        ability.target,
+                        AbilityTarget::Area { .. }
+                            | AbilityTarget::Teleport { .. }
+                            | AbilityTarget::Trap { .. }
+                    ));
                     if relevant_character.reaches_with_ability(
                         *ability,
                         selected_enhancements,
@@ -260,6 +324,8 @@ impl ConfiguredAction {
                     AbilityTarget::Enemy { .. } => Some("Select an enemy"),
                     AbilityTarget::Ally { .. } => Some("Select an ally"),
                     AbilityTarget::Area { .. } => Some("Select an area"),
+                    AbilityTarget::Teleport { .. } => Some("Select a destination"),
+                    AbilityTarget::Trap { .. } => Some("Select a position"),
                 },
             },
 
@@ -288,13 +354,41 @@ impl ConfiguredAction {
                 }
             }
 
-            ConfiguredAction::UseConsumable(consumable) => {
-                if consumable.is_some() {
-                    None
-                } else {
-                    Some("Select a consumable")
-                }
-            }
+            ConfiguredAction::UseConsumable(consumption) => match consumption {
+                Some(EquipmentConsumption {
+                    consumable:
+                        Consumable {
+                            thrown: Some(thrown),
+                            ..
+                        },
+                    target,
+                    ..
+                }) => match target {
+                    Some(target_pos) => {
+                        if within_range_squared(
+                            thrown.range.squared(),
+                            relevant_character.pos(),
+                            *target_pos,
+                        ) {
+                            if pathfind_grid
+                                .obstructed_line_of_sight(relevant_character.pos(), *target_pos)
+                            {
+                                Some(NO_LINE_OF_SIGHT)
+                            } else {
+                                None
+                            }
+                        } else {
+                            Some(OUT_OF_REACH)
+                        }
+                    }
+                    None => Some("Select an area"),
+                },
+                Some(_) => None,
+                None => Some("Select a consumable"),
+            },
+
+            ConfiguredAction::DelayTurn => None,
+            ConfiguredAction::GuardStance => None,
         }
     }
 
@@ -313,11 +407,14 @@ impl ConfiguredAction {
             BaseAction::Move => Some(Self::Move {
                 cost: 0,
                 selected_movement_path: Default::default(),
+                selected_enhancements: vec![],
             }),
             BaseAction::ChangeEquipment => Some(Self::ChangeEquipment {
                 drag: Rc::new(RefCell::new(None)),
             }),
             BaseAction::UseConsumable => Some(Self::UseConsumable(None)),
+            BaseAction::DelayTurn => Some(Self::DelayTurn),
+            BaseAction::GuardStance => Some(Self::GuardStance),
         }
     }
 
@@ -328,6 +425,8 @@ impl ConfiguredAction {
             ConfiguredAction::Move { .. } => BaseAction::Move,
             ConfiguredAction::ChangeEquipment { .. } => BaseAction::ChangeEquipment,
             ConfiguredAction::UseConsumable { .. } => BaseAction::UseConsumable,
+            ConfiguredAction::DelayTurn => BaseAction::DelayTurn,
+            ConfiguredAction::GuardStance => BaseAction::GuardStance,
         }
     }
 
@@ -363,6 +462,8 @@ impl ConfiguredAction {
             ConfiguredAction::Move { cost: ap_cost, .. } => *ap_cost,
             ConfiguredAction::ChangeEquipment { .. } => 1,
             ConfiguredAction::UseConsumable { .. } => 1,
+            ConfiguredAction::DelayTurn => 0,
+            ConfiguredAction::GuardStance => 0,
         }
     }
 
@@ -508,6 +609,10 @@ pub struct UserInterface {
 
     faster_movement: Rc<Cell<bool>>,
     settings: Container,
+
+    // Needed for building UI for characters that are added mid-fight (summons)
+    ui_resources: UiResources,
+    next_character_ui_button_id: u32,
 }
 
 impl UserInterface {
@@ -523,12 +628,14 @@ impl UserInterface {
 
         let event_queue = Rc::new(RefCell::new(vec![]));
 
+        let mut next_character_ui_button_id = 1;
         let character_uis = build_character_uis(
             &ui_resources.equipment_icons,
             &ui_resources.icons,
             &event_queue,
             &resources.simple_font,
             characters.iter(),
+            &mut next_character_ui_button_id,
             resources.status_textures.clone(),
             sound_player.clone(),
         );
@@ -591,7 +698,7 @@ impl UserInterface {
         let activity_popup = ActivityPopup::new(
             resources.simple_font.clone(),
             ui_state.clone(),
-            ui_resources.icons,
+            ui_resources.icons.clone(),
             characters.clone(),
             active_character_id,
             sound_player.clone(),
@@ -636,6 +743,8 @@ impl UserInterface {
             status_textures: resources.status_textures,
             faster_movement,
             settings,
+            ui_resources,
+            next_character_ui_button_id,
         }
     }
 
@@ -746,6 +855,7 @@ impl UserInterface {
             if selected_character_id != self.active_character_id
                 && matches!(*self.state.borrow(), UiState::ChoosingAction)
                 && !new_selected_char.has_taken_a_turn_this_round.get()
+                && new_selected_char.is_part_of_active_group.get()
             {
                 if player_chose.is_some() {
                     println!(
@@ -859,12 +969,10 @@ impl UserInterface {
         }
 
         if let Some(new_selected_player_char) = outcome.tried_switching_selected_player_char {
+            let new_selected_char = self.characters.get(new_selected_player_char);
             if matches!(*self.state.borrow(), UiState::ChoosingAction)
-                && !self
-                    .characters
-                    .get(new_selected_player_char)
-                    .has_taken_a_turn_this_round
-                    .get()
+                && !new_selected_char.has_taken_a_turn_this_round.get()
+                && new_selected_char.is_part_of_active_group.get()
             {
                 self.sound_player.play(SoundId::ClickButton);
                 return Some(PlayerChose::SwitchTo(new_selected_player_char));
@@ -880,6 +988,7 @@ impl UserInterface {
                     let UiState::ConfiguringAction(ConfiguredAction::Move {
                         selected_movement_path,
                         cost,
+                        selected_enhancements,
                     }) = &*self.state.borrow()
                     else {
                         unreachable!()
@@ -895,6 +1004,7 @@ impl UserInterface {
                         total_distance,
                         positions,
                         extra_cost: *cost,
+                        enhancements: selected_enhancements.clone(),
                     })));
                 }
                 self.activity_popup.on_new_movement_ap_cost();
@@ -932,6 +1042,23 @@ impl UserInterface {
         player_chose
     }
 
+    // Extra free movement from the selected movement enhancements (e.g. Sprint)
+    fn movement_bonus_distance(&self) -> f32 {
+        match &*self.state.borrow() {
+            UiState::ConfiguringAction(ConfiguredAction::Move {
+                selected_enhancements,
+                ..
+            }) => {
+                let character = self.characters.get(self.active_character_id);
+                selected_enhancements
+                    .iter()
+                    .map(|enhancement| character.movement_enhancement_distance(enhancement))
+                    .sum()
+            }
+            _ => 0.0,
+        }
+    }
+
     fn refresh_movement_state(&mut self) {
         if let UiState::ConfiguringAction(ConfiguredAction::Move {
             selected_movement_path,
@@ -1042,6 +1169,32 @@ impl UserInterface {
                         character_id: *target_id,
                         prediction: TargetPrediction::from(prediction),
                     });
+                let mut breakdown = CoreGame::explain_attack(
+                    self.active_character(),
+                    attack.hand,
+                    target_char,
+                    &selected_enhancement_effects,
+                    None,
+                );
+                let (hit_chance, worst_case) = CoreGame::hit_chance_range(
+                    self.active_character(),
+                    attack.hand,
+                    target_char,
+                    &selected_enhancement_effects,
+                );
+                breakdown.push((
+                    "Hit chance".to_string(),
+                    Goodness::Neutral,
+                    format!("{:.0}%", hit_chance * 100.0),
+                ));
+                if let Some((reaction, chance)) = worst_case {
+                    breakdown.push((
+                        format!("If they {}", reaction.name.to_lowercase()),
+                        Goodness::Bad,
+                        format!("{:.0}%", chance * 100.0),
+                    ));
+                }
+                self.game_grid.set_attack_breakdown(*target_id, breakdown);
 
                 //self.target_ui.set_action(header, details, true);
             }
@@ -1330,6 +1483,18 @@ impl UserInterface {
                 is_reacting = Some(*reactor);
             }
 
+            UiState::ReactingToOverwatch { reactor, .. } => {
+                is_reacting = Some(*reactor);
+            }
+
+            UiState::ReactingToIntercept { reactor, .. } => {
+                is_reacting = Some(*reactor);
+            }
+
+            UiState::ReactingToCounterspell { reactor, .. } => {
+                is_reacting = Some(*reactor);
+            }
+
             UiState::ChoosingAction => {
                 /*
                 self.target_ui
@@ -1369,7 +1534,8 @@ impl UserInterface {
         self.refresh_target_state();
         self.refresh_movement_state();
 
-        self.game_grid.update_move_speed(self.active_character_id);
+        self.game_grid
+            .update_move_speed(self.active_character_id, self.movement_bonus_distance());
 
         self.refresh_selected_action_button();
         self.target_ui.rebuild_character_ui();
@@ -1436,8 +1602,13 @@ impl UserInterface {
                 //  .set_action("Use consumable".to_string(), vec![], false);
 
                 if let Some(consumption) = consumption {
+                    let verb = if consumption.consumable.thrown.is_some() {
+                        "Throw"
+                    } else {
+                        "Use"
+                    };
                     self.activity_popup.additional_line =
-                        Some(format!("Use {}", consumption.consumable.name));
+                        Some(format!("{} {}", verb, consumption.consumable.name));
                 } else {
                     self.activity_popup.additional_line = Some(
                         "Select a consumable in your inventory by right-clicking it".to_string(),
@@ -1482,6 +1653,66 @@ impl UserInterface {
 
                 self.animation_stopwatch.set_to_at_least(0.4);
             }
+            GameEvent::CharacterIntercepted { protector, victim } => {
+                let protector = self.characters.get(protector);
+                self.log.add(format!(
+                    "{} stepped in to protect {}",
+                    protector.name,
+                    self.characters.get(victim).name
+                ));
+                self.game_grid.add_text_effect(
+                    protector.pos(),
+                    0.0,
+                    0.5,
+                    None,
+                    "!".to_string(),
+                    TextEffectStyle::ReactionExclamation,
+                );
+
+                self.animation_stopwatch.set_to_at_least(0.4);
+            }
+            GameEvent::CharacterCounterspelled {
+                reactor,
+                caster,
+                ability_name,
+                success,
+                detail_lines,
+            } => {
+                let reactor = self.characters.get(reactor);
+                let caster = self.characters.get(caster);
+                let outcome = if success {
+                    "countered"
+                } else {
+                    "failed to counter"
+                };
+                self.log.add_with_details(
+                    format!(
+                        "{} {} {}'s {}",
+                        reactor.name, outcome, caster.name, ability_name
+                    ),
+                    &detail_lines,
+                );
+                self.game_grid.add_text_effect(
+                    reactor.pos(),
+                    0.0,
+                    0.5,
+                    None,
+                    "!".to_string(),
+                    TextEffectStyle::ReactionExclamation,
+                );
+                if success {
+                    self.game_grid.add_text_effect(
+                        caster.pos(),
+                        0.3,
+                        1.5,
+                        None,
+                        "Countered".to_string(),
+                        TextEffectStyle::HostileEffect,
+                    );
+                }
+
+                self.animation_stopwatch.set_to_at_least(0.6);
+            }
             GameEvent::CharacterReactedWithOpportunityAttack { reactor } => {
                 let reactor = self.characters.get(reactor);
                 self.log.add("Opportunity attack:".to_string());
@@ -1496,6 +1727,24 @@ impl UserInterface {
 
                 self.animation_stopwatch.set_to_at_least(0.4);
             }
+            GameEvent::CharacterReactedWithOverwatch { reactor, target } => {
+                let reactor = self.characters.get(reactor);
+                self.log.add(format!(
+                    "Overwatch ({} spotted {}):",
+                    reactor.name,
+                    self.characters.get(target).name
+                ));
+                self.game_grid.add_text_effect(
+                    reactor.pos(),
+                    0.0,
+                    0.5,
+                    None,
+                    "!".to_string(),
+                    TextEffectStyle::ReactionExclamation,
+                );
+
+                self.animation_stopwatch.set_to_at_least(0.4);
+            }
             GameEvent::CharacterReactedToHit {
                 main_line,
                 detail_lines,
@@ -1617,6 +1866,8 @@ impl UserInterface {
                 area_outcome,
                 ability,
                 mut detail_lines,
+                cast_index,
+                ..
             }) => {
                 if let Some(sound_id) = ability.initiate_sound {
                     self.sound_player.stop(sound_id);
@@ -1633,6 +1884,9 @@ impl UserInterface {
                     let target_name = self.characters.get(*target_id).name;
                     line.push_str(&format!(" on |{}|", target_name));
                 }
+                if cast_index > 0 {
+                    line.push_str(" again");
+                }
 
                 let mut attacks = vec![];
 
@@ -1733,6 +1987,7 @@ impl UserInterface {
                 user,
                 consumable,
                 detail_lines,
+                area_outcome,
             } => {
                 self.sound_player.play(SoundId::Powerup);
                 self.log.add_with_details(
@@ -1743,6 +1998,15 @@ impl UserInterface {
                     ),
                     &detail_lines,
                 );
+
+                if let Some(AbilityAreaOutcome {
+                    center,
+                    targets,
+                    shape,
+                }) = &area_outcome
+                {
+                    self.add_effects_for_area_outcomes(0.0, RED, center, Some(*shape), targets);
+                }
             }
             GameEvent::CharactersDying { characters } => {
                 let duration = 0.5;
@@ -1792,6 +2056,7 @@ impl UserInterface {
             }
             GameEvent::NewActiveCharacter { new_active } => {
                 let was_players_turn = self.active_character().player_controlled();
+                self.rebuild_character_ui_if_actions_changed(new_active);
                 self.set_new_active_character_id(new_active);
                 if self.active_character().player_controlled() {
                     if !was_players_turn {
@@ -1853,7 +2118,11 @@ impl UserInterface {
 
                 self.game_grid
                     .set_character_motion(character, from, to, duration, movement_type);
-                if movement_type != MovementType::KnockedBack && step_idx % 2 == 0 {
+                let is_walking = !matches!(
+                    movement_type,
+                    MovementType::KnockedBack | MovementType::Teleported
+                );
+                if is_walking && step_idx % 2 == 0 {
                     self.sound_player.play(SoundId::Walk);
                 }
                 self.animation_stopwatch.set_to_at_least(duration);
@@ -1910,6 +2179,279 @@ impl UserInterface {
                     TextEffectStyle::HostileEffect,
                 );
             }
+            GameEvent::ArrowsDepleted { character } => {
+                let character = self.characters.get(character);
+                self.log
+                    .add(format!("{} ran out of special arrows", character.name));
+                self.game_grid.add_text_effect(
+                    character.pos(),
+                    0.0,
+                    2.0,
+                    None,
+                    "Out of arrows".to_string(),
+                    TextEffectStyle::HostileEffect,
+                );
+            }
+            GameEvent::ChannelBroken { character, ability } => {
+                let character = self.characters.get(character);
+                self.log.add(format!(
+                    "{} stopped channeling {}",
+                    character.name, ability.name
+                ));
+                self.game_grid.add_text_effect(
+                    character.pos(),
+                    0.0,
+                    2.0,
+                    None,
+                    "Channel broken".to_string(),
+                    TextEffectStyle::HostileEffect,
+                );
+            }
+            GameEvent::CharacterGainedHealth {
+                character,
+                amount,
+                source,
+            } => {
+                let character = self.characters.get(character);
+                self.log.add(format!(
+                    "|{}| gained {} health from {}",
+                    character.name,
+                    amount,
+                    source.name()
+                ));
+                self.game_grid.add_text_effect(
+                    character.pos(),
+                    0.0,
+                    1.5,
+                    None,
+                    format!("+{}", amount),
+                    TextEffectStyle::Healing,
+                );
+            }
+            GameEvent::CharacterGainedShield { character, amount } => {
+                let character = self.characters.get(character);
+                self.log
+                    .add(format!("{} gained a shield ({})", character.name, amount));
+                self.game_grid.add_text_effect(
+                    character.pos(),
+                    0.0,
+                    2.0,
+                    None,
+                    format!("+{} shield", amount),
+                    TextEffectStyle::FriendlyEffect,
+                );
+            }
+            GameEvent::ShieldAbsorbedDamage { character, amount } => {
+                let character = self.characters.get(character);
+                self.log.add(format!(
+                    "{}'s shield absorbed {} damage",
+                    character.name, amount
+                ));
+                self.game_grid.add_text_effect(
+                    character.pos(),
+                    0.0,
+                    2.0,
+                    None,
+                    format!("Absorbed {}", amount),
+                    TextEffectStyle::FriendlyEffect,
+                );
+            }
+            GameEvent::CharacterRevealed { character } => {
+                let character = self.characters.get(character);
+                self.log.add(format!("{} was revealed", character.name));
+                self.game_grid.add_text_effect(
+                    character.pos(),
+                    0.0,
+                    2.0,
+                    None,
+                    "Revealed".to_string(),
+                    TextEffectStyle::HostileEffect,
+                );
+            }
+            GameEvent::ConditionsDispelled {
+                character,
+                conditions,
+            } => {
+                let character = self.characters.get(character);
+                let style = if conditions[0].is_positive() {
+                    TextEffectStyle::HostileEffect
+                } else {
+                    TextEffectStyle::FriendlyEffect
+                };
+                self.game_grid.add_text_effect(
+                    character.pos(),
+                    0.0,
+                    2.0,
+                    None,
+                    format!("Dispelled {}", conditions.len()),
+                    style,
+                );
+            }
+            GameEvent::CharacterDowned { character } => {
+                let character = self.characters.get(character);
+                self.log.add(format!("|{}| was downed", character.name));
+                self.game_grid.add_text_effect(
+                    character.pos(),
+                    0.0,
+                    2.0,
+                    None,
+                    "Downed".to_string(),
+                    TextEffectStyle::HostileEffect,
+                );
+            }
+            GameEvent::CharacterRevived { character } => {
+                let character = self.characters.get(character);
+                self.log.add(format!("|{}| was revived", character.name));
+                self.game_grid.add_text_effect(
+                    character.pos(),
+                    0.0,
+                    2.0,
+                    None,
+                    "Revived".to_string(),
+                    TextEffectStyle::FriendlyEffect,
+                );
+            }
+            GameEvent::CharacterSummoned {
+                character,
+                summoner,
+            } => {
+                self.log.add(format!(
+                    "|{}| summoned |{}|",
+                    self.characters.get(summoner).name,
+                    character.name
+                ));
+
+                self.characters
+                    .insert_after(Rc::clone(&character), summoner);
+                self.activity_popup
+                    .add_character(Rc::clone(&character), summoner);
+                self.game_grid
+                    .characters
+                    .insert(character.id(), Rc::clone(&character));
+
+                let portrait_texture =
+                    self.ui_resources.portrait_textures[&character.portrait].clone();
+                self.top_character_portraits.add_character(
+                    &character,
+                    self.simple_font.clone(),
+                    portrait_texture.clone(),
+                );
+
+                if character.player_controlled() {
+                    self.player_portraits.add_character(
+                        &character,
+                        portrait_texture,
+                        self.status_textures.clone(),
+                    );
+                    let character_ui = build_character_ui(
+                        &self.ui_resources.equipment_icons,
+                        &self.ui_resources.icons,
+                        &self.event_queue,
+                        &self.simple_font,
+                        &character,
+                        &mut self.next_character_ui_button_id,
+                        self.status_textures.clone(),
+                        Rc::new(RefCell::new((100.0, 100.0))),
+                        self.sound_player.clone(),
+                    );
+                    self.character_uis.insert(character.id(), character_ui);
+                }
+
+                self.game_grid.add_text_effect(
+                    character.pos(),
+                    0.0,
+                    1.5,
+                    None,
+                    "Summoned".to_string(),
+                    TextEffectStyle::FriendlyEffect,
+                );
+            }
+            GameEvent::LootDropped {
+                position,
+                money,
+                deposited,
+                left_on_ground,
+            } => {
+                if money > 0 {
+                    self.log.add(format!("The party looted {} gold", money));
+                    self.game_grid.add_text_effect(
+                        position,
+                        0.0,
+                        2.0,
+                        None,
+                        format!("+{} gold", money),
+                        TextEffectStyle::FriendlyEffect,
+                    );
+                }
+                for entry in deposited {
+                    self.log
+                        .add(format!("{} was put in the stash", entry.name()));
+                }
+                if !left_on_ground.is_empty() {
+                    let names: Vec<&str> =
+                        left_on_ground.iter().map(|entry| entry.name()).collect();
+                    self.log.add(format!(
+                        "The stash is full! Left on the ground: {}",
+                        names.join(", ")
+                    ));
+                    self.game_grid.ground_loot.insert(position);
+                }
+            }
+            GameEvent::LootPickedUp {
+                character,
+                position,
+                items,
+                remaining_on_ground,
+            } => {
+                let character = self.characters.get(character);
+                let names: Vec<&str> = items.iter().map(|entry| entry.name()).collect();
+                self.log.add(format!(
+                    "{} picked up: {}",
+                    character.name,
+                    names.join(", ")
+                ));
+                if !remaining_on_ground {
+                    self.game_grid.ground_loot.remove(&position);
+                }
+            }
+            GameEvent::GroundEffectsChanged { positions } => {
+                self.game_grid.ground_effects = positions.into_iter().collect();
+            }
+            GameEvent::TrapsChanged { positions } => {
+                self.game_grid.traps = positions.into_iter().collect();
+            }
+            GameEvent::TrapTriggered {
+                victim,
+                position: _,
+                ability_name,
+                damage,
+                detail_lines,
+            } => {
+                let victim = self.characters.get(victim);
+                self.log.add_with_details(
+                    format!("{} set off a trap ({})", victim.name, ability_name),
+                    &detail_lines,
+                );
+                let text = match damage {
+                    Some(damage) => format!("Trap! {}", damage),
+                    None => "Trap!".to_string(),
+                };
+                self.game_grid.add_text_effect(
+                    victim.pos(),
+                    0.0,
+                    2.0,
+                    None,
+                    text,
+                    TextEffectStyle::HostileEffect,
+                );
+            }
+            GameEvent::FightEnded { survivors } => {
+                let names: Vec<&str> = survivors
+                    .iter()
+                    .map(|id| self.characters.get(*id).name)
+                    .collect();
+                self.log.add(format!("Survivors: {}", names.join(", ")));
+            }
             GameEvent::GameOver(text) => {
                 self.banner.set(text, 1.5);
                 self.animation_stopwatch.set_to_at_least(1.5);
@@ -2318,6 +2860,37 @@ impl UserInterface {
         }
     }
 
+    /// Characters can pick up new actions mid-fight (like standing up, after being knocked prone),
+    /// and those need buttons
+    fn rebuild_character_ui_if_actions_changed(&mut self, character_id: CharacterId) {
+        let Some(character_ui) = self.character_uis.get(&character_id) else {
+            return;
+        };
+        let character = self.characters.get_rc(character_id);
+        let has_all_buttons = character.known_actions().into_iter().all(|action| {
+            character_ui
+                .tracked_action_buttons
+                .contains_key(&button_action_id(ButtonAction::Action(action)))
+        });
+        if has_all_buttons {
+            return;
+        }
+
+        let character_ui = build_character_ui(
+            &self.ui_resources.equipment_icons,
+            &self.ui_resources.icons,
+            &self.event_queue,
+            &self.simple_font,
+            &character,
+            &mut self.next_character_ui_button_id,
+            self.status_textures.clone(),
+            Rc::new(RefCell::new((100.0, 100.0))),
+            self.sound_player.clone(),
+        );
+        self.character_uis.insert(character_id, character_ui);
+        self.hovered_button = None;
+    }
+
     fn set_new_active_character_id(&mut self, new_active_id: CharacterId) {
         if new_active_id != self.active_character_id {
             // When control switches to a new player controlled character, make the UI show that character
@@ -2381,7 +2954,13 @@ impl UserInterface {
                 self.refresh_attack_state();
             }
             Some(ActivityPopupOutcome::ChangedMovementSprint(_sprint_usage)) => {
-                self.game_grid.update_move_speed(self.active_character_id);
+                self.game_grid
+                    .update_move_speed(self.active_character_id, self.movement_bonus_distance());
+            }
+            Some(ActivityPopupOutcome::ChangedMovementEnhancements) => {
+                self.game_grid
+                    .update_move_speed(self.active_character_id, self.movement_bonus_distance());
+                self.refresh_movement_state();
             }
             Some(ActivityPopupOutcome::ChangedReaction) => {
                 self.refresh_reaction_state();
@@ -2512,9 +3091,17 @@ impl UserInterface {
         // HOVERING ACTION
         else if let Some(hovered_btn) = &self.hovered_button {
             if hovered_btn.context != Some(ButtonContext::CharacterSheet) {
+                let hovered_ap = match hovered_btn.action {
+                    ButtonAction::Action(BaseAction::ChangeEquipment) => {
+                        game.characters
+                            .get(self.player_portraits.selected_id())
+                            .change_equipment_ap_cost() as i32
+                    }
+                    action => action.action_point_cost(),
+                };
                 character_ui.action_points_row.reserved_and_hovered_ap = (
                     self.activity_popup.reserved_and_hovered_action_points().0,
-                    hovered_btn.action.action_point_cost(),
+                    hovered_ap,
                 );
                 character_ui
                     .mana_bar
@@ -2589,6 +3176,7 @@ impl UserInterface {
                     &ConfiguredAction::Move {
                         cost,
                         selected_movement_path,
+                        selected_enhancements,
                     } => {
                         let dst = selected_movement_path.last().unwrap();
                         let total_distance = dst.0;
@@ -2601,6 +3189,7 @@ impl UserInterface {
                             total_distance,
                             extra_cost: *cost,
                             positions,
+                            enhancements: selected_enhancements.clone(),
                         })
                     }
                     &ConfiguredAction::ChangeEquipment { drag } => {
@@ -2615,9 +3204,15 @@ impl UserInterface {
 
                         Some(Action::ChangeEquipment { from, to })
                     }
-                    &ConfiguredAction::UseConsumable(consumption) => Some(Action::UseConsumable {
-                        inventory_equipment_index: consumption.unwrap().equipment_idx,
-                    }),
+                    &ConfiguredAction::UseConsumable(consumption) => {
+                        let consumption = consumption.unwrap();
+                        Some(Action::UseConsumable {
+                            inventory_equipment_index: consumption.equipment_idx,
+                            target: consumption.target,
+                        })
+                    }
+                    &ConfiguredAction::DelayTurn => Some(Action::DelayTurn),
+                    &ConfiguredAction::GuardStance => Some(Action::GuardStance),
                 };
                 PlayerChose::Action(action)
             }
@@ -2630,6 +3225,11 @@ impl UserInterface {
             UiState::ReactingToRangedAttackOpportunity { selected, .. } => {
                 PlayerChose::OpportunityAttack(*selected)
             }
+            UiState::ReactingToOverwatch { selected, .. } => PlayerChose::Overwatch(*selected),
+            UiState::ReactingToIntercept { selected, .. } => PlayerChose::Intercept(*selected),
+            UiState::ReactingToCounterspell { selected, .. } => {
+                PlayerChose::Counterspell(*selected)
+            }
 
             UiState::ChoosingAction | UiState::Idle => unreachable!(),
         }
@@ -2651,11 +3251,12 @@ impl UserInterface {
 
                 ui.character_sheet.conditions_list.borrow_mut().infos = character.condition_infos();
 
-                ui.action_points_row.current_ap = self
+                let selected_action_points = &self
                     .characters
                     .get(self.player_portraits.selected_id())
-                    .action_points
-                    .current();
+                    .action_points;
+                ui.action_points_row.current_ap = selected_action_points.current();
+                ui.action_points_row.max_ap = selected_action_points.max();
                 ui.action_points_row.is_characters_turn = *id == self.active_character_id;
 
                 let statuses = &character.condition_infos();
@@ -2671,11 +3272,10 @@ fn build_character_uis<'a>(
     event_queue: &Rc<RefCell<Vec<InternalUiEvent>>>,
     simple_font: &Font,
     characters: impl Iterator<Item = &'a Rc<Character>>,
+    next_button_id: &mut u32,
     status_textures: HashMap<StatusId, Texture2D>,
     sound_player: SoundPlayer,
 ) -> HashMap<u32, CharacterUi> {
-    let mut next_button_id = 1;
-
     let mut character_uis: HashMap<CharacterId, CharacterUi> = Default::default();
 
     let character_sheet_screen_pos = Rc::new(RefCell::new((100.0, 100.0)));
@@ -2691,7 +3291,7 @@ fn build_character_uis<'a>(
             event_queue,
             simple_font,
             character,
-            &mut next_button_id,
+            next_button_id,
             status_textures.clone(),
             Rc::clone(&character_sheet_screen_pos),
             sound_player.clone(),
@@ -2805,7 +3405,7 @@ fn build_character_ui(
 
                 hoverable_buttons.push(btn);
             }
-            BaseAction::Move => {
+            BaseAction::Move | BaseAction::DelayTurn | BaseAction::GuardStance => {
                 *btn.hotkey.borrow_mut() = basic_hotkeys
                     .get(basic_buttons.len())
                     .map(|key| (*key, simple_font.clone()));
@@ -3003,6 +3603,8 @@ fn button_action_id(btn_action: ButtonAction) -> String {
             BaseAction::Move => "MOVE".to_string(),
             BaseAction::ChangeEquipment => "CHANGING_EQUIPMENT".to_string(),
             BaseAction::UseConsumable => "USING_CONSUMABLE".to_string(),
+            BaseAction::DelayTurn => "DELAY_TURN".to_string(),
+            BaseAction::GuardStance => "GUARD_STANCE".to_string(),
         },
 
         _ => unreachable!(),
@@ -3023,6 +3625,9 @@ pub enum PlayerChose {
     AttackedReaction(Option<OnAttackedReaction>),
     HitReaction(Option<OnHitReaction>),
     OpportunityAttack(bool),
+    Overwatch(bool),
+    Intercept(bool),
+    Counterspell(bool),
     Action(Option<Action>),
     SwitchTo(CharacterId),
 }
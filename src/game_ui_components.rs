@@ -157,6 +157,15 @@ impl TopCharacterPortraits {
             .retain(|_id, portrait| !portrait.borrow().character.is_dead());
         self.row.remove_dropped_children();
     }
+
+    pub fn add_character(&mut self, character: &Rc<Character>, font: Font, texture: Texture2D) {
+        let portrait = Rc::new(RefCell::new(TopCharacterPortrait::new(
+            character, font, texture,
+        )));
+        let cloned = Rc::downgrade(&portrait);
+        self.row.children.push(Element::WeakRefCell(cloned));
+        self.portraits.insert(character.id(), portrait);
+    }
 }
 
 struct TopCharacterPortrait {
@@ -467,6 +476,23 @@ impl PlayerPortraits {
         self.set_active_character(game.active_character_id);
     }
 
+    pub fn add_character(
+        &mut self,
+        character: &Rc<Character>,
+        texture: Texture2D,
+        status_textures: HashMap<StatusId, Texture2D>,
+    ) {
+        let portrait = Rc::new(RefCell::new(PlayerCharacterPortrait::new(
+            character,
+            self.font.clone(),
+            texture,
+            status_textures,
+            self.sound_player.clone(),
+        )));
+        self.row.children.push(Element::RcRefCell(portrait.clone()));
+        self.portraits.insert(character.id(), portrait);
+    }
+
     pub fn draw(&self, x: f32, y: f32, may_show_end_turn_button: bool) -> PlayerPortraitOutcome {
         for (_, portrait) in &self.portraits {
             let mut portrait = portrait.borrow_mut();
@@ -959,7 +985,7 @@ pub struct ActionPointsRow {
     pub is_characters_turn: bool,
     pub current_ap: u32,
     pub reserved_and_hovered_ap: (i32, i32),
-    max_ap: u32,
+    pub max_ap: u32,
     cell_size: (f32, f32),
     pub padding: f32,
     style: Style,
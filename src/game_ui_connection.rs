@@ -16,8 +16,13 @@ use macroquad::{
 use crate::core::{GameEvent, Position};
 
 use super::bot::bot_choose_action;
-use super::bot::{bot_choose_attack_reaction, bot_choose_hit_reaction};
-use super::core::{Action, CharacterId, CoreGame, HandType, OnAttackedReaction, OnHitReaction};
+use super::bot::{
+    bot_choose_attack_reaction, bot_choose_counterspell, bot_choose_hit_reaction,
+    bot_choose_intercept,
+};
+use super::core::{
+    Ability, Action, CharacterId, CoreGame, HandType, OnAttackedReaction, OnHitReaction,
+};
 
 use super::game_ui::{PlayerChose, UiState, UserInterface};
 
@@ -31,6 +36,9 @@ enum UiOutcome {
     ChoseOnHitReaction(Option<OnHitReaction>),
     ChoseOnAttackedReaction(Option<OnAttackedReaction>),
     ChoseOpportunityAttack(bool),
+    ChoseOverwatch(bool),
+    ChoseIntercept(bool),
+    ChoseCounterspell(bool),
     SwitchedTo(CharacterId),
     None,
 }
@@ -62,6 +70,21 @@ enum MessageFromGame {
         attacker: CharacterId,
         victim: CharacterId,
     },
+    AwaitingChooseOverwatch {
+        reactor: CharacterId,
+        target: CharacterId,
+        movement: (Position, Position),
+    },
+    AwaitingChooseIntercept {
+        reactor: CharacterId,
+        attacker: CharacterId,
+        victim: CharacterId,
+    },
+    AwaitingChooseCounterspell {
+        reactor: CharacterId,
+        caster: CharacterId,
+        ability: Ability,
+    },
     // Box since GameEvent was significantly larger than other variants, and we started getting a stackoverflow
     // when handling the event
     Event(Box<GameEvent>),
@@ -211,6 +234,75 @@ impl GameUserInterfaceConnection {
         }
     }
 
+    pub async fn choose_overwatch(
+        &self,
+        game: &CoreGame,
+        reactor: CharacterId,
+        target: CharacterId,
+        movement: (Position, Position),
+    ) -> Result<bool, QuitEvent> {
+        match self
+            .run_ui(
+                game,
+                MessageFromGame::AwaitingChooseOverwatch {
+                    reactor,
+                    target,
+                    movement,
+                },
+            )
+            .await?
+        {
+            UiOutcome::ChoseOverwatch(choice) => Ok(choice),
+            _ => unreachable!(),
+        }
+    }
+
+    pub async fn choose_intercept(
+        &self,
+        game: &CoreGame,
+        reactor: CharacterId,
+        attacker: CharacterId,
+        victim: CharacterId,
+    ) -> Result<bool, QuitEvent> {
+        match self
+            .run_ui(
+                game,
+                MessageFromGame::AwaitingChooseIntercept {
+                    reactor,
+                    attacker,
+                    victim,
+                },
+            )
+            .await?
+        {
+            UiOutcome::ChoseIntercept(choice) => Ok(choice),
+            _ => unreachable!(),
+        }
+    }
+
+    pub async fn choose_counterspell(
+        &self,
+        game: &CoreGame,
+        reactor: CharacterId,
+        caster: CharacterId,
+        ability: Ability,
+    ) -> Result<bool, QuitEvent> {
+        match self
+            .run_ui(
+                game,
+                MessageFromGame::AwaitingChooseCounterspell {
+                    reactor,
+                    caster,
+                    ability,
+                },
+            )
+            .await?
+        {
+            UiOutcome::ChoseCounterspell(choice) => Ok(choice),
+            _ => unreachable!(),
+        }
+    }
+
     pub async fn handle_event(&self, game: &CoreGame, event: GameEvent) {
         let msg = MessageFromGame::Event(Box::new(event));
 
@@ -329,6 +421,62 @@ impl _GameUserInterfaceConnection {
                 }
             }
 
+            MessageFromGame::AwaitingChooseOverwatch {
+                reactor,
+                target,
+                movement,
+            } => {
+                if players_turn {
+                    // The bot set aside its AP for this, so it always takes the shot
+                    return Ok(UiOutcome::ChoseOverwatch(true));
+                } else {
+                    user_interface.set_state(UiState::ReactingToOverwatch {
+                        reactor,
+                        target,
+                        movement,
+                        selected: false,
+                    });
+                }
+            }
+
+            MessageFromGame::AwaitingChooseIntercept {
+                reactor,
+                attacker,
+                victim,
+            } => {
+                if players_turn {
+                    return Ok(UiOutcome::ChoseIntercept(bot_choose_intercept(
+                        game, reactor, victim,
+                    )));
+                } else {
+                    user_interface.set_state(UiState::ReactingToIntercept {
+                        reactor,
+                        attacker,
+                        victim,
+                        selected: false,
+                    });
+                }
+            }
+
+            MessageFromGame::AwaitingChooseCounterspell {
+                reactor,
+                caster,
+                ability,
+            } => {
+                if players_turn {
+                    return Ok(UiOutcome::ChoseCounterspell(bot_choose_counterspell(
+                        game, reactor, ability,
+                    )));
+                } else {
+                    user_interface.set_state(UiState::ReactingToCounterspell {
+                        reactor,
+                        caster,
+                        ability,
+                        selected: false,
+                    });
+                }
+            }
+
             MessageFromGame::Event(event) => {
                 waiting_for_ui_animation_potentially = true;
                 user_interface.handle_game_event(event);
@@ -389,6 +537,9 @@ impl _GameUserInterfaceConnection {
                     PlayerChose::OpportunityAttack(choice) => {
                         UiOutcome::ChoseOpportunityAttack(choice)
                     }
+                    PlayerChose::Overwatch(choice) => UiOutcome::ChoseOverwatch(choice),
+                    PlayerChose::Intercept(choice) => UiOutcome::ChoseIntercept(choice),
+                    PlayerChose::Counterspell(choice) => UiOutcome::ChoseCounterspell(choice),
                     PlayerChose::Action(action) => UiOutcome::ChoseAction(action),
                     PlayerChose::SwitchTo(character_id) => UiOutcome::SwitchedTo(character_id),
                 };
@@ -9,7 +9,7 @@ use std::{
 
 use indexmap::IndexMap;
 use macroquad::{
-    color::{Color, BLACK, GRAY, LIGHTGRAY, MAGENTA, ORANGE},
+    color::{Color, BLACK, GOLD, GRAY, LIGHTGRAY, MAGENTA, ORANGE},
     input::mouse_wheel,
     math::Vec2,
     shapes::{
@@ -35,14 +35,21 @@ use macroquad::{
     text::measure_text,
 };
 
+use crate::{
+    action_button::{draw_tooltip, TooltipPositionPreference},
+    core::{CharacterId, HandType, Range},
+    drawing::{draw_arrow, draw_dashed_line},
+};
 use crate::{
     base_ui::{
         draw_text_rounded, draw_text_with_font_tags, measure_text_with_font_tags, Drawable, Style,
     },
     core::{
+        characters_in_area, find_path_into_melee, is_target_within_shape,
         target_within_range_squared, within_range_squared, AbilityId, AbilityReach, AbilityTarget,
-        ActionReach, ActionTarget, AreaEffect, AreaShape, AttackAction, BaseAction, Character,
-        Goodness, MovementType, Position, TargetPrediction, MOVE_DISTANCE_PER_STAMINA,
+        ActionReach, ActionTarget, AreaEffect, AreaShape, AreaTargetAcquisition, AttackAction,
+        BaseAction, Character, Goodness, MovementType, Position, TargetPrediction,
+        MOVE_DISTANCE_PER_STAMINA,
     },
     drawing::{
         draw_cornered_rectangle_lines, draw_cross, draw_crosshair, draw_dashed_line_ex,
@@ -62,10 +69,6 @@ use crate::{
     },
     util::{line_visitor, rgb, COL_RED},
 };
-use crate::{
-    core::{CharacterId, HandType, Range},
-    drawing::{draw_arrow, draw_dashed_line},
-};
 
 const BACKGROUND_COLOR: Color = rgb(12, 64, 59); // COL_GRAY; // Color::new(0.2, 0.2, 0.2, 1.0);
 const GRID_COLOR: Color = Color::new(0.4, 0.4, 0.4, 1.0);
@@ -84,6 +87,15 @@ const HOVER_TERRAIN_NEED_CHAR_TARGET_COLOR: Color = LIGHTGRAY;
 const HOVER_ENEMY_COLOR: Color = Color::new(0.8, 0.2, 0.2, 1.0);
 const TARGET_ENEMY_COLOR: Color = Color::new(1.0, 0.0, 0.3, 1.0);
 const HOVER_ALLY_COLOR: Color = Color::new(0.2, 0.8, 0.2, 1.0);
+const SPARED_ALLY_COLOR: Color = Color::new(0.2, 0.8, 0.2, 0.4);
+const AREA_HIT_ALLY_COLOR: Color = Color::new(1.0, 0.0, 0.0, 0.5);
+const AREA_HIT_ENEMY_COLOR: Color = Color::new(1.0, 0.6, 0.0, 0.3);
+const GROUND_EFFECT_COLOR: Color = Color::new(1.0, 0.35, 0.0, 0.35);
+const TELEPORT_DESTINATION_COLOR: Color = Color::new(0.5, 0.3, 1.0, 0.2);
+const TRAP_COLOR: Color = Color::new(0.9, 0.1, 0.1, 0.8);
+const HIGH_GROUND_COLOR: Color = Color::new(1.0, 1.0, 0.8, 0.12);
+const LOW_GROUND_COLOR: Color = Color::new(0.0, 0.0, 0.1, 0.25);
+const DIFFICULT_TERRAIN_COLOR: Color = Color::new(0.4, 0.25, 0.1, 0.25);
 const INSPECTING_TARGET_COLOR: Color = LIGHTGRAY;
 
 const ACTIVE_CHARACTER_COLOR: Color = Color::new(1.0, 0.8, 0.0, 0.4);
@@ -205,6 +217,7 @@ const ZOOM_LEVELS: [f32; 4] = [50.0 / 3.0, 64.0 / 3.0, 85.0 / 3.0, 96.0 / 3.0];
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum TextEffectStyle {
     FriendlyEffect,
+    Healing,
     Miss,
     ReactionExclamation,
     HostileEffect,
@@ -247,9 +260,17 @@ pub struct GameGrid {
     pub pathfind_grid: Rc<PathfindGrid>,
     //routes: IndexMap<Position, ChartNode>,
     pub characters: HashMap<CharacterId, Rc<Character>>,
+    // Positions where there's loot that didn't fit in the party stash
+    pub ground_loot: HashSet<Position>,
+    // Positions with a lingering effect, e.g. a wall of fire
+    pub ground_effects: HashSet<Position>,
+    // Positions of the traps that the player side has placed
+    pub traps: HashSet<Position>,
 
     ability_character_animation: Option<ParticleGroup>,
     target_damage_previews: HashMap<CharacterId, TargetEffectPreview>,
+    // Shown as a tooltip when hovering the target of the attack being configured
+    attack_breakdown: Option<(CharacterId, Vec<(String, Goodness, String)>)>,
     character_animations: Vec<CharacterAnimation>,
     pub grid_dimensions: (u32, u32),
     pub position_on_screen: (f32, f32),
@@ -263,6 +284,8 @@ pub struct GameGrid {
     active_character_id: CharacterId,
 
     movement_range: MovementRange,
+    // Free movement gained from movement enhancements (e.g. Sprint) that haven't been paid for yet
+    movement_bonus_distance: f32,
 
     locked_inspection_target: Option<CharacterId>,
     hovered_character: Option<CharacterId>,
@@ -301,10 +324,14 @@ impl GameGrid {
             dragging_camera_from: None,
             camera_position: (Cell::new(0.0), Cell::new(0.0)),
             characters,
+            ground_loot: Default::default(),
+            ground_effects: Default::default(),
+            traps: Default::default(),
             effects: vec![],
             selected_player_character_id: Some(selected_character_id),
             active_character_id: 0,
             movement_range: MovementRange::default(),
+            movement_bonus_distance: 0.0,
             locked_inspection_target: None,
             hovered_character: None,
             enemys_target: None,
@@ -314,6 +341,7 @@ impl GameGrid {
             position_on_screen: (0.0, 0.0),
             ability_character_animation: Default::default(),
             target_damage_previews: Default::default(),
+            attack_breakdown: None,
             character_animations: Default::default(),
             big_font,
             simple_font,
@@ -466,6 +494,15 @@ impl GameGrid {
 
     pub fn clear_target_damage_previews(&mut self) {
         self.target_damage_previews.clear();
+        self.attack_breakdown = None;
+    }
+
+    pub fn set_attack_breakdown(
+        &mut self,
+        target_id: CharacterId,
+        breakdown: Vec<(String, Goodness, String)>,
+    ) {
+        self.attack_breakdown = Some((target_id, breakdown));
     }
 
     pub fn set_character_motion(
@@ -744,6 +781,7 @@ impl GameGrid {
                 WHITE
                 //Color::new(1.0, 0.8, 0.8, 1.0)
             }
+            TextEffectStyle::Healing => GREEN,
             TextEffectStyle::Miss => WHITE,
             TextEffectStyle::ReactionExclamation => ORANGE,
             TextEffectStyle::HostileGraze => LIGHTGRAY,
@@ -790,12 +828,18 @@ impl GameGrid {
         self.effects.push(concrete_effect);
     }
 
-    pub fn update_move_speed(&mut self, active_char_id: CharacterId) {
+    pub fn update_move_speed(&mut self, active_char_id: CharacterId, bonus_distance: f32) {
         let active_char = &self.characters[&active_char_id];
+        self.movement_bonus_distance = bonus_distance;
 
         let speed = active_char.move_speed();
-        let max_range = active_char.remaining_movement.get()
-            + (active_char.stamina.current() * MOVE_DISTANCE_PER_STAMINA) as f32;
+        let max_range = if active_char.is_immobilized() {
+            0.0
+        } else {
+            active_char.remaining_movement.get()
+                + bonus_distance
+                + (active_char.stamina.current() * MOVE_DISTANCE_PER_STAMINA) as f32
+        };
 
         self.movement_range.set(speed, max_range);
     }
@@ -919,6 +963,39 @@ impl GameGrid {
             }
         }
 
+        for (pos, elevation) in self.pathfind_grid.elevations().iter() {
+            let color = if *elevation > 0 {
+                HIGH_GROUND_COLOR
+            } else {
+                LOW_GROUND_COLOR
+            };
+            // Each level of height or depth is shaded one step further
+            for _ in 0..elevation.unsigned_abs() {
+                self.fill_cell(*pos, color, 0.0);
+            }
+        }
+
+        for pos in self.pathfind_grid.difficult_positions().iter() {
+            self.fill_cell(*pos, DIFFICULT_TERRAIN_COLOR, 0.0);
+        }
+
+        for pos in &self.ground_effects {
+            self.fill_cell(*pos, GROUND_EFFECT_COLOR, 0.0);
+        }
+
+        for pos in &self.traps {
+            self.draw_cell_outline(*pos, TRAP_COLOR, 0.0, 2.0);
+        }
+
+        for (x, y) in &self.ground_loot {
+            draw_circle(
+                self.grid_x_to_screen(*x) + self.cell_w / 2.0,
+                self.grid_y_to_screen(*y) + self.cell_w / 2.0,
+                self.cell_w * 0.3,
+                GOLD,
+            );
+        }
+
         for pos in self.pathfind_grid.occupied_positions().iter() {
             // TODO
             if false {
@@ -1307,6 +1384,7 @@ impl GameGrid {
                 ConfiguredAction::Attack { .. } => MouseState::RequiresEnemyTarget {
                     area_radius: None,
                     move_into_melee: None,
+                    spare_allies: false,
                 },
 
                 ConfiguredAction::UseAbility {
@@ -1314,11 +1392,8 @@ impl GameGrid {
                     selected_enhancements,
                     target,
                 } => match ability.target {
-                    AbilityTarget::Enemy {
-                        impact_circle: impact_area,
-                        reach,
-                        ..
-                    } => {
+                    AbilityTarget::Enemy { reach, .. } => {
+                        let impact_area = ability.impact_circle(selected_enhancements);
                         let mut area_radius = None;
                         if let Some((mut radius, _acquisition, _effect)) = impact_area {
                             for effect in
@@ -1346,13 +1421,20 @@ impl GameGrid {
                             move_into_melee = Some(range);
                         }
 
+                        let spare_allies = selected_enhancements
+                            .iter()
+                            .any(|e| e.spell_effect.is_some_and(|e| e.spare_allies));
+
                         MouseState::RequiresEnemyTarget {
                             area_radius,
                             move_into_melee,
+                            spare_allies,
                         }
                     }
                     AbilityTarget::Ally { .. } => MouseState::RequiresAllyTarget,
-                    AbilityTarget::Area { area_effect, range } => {
+                    AbilityTarget::Area {
+                        area_effect, range, ..
+                    } => {
                         if *target == ActionTarget::None {
                             is_aiming_area = true;
                         }
@@ -1362,13 +1444,33 @@ impl GameGrid {
                             range,
                         }
                     }
+                    AbilityTarget::Teleport { range } => {
+                        MouseState::RequiresTeleportDestination { range }
+                    }
+                    AbilityTarget::Trap { range, .. } => MouseState::RequiresTrapPosition { range },
                     AbilityTarget::None { .. } => MouseState::ImplicitTarget,
                 },
 
                 ConfiguredAction::Move { .. } => MouseState::MayInputMovement,
 
                 ConfiguredAction::ChangeEquipment { .. } => MouseState::None,
-                ConfiguredAction::UseConsumable { .. } => MouseState::None,
+                ConfiguredAction::UseConsumable(Some(consumption)) => {
+                    match consumption.consumable.thrown {
+                        Some(thrown) => {
+                            if consumption.target.is_none() {
+                                is_aiming_area = true;
+                            }
+                            MouseState::RequiresPositionTarget {
+                                shape: thrown.area_effect.shape,
+                                range: thrown.range,
+                            }
+                        }
+                        None => MouseState::None,
+                    }
+                }
+                ConfiguredAction::UseConsumable(None) => MouseState::None,
+                ConfiguredAction::DelayTurn => MouseState::None,
+                ConfiguredAction::GuardStance => MouseState::None,
             },
             _ => MouseState::None,
         };
@@ -1477,30 +1579,42 @@ impl GameGrid {
         match mouse_state {
             MouseState::RequiresEnemyTarget {
                 area_radius: Some(aoe_radius),
+                spare_allies,
                 ..
             } => {
-                if let ActionTarget::Character(target_id, _movement) =
+                let center = if let ActionTarget::Character(target_id, _movement) =
                     ui_state.players_action_target()
                 {
                     // TODO draw movement?
-                    self.draw_range_indicator(
-                        self.characters[&target_id].pos(),
-                        aoe_radius,
-                        RangeIndicator::TargetAreaEffect,
-                    );
+                    Some(self.characters[&target_id].pos())
                 } else if is_mouse_within_grid && receptive_to_input {
                     if let Some(hovered_id) = self.hovered_character {
-                        self.draw_range_indicator(
-                            self.characters[&hovered_id].pos(),
-                            aoe_radius,
-                            RangeIndicator::TargetAreaEffect,
-                        );
+                        Some(self.characters[&hovered_id].pos())
                     } else {
-                        self.draw_range_indicator(
-                            mouse_grid_pos,
-                            aoe_radius,
-                            RangeIndicator::TargetAreaEffect,
-                        );
+                        Some(mouse_grid_pos)
+                    }
+                } else {
+                    None
+                };
+                if let Some(center) = center {
+                    self.draw_range_indicator(center, aoe_radius, RangeIndicator::TargetAreaEffect);
+                    self.draw_area_hit_prediction(ui_state, center);
+
+                    if spare_allies {
+                        // Highlight the allies that the area effect will leave untouched
+                        let caster = &self.characters[&self.active_character_id];
+                        for character in self.characters.values() {
+                            if character.player_controlled() == caster.player_controlled()
+                                && is_target_within_shape(
+                                    active_char_pos,
+                                    center,
+                                    AreaShape::Circle(aoe_radius),
+                                    character,
+                                )
+                            {
+                                self.fill_cell(character.pos(), SPARED_ALLY_COLOR, 0.0);
+                            }
+                        }
                     }
                 }
             }
@@ -1522,6 +1636,7 @@ impl GameGrid {
                                 radius,
                                 RangeIndicator::TargetAreaEffect,
                             );
+                            self.draw_area_hit_prediction(ui_state, center);
                         }
                     }
                     AreaShape::Line => {
@@ -1562,11 +1677,28 @@ impl GameGrid {
                                     false
                                 }
                             });
+
+                            if let Some(to) = snapped_position_target {
+                                self.draw_area_hit_prediction(ui_state, to);
+                            }
                         }
                     }
                 };
             }
 
+            MouseState::RequiresTeleportDestination { range }
+            | MouseState::RequiresTrapPosition { range } => {
+                let reach = f32::from(range).ceil() as i32;
+                for dx in -reach..=reach {
+                    for dy in -reach..=reach {
+                        let pos = (active_char_pos.0 + dx, active_char_pos.1 + dy);
+                        if self.is_valid_position_target(&mouse_state, pos) {
+                            self.fill_cell(pos, TELEPORT_DESTINATION_COLOR, 0.0);
+                        }
+                    }
+                }
+            }
+
             _ => {}
         }
 
@@ -1606,6 +1738,41 @@ impl GameGrid {
                 self.draw_overhead_question_mark(reactor);
             }
 
+            UiState::ReactingToOverwatch {
+                reactor,
+                target,
+                movement,
+                selected,
+            } => {
+                let target = &self.characters[target];
+                let reactor = &self.characters[reactor];
+
+                let path = [movement.0, movement.1];
+                self.draw_movement_path_arrow(path.iter().copied(), RED, 7.0, true);
+                self.draw_cornered_outline(
+                    self.character_screen_pos(reactor),
+                    ACTIVE_CHARACTER_COLOR,
+                    5.0,
+                    2.0,
+                    true,
+                );
+
+                if *selected {
+                    self.draw_target_crosshair(
+                        reactor.pos(),
+                        target.pos(),
+                        PLAYERS_TARGET_CROSSHAIR_COLOR,
+                        4.0,
+                        true,
+                    );
+                }
+
+                labelled_char_ids.insert(target.id());
+                labelled_char_ids.insert(reactor.id());
+
+                self.draw_overhead_question_mark(reactor);
+            }
+
             UiState::ReactingToRangedAttackOpportunity {
                 reactor,
                 attacker,
@@ -1693,6 +1860,7 @@ impl GameGrid {
         if !matches!(
             ui_state,
             UiState::ReactingToMovementAttackOpportunity { .. }
+                | UiState::ReactingToOverwatch { .. }
         ) {
             labelled_char_ids.insert(self.active_character_id);
         }
@@ -1715,6 +1883,24 @@ impl GameGrid {
                 self.locked_inspection_target = None;
             }
 
+            if pressed_left_mouse
+                && matches!(
+                    mouse_state,
+                    MouseState::RequiresTeleportDestination { .. }
+                        | MouseState::RequiresTrapPosition { .. }
+                )
+            {
+                if ui_state.players_action_target() == ActionTarget::None {
+                    if self.is_valid_position_target(&mouse_state, mouse_grid_pos) {
+                        ui_state.set_target(ActionTarget::Position(mouse_grid_pos));
+                        outcome.switched_players_action_target = true;
+                    }
+                } else {
+                    *ui_state = UiState::ChoosingAction;
+                    outcome.switched_state = Some(NewState::ChoosingAction);
+                }
+            }
+
             if pressed_left_mouse
                 && matches!(mouse_state, MouseState::RequiresPositionTarget { .. })
             {
@@ -1810,6 +1996,36 @@ impl GameGrid {
                         }
                     }
                 }
+                MouseState::RequiresTeleportDestination { .. }
+                | MouseState::RequiresTrapPosition { .. } => {
+                    let is_teleport =
+                        matches!(mouse_state, MouseState::RequiresTeleportDestination { .. });
+                    if ui_state.players_action_target() == ActionTarget::None {
+                        if self.is_valid_position_target(&mouse_state, mouse_grid_pos) {
+                            let text = if is_teleport {
+                                "Select destination"
+                            } else {
+                                "Place trap"
+                            };
+                            self.draw_cursor_text(text, mouse_grid_pos);
+                            self.draw_target_crosshair(
+                                active_char_pos,
+                                mouse_grid_pos,
+                                HOVER_PLAYERS_TARGET_CROSSHAIR_COLOR,
+                                4.0,
+                                true,
+                            );
+                        } else {
+                            self.draw_invalid_target_marker(mouse_grid_pos);
+                            let text = if is_teleport {
+                                "Can't go there"
+                            } else {
+                                "Can't place it there"
+                            };
+                            self.draw_cursor_text(text, mouse_grid_pos);
+                        }
+                    }
+                }
                 MouseState::MayInputMovement => {
                     hovered_move_route = self.determine_hovered_route_position(mouse_grid_pos);
 
@@ -1838,13 +2054,17 @@ impl GameGrid {
                         .cost(hovered_route_node.distance_from_start, remaining_movement);
 
                     if pressed_left_mouse {
-                        let commit_movement = matches!(
-                            ui_state,
-                            UiState::ConfiguringAction(ConfiguredAction::Move { .. })
-                        );
+                        let (commit_movement, selected_enhancements) = match ui_state {
+                            UiState::ConfiguringAction(ConfiguredAction::Move {
+                                selected_enhancements,
+                                ..
+                            }) => (true, selected_enhancements.clone()),
+                            _ => (false, vec![]),
+                        };
                         *ui_state = UiState::ConfiguringAction(ConfiguredAction::Move {
                             cost,
                             selected_movement_path: path.positions,
+                            selected_enhancements,
                         });
                         outcome.switched_state = Some(NewState::Move { commit_movement });
                     } else {
@@ -2129,6 +2349,12 @@ impl GameGrid {
 
         self.draw_effects();
 
+        if let Some((target_id, breakdown)) = &self.attack_breakdown {
+            if self.hovered_character == Some(*target_id) {
+                self.draw_attack_breakdown(breakdown);
+            }
+        }
+
         let inspect_target = self.hovered_character.or(self.locked_inspection_target);
         if inspect_target != prev_inspect_target {
             outcome.switched_inspect_target = Some(inspect_target);
@@ -2137,6 +2363,34 @@ impl GameGrid {
         outcome
     }
 
+    fn draw_attack_breakdown(&self, breakdown: &[(String, Goodness, String)]) {
+        let lines: Vec<String> = breakdown
+            .iter()
+            .map(|(label, goodness, value)| {
+                let sign = match goodness {
+                    Goodness::Good => "+ ",
+                    Goodness::Neutral => "",
+                    Goodness::Bad => "- ",
+                };
+                if value.is_empty() {
+                    format!("{}{}", sign, label)
+                } else {
+                    format!("{}{}: |<value>{}|", sign, label, value)
+                }
+            })
+            .collect();
+        let (mouse_x, mouse_y) = mouse_position();
+        draw_tooltip(
+            &self.simple_font,
+            TooltipPositionPreference::At((mouse_x + 20.0, mouse_y + 20.0)),
+            "Attack breakdown",
+            None,
+            &lines,
+            &[],
+            false,
+        );
+    }
+
     fn draw_cursor_text(&self, text: &str, mouse_grid_pos: (i32, i32)) {
         let snapped_mouse_pos = self.grid_pos_to_screen(mouse_grid_pos);
 
@@ -2308,11 +2562,15 @@ impl GameGrid {
         move_range: Range,
     ) -> Vec<Position> {
         let actor_id = self.active_character_id;
-        let maybe_path = self.pathfind_grid.find_shortest_path_to_adjacent(
+        if self.characters[&actor_id].is_immobilized() {
+            return vec![];
+        }
+        let maybe_path = find_path_into_melee(
+            &self.pathfind_grid,
             actor_id,
             actor_pos,
             target_pos,
-            f32::from(move_range) - 1.0,
+            move_range,
         );
 
         if let Some(path) = maybe_path {
@@ -2384,22 +2642,11 @@ impl GameGrid {
 
                             (range, reach)
                         }
-                        None => {
-                            let range = active_char.weapon(attack.hand).unwrap().range.into_range();
-
-                            let hovered_pos = mouse_grid_pos;
-
-                            let reach = if within_range_squared(
-                                range.squared(),
-                                active_char.pos(),
-                                hovered_pos,
-                            ) {
-                                ActionReach::Yes
-                            } else {
-                                ActionReach::No
-                            };
-                            (range, reach)
-                        }
+                        None => active_char.reaches_with_attack(
+                            attack.hand,
+                            mouse_grid_pos,
+                            selected_enhancements.iter().map(|e| e.effect),
+                        ),
                     };
 
                     let indicator = match (reach, obstructed_line_of_sight) {
@@ -2499,6 +2746,21 @@ impl GameGrid {
                         }
                     }
                 }
+                ConfiguredAction::UseConsumable(Some(consumption)) => {
+                    let thrown = consumption.consumable.thrown?;
+                    let active_char = &self.characters[&self.active_character_id];
+                    let target_pos = consumption.target.unwrap_or(mouse_grid_pos);
+                    let indicator = if within_range_squared(
+                        thrown.range.squared(),
+                        active_char.pos(),
+                        target_pos,
+                    ) {
+                        RangeIndicator::ActionTargetRange
+                    } else {
+                        RangeIndicator::CannotReach
+                    };
+                    Some((self.active_character_id, thrown.range, indicator))
+                }
                 _ => None,
             }
         } else {
@@ -2507,6 +2769,89 @@ impl GameGrid {
         }
     }
 
+    fn is_valid_position_target(&self, mouse_state: &MouseState, pos: Position) -> bool {
+        let active_char_pos = self.characters[&self.active_character_id].pos();
+        match *mouse_state {
+            MouseState::RequiresTeleportDestination { range } => {
+                pos != active_char_pos
+                    && within_range_squared(range.squared(), active_char_pos, pos)
+                    && self
+                        .pathfind_grid
+                        .is_free(Some(self.active_character_id), pos)
+            }
+            MouseState::RequiresTrapPosition { range } => {
+                within_range_squared(range.squared(), active_char_pos, pos)
+                    && self.pathfind_grid.is_open_ground(pos)
+            }
+            _ => false,
+        }
+    }
+
+    /// Marks the characters that would be caught by the area effect that's being aimed, so that
+    /// the player is warned before hitting their own allies.
+    fn draw_area_hit_prediction(&self, ui_state: &UiState, center: Position) {
+        let (shape, acquisition, enhancements) = match ui_state {
+            UiState::ConfiguringAction(ConfiguredAction::UseAbility {
+                ability,
+                selected_enhancements,
+                ..
+            }) => match ability.target {
+                AbilityTarget::Enemy { .. } => {
+                    let Some((radius, acquisition, _effect)) =
+                        ability.impact_circle(selected_enhancements)
+                    else {
+                        return;
+                    };
+                    (
+                        AreaShape::Circle(radius),
+                        acquisition,
+                        &selected_enhancements[..],
+                    )
+                }
+                AbilityTarget::Area { area_effect, .. } => (
+                    area_effect.shape,
+                    area_effect.acquisition,
+                    &selected_enhancements[..],
+                ),
+                _ => return,
+            },
+            UiState::ConfiguringAction(ConfiguredAction::UseConsumable(Some(consumption))) => {
+                match consumption.consumable.thrown {
+                    Some(thrown) => (
+                        thrown.area_effect.shape,
+                        thrown.area_effect.acquisition,
+                        &[][..],
+                    ),
+                    None => return,
+                }
+            }
+            _ => return,
+        };
+
+        if acquisition == AreaTargetAcquisition::Allies {
+            // Only beneficial effects are restricted to allies
+            return;
+        }
+
+        let caster = &self.characters[&self.active_character_id];
+        for id in characters_in_area(
+            self.characters.values(),
+            caster,
+            center,
+            shape,
+            acquisition,
+            enhancements,
+        ) {
+            let character = &self.characters[&id];
+            let color = if character.player_controlled() == caster.player_controlled() {
+                AREA_HIT_ALLY_COLOR
+            } else {
+                AREA_HIT_ENEMY_COLOR
+            };
+            self.fill_cell(character.pos(), color, 0.0);
+        }
+    }
+
     fn draw_invalid_target_marker(&self, grid_pos: Position) {
         self.fill_cell(grid_pos, Color::new(1.0, 0.0, 0.0, 0.3), 4.0);
         let (x, y) = self.grid_pos_to_screen(grid_pos);
@@ -2570,6 +2915,7 @@ impl GameGrid {
             }
             action_points_row.padding = 1.0;
             action_points_row.current_ap = character.action_points.current();
+            action_points_row.max_ap = character.action_points.max();
             action_points_row.draw(
                 x - (action_points_row.size().0 - self.cell_w) / 2.0,
                 ap_row_y,
@@ -3044,6 +3390,7 @@ impl GameGrid {
         self.characters[&self.active_character_id]
             .remaining_movement
             .get()
+            + self.movement_bonus_distance
     }
 
     fn draw_movement_path_arrow(
@@ -3491,12 +3838,19 @@ enum MouseState {
     RequiresEnemyTarget {
         area_radius: Option<Range>,
         move_into_melee: Option<Range>,
+        spare_allies: bool,
     },
     RequiresAllyTarget,
     RequiresPositionTarget {
         shape: AreaShape,
         range: Range,
     },
+    RequiresTeleportDestination {
+        range: Range,
+    },
+    RequiresTrapPosition {
+        range: Range,
+    },
     ImplicitTarget,
     MayInputMovement,
     None,
@@ -81,6 +81,14 @@ pub fn init_fight_map(player_characters: Vec<Rc<Character>>, fight_id: FightId)
         pathfind_grid.set_occupied(*pos, Some(Occupation::Terrain(terrain_id.terrain_type())));
     }
 
+    for (pos, elevation) in map_data.elevation.iter() {
+        pathfind_grid.set_elevation(*pos, *elevation);
+    }
+
+    for pos in &map_data.difficult_terrain {
+        pathfind_grid.set_difficult(*pos, true);
+    }
+
     GameInitState {
         characters,
         active_character_id: 0,
@@ -7,7 +7,7 @@ mod character_sheet;
 pub mod chest_scene;
 pub mod conditions_ui;
 pub mod core;
-mod d20;
+pub mod d20;
 pub mod data;
 pub mod drawing;
 pub mod equipment_ui;
@@ -23,6 +23,7 @@ mod non_combat_ui;
 pub mod pathfind;
 pub mod resources;
 pub mod rest_scene;
+pub mod save_file;
 mod settings;
 pub mod shop_scene;
 pub mod skill_tree;
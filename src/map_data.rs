@@ -5,10 +5,10 @@ use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    bot::BotBehaviour,
+    bot::{BotBehaviour, FighterBehaviour},
     core::{
         ArrowStack, Attributes, BaseAction, Bot, Character, CharacterId, CharacterKind, Condition,
-        EquipmentEntry, HandType, Party, PlayerId, Position, Shield, Weapon,
+        EquipmentEntry, HandType, Loot, Party, PlayerId, Position, Shield, Weapon,
     },
     data::{
         BAD_BOW, BAD_DAGGER, BAD_RAPIER, BAD_SMALL_SHIELD, BAD_SWORD, BAD_WAR_HAMMER, CHAIN_MAIL,
@@ -25,7 +25,7 @@ use crate::{
 
 use crate::data::{
     PassiveSkill, ARCANE_POTION, BOW, CRIPPLING_SHOT, DAGGER, EXPLODING_ARROWS, FIREBALL,
-    FIREBALL_INFERNO, FIREBALL_MASSIVE, FIREBALL_REACH, HEAL, HEALTH_POTION, HEAL_ENERGIZE,
+    FIREBALL_CAREFUL, FIREBALL_INFERNO, FIREBALL_MASSIVE, HEAL, HEALTH_POTION, HEAL_ENERGIZE,
     INFLICT_WOUNDS, INFLICT_WOUNDS_NECROTIC_INFLUENCE, INSPIRE, LEATHER_ARMOR, MANA_POTION,
     MEDIUM_SHIELD, PIERCING_SHOT, SHACKLED_MIND, SHIELD_BASH, SHIELD_BASH_KNOCKBACK, SHIRT, SMITE,
     SWEEP_ATTACK, SWORD,
@@ -52,6 +52,14 @@ pub fn create_game_grid(
         pathfind_grid.set_occupied(*pos, Some(Occupation::Terrain(terrain_id.terrain_type())));
     }
 
+    for (pos, elevation) in map_data.elevation.iter() {
+        pathfind_grid.set_elevation(*pos, *elevation);
+    }
+
+    for pos in &map_data.difficult_terrain {
+        pathfind_grid.set_difficult(*pos, true);
+    }
+
     let characters_map: HashMap<CharacterId, Rc<Character>> = characters
         .iter()
         .map(|(_id, ch)| (ch.id(), Rc::clone(ch)))
@@ -84,6 +92,8 @@ pub struct MapData {
     pub terrain_objects: IndexMap<Position, TerrainId>,
     pub decorations: IndexMap<Position, TerrainId>,
     pub background: IndexMap<Position, TerrainId>,
+    pub elevation: IndexMap<Position, i32>,
+    pub difficult_terrain: Vec<Position>,
     pub characters: Vec<CharacterData>,
 }
 
@@ -92,11 +102,14 @@ impl MapData {
         let terrain_objects = keys_pos_to_str(&self.terrain_objects);
         let background = keys_pos_to_str(&self.background);
         let decorations = keys_pos_to_str(&self.decorations);
+        let elevation = keys_pos_to_str(&self.elevation);
         let map_data = SerializableMapData {
             grid_dimensions: self.grid_dimensions,
             terrain_objects,
             background,
             decorations,
+            elevation,
+            difficult_terrain: self.difficult_terrain.clone(),
             characters: self.characters.clone(),
         };
         let json_str = serde_json::to_string_pretty(&map_data).unwrap();
@@ -117,6 +130,8 @@ impl MapData {
             terrain_objects: keys_str_to_pos(&map_data.terrain_objects),
             background: keys_str_to_pos(&map_data.background),
             decorations: keys_str_to_pos(&map_data.decorations),
+            elevation: keys_str_to_pos(&map_data.elevation),
+            difficult_terrain: map_data.difficult_terrain,
             characters: map_data.characters,
         }
     }
@@ -148,6 +163,11 @@ struct SerializableMapData {
     pub terrain_objects: IndexMap<String, TerrainId>,
     pub background: IndexMap<String, TerrainId>,
     pub decorations: IndexMap<String, TerrainId>,
+    // Older maps are flat
+    #[serde(default)]
+    pub elevation: IndexMap<String, i32>,
+    #[serde(default)]
+    pub difficult_terrain: Vec<Position>,
     pub characters: Vec<CharacterData>,
 }
 
@@ -259,7 +279,7 @@ pub fn create_character(
         }
         CharacterType::Skeleton => {
             let mut skeleton = Character::new(
-                bot(BotBehaviour::Fighter(Default::default()), 14.0),
+                bot(BotBehaviour::Fighter(Default::default()), 14.0, Some(8)),
                 "Skeleton",
                 PortraitId::Skeleton,
                 char_data.type_.sprite_id(),
@@ -274,12 +294,24 @@ pub fn create_character(
             //skeleton.learn_ability(ENEMY_BRACE);
             //skeleton.learn_ability(ENEMY_SLASHING_ATTACK);
             skeleton.learn_attack_enhancement(ENEMY_SLASHING);
+            skeleton
+                .condition_immunities
+                .replace(vec![Condition::Bleeding, Condition::Poisoned]);
+            // Dry bones don't burn for long
+            skeleton
+                .condition_resistance_percentages
+                .borrow_mut()
+                .insert(Condition::Burning, 50);
+            skeleton.loot.replace(Loot {
+                money: 2,
+                items: vec![],
+            });
 
             skeleton
         }
         CharacterType::SkeletonLeader => {
             let skeleton = Character::new(
-                bot(BotBehaviour::Fighter(Default::default()), 14.0),
+                bot(BotBehaviour::Fighter(Default::default()), 14.0, None),
                 "Skeleton",
                 PortraitId::Skeleton,
                 char_data.type_.sprite_id(),
@@ -292,12 +324,28 @@ pub fn create_character(
             skeleton.set_shield(SMALL_SHIELD);
             skeleton.learn_ability(ENEMY_BRACE);
             skeleton.learn_ability(ENEMY_INSPIRE);
+            skeleton
+                .condition_immunities
+                .replace(vec![Condition::Bleeding, Condition::Poisoned]);
+            // Dry bones don't burn for long
+            skeleton
+                .condition_resistance_percentages
+                .borrow_mut()
+                .insert(Condition::Burning, 50);
+            skeleton.loot.replace(Loot {
+                money: 4,
+                items: vec![EquipmentEntry::Consumable(HEALTH_POTION)],
+            });
             skeleton
         }
         CharacterType::Ghoul1 => {
             // TODO these should have archer behaviour, i.e. run away from melee
             let ghoul = Character::new(
-                bot(BotBehaviour::Fighter(Default::default()), 11.0),
+                bot(
+                    BotBehaviour::Fighter(FighterBehaviour::cowardly()),
+                    11.0,
+                    Some(0),
+                ),
                 "Ghoul",
                 PortraitId::Ghoul,
                 char_data.type_.sprite_id(),
@@ -306,11 +354,17 @@ pub fn create_character(
             );
             ghoul.health.change_max_value_to(9);
             ghoul.set_weapon(HandType::MainHand, BAD_BOW);
+            // Skittish, and quick to loose an extra arrow
+            ghoul.set_action_point_economy(5, 7);
             ghoul
         }
         CharacterType::Ghoul2 => {
             let ghoul = Character::new(
-                bot(BotBehaviour::Fighter(Default::default()), 14.0),
+                bot(
+                    BotBehaviour::Fighter(FighterBehaviour::cowardly()),
+                    14.0,
+                    Some(0),
+                ),
                 "Ghoul",
                 PortraitId::Ghoul,
                 char_data.type_.sprite_id(),
@@ -324,7 +378,7 @@ pub fn create_character(
         }
         CharacterType::Ogre => {
             let mut ogre = Character::new(
-                bot(BotBehaviour::Fighter(Default::default()), 12.0),
+                bot(BotBehaviour::Fighter(Default::default()), 12.0, Some(6)),
                 "Ogre",
                 PortraitId::Ogre,
                 SpriteId::Ogre,
@@ -336,12 +390,19 @@ pub fn create_character(
             ogre.set_weapon(HandType::MainHand, BAD_WAR_HAMMER);
             ogre.learn_ability(ENEMY_TACKLE);
             ogre.learn_passive(PassiveSkill::BloodRage);
+            ogre.condition_resistances
+                .borrow_mut()
+                .insert(Condition::Dazed, 1);
+            ogre.loot.replace(Loot {
+                money: 6,
+                items: vec![],
+            });
             ogre
         }
         CharacterType::Huldra => {
             let huldra = Character::new(
                 //bot(BotBehaviour::Magi(Default::default()), 9.0),
-                bot(BotBehaviour::Huldra(Default::default()), 12.0),
+                bot(BotBehaviour::Huldra(Default::default()), 12.0, None),
                 "Huldra",
                 PortraitId::Huldra,
                 SpriteId::Huldra,
@@ -354,11 +415,15 @@ pub fn create_character(
             huldra.armor_piece.set(Some(SHIRT));
             //huldra.set_weapon(HandType::MainHand, BAD_SWORD);
             huldra.health.change_max_value_to(40);
+            huldra.loot.replace(Loot {
+                money: 10,
+                items: vec![EquipmentEntry::Consumable(MANA_POTION)],
+            });
             huldra
         }
         CharacterType::Enslaved => {
             let enslaved = Character::new(
-                bot(BotBehaviour::Fighter(Default::default()), 12.0),
+                bot(BotBehaviour::Fighter(Default::default()), 12.0, Some(4)),
                 "Enslaved",
                 PortraitId::Ghoul,
                 SpriteId::Skeleton2,
@@ -387,10 +452,11 @@ pub fn create_character(
     Rc::new(char)
 }
 
-fn bot(behaviour: BotBehaviour, move_speed: f32) -> CharacterKind {
+fn bot(behaviour: BotBehaviour, move_speed: f32, morale: Option<u32>) -> CharacterKind {
     CharacterKind::Bot(Bot {
         behaviour,
         base_movement: move_speed,
+        morale,
     })
 }
 
@@ -457,7 +523,7 @@ pub fn make_medium_clara(party: &Rc<Party>) -> Character {
     // TODO:
     clara.armor_piece.set(Some(SHIRT));
     clara.learn_ability(FIREBALL);
-    clara.learn_ability_enhancement(FIREBALL_REACH);
+    clara.learn_ability_enhancement(FIREBALL_CAREFUL);
     clara.learn_ability(SHACKLED_MIND);
     clara
 }
@@ -537,7 +603,7 @@ fn make_high_clara(party: &Rc<Party>) -> Character {
     clara.learn_passive(PassiveSkill::CriticalCharge);
     clara.learn_ability(FIREBALL);
     clara.learn_ability_enhancement(FIREBALL_INFERNO);
-    clara.learn_ability_enhancement(FIREBALL_REACH);
+    clara.learn_ability_enhancement(FIREBALL_CAREFUL);
     clara.learn_ability_enhancement(FIREBALL_MASSIVE);
     clara.learn_ability(SHACKLED_MIND);
     clara.learn_ability(INFLICT_WOUNDS);
@@ -17,7 +17,7 @@ use macroquad::{
 use crate::{
     base_ui::draw_text_rounded,
     chest_scene::{generate_chest_content, ChestEntry},
-    core::{Character, EquipmentEntry},
+    core::{Character, EquipmentEntry, RestType},
     data::{CHAIN_MAIL, DAGGER, LEATHER_ARMOR, RAPIER, SMALL_SHIELD, SWORD},
     drawing::draw_dashed_line,
     init_fight_map::FightId,
@@ -55,7 +55,7 @@ impl Node {
 
 #[derive(Clone, Debug)]
 pub enum MapChoice {
-    Rest,
+    Rest(RestType),
     Shop(Vec<ShopEntry>),
     Fight(FightId),
     Chest(Vec<ChestEntry>),
@@ -92,8 +92,8 @@ impl MapScene {
             Node::new((3, 0), Some(MapChoice::Shop(generate_shop_contents()))),
             Node::new((3, 1), Some(MapChoice::Chest(generate_chest_content()))),
             Node::new((3, 2), Some(MapChoice::Fight(FightId::EliteOgre))),
-            Node::new((4, 0), Some(MapChoice::Rest)),
-            Node::new((4, 1), Some(MapChoice::Chest(generate_chest_content()))),
+            Node::new((4, 0), Some(MapChoice::Rest(RestType::Long))),
+            Node::new((4, 1), Some(MapChoice::Rest(RestType::Short))),
             Node::new((5, 0), Some(MapChoice::Fight(FightId::EasySurrounded))),
             Node::new((6, 0), Some(MapChoice::Fight(FightId::EliteHuldra))),
         ];
@@ -133,13 +133,14 @@ impl MapScene {
             column_sizes.insert(node.map_pos.0, n + 1);
             if let Some(choice) = &node.choice {
                 node.text = match choice {
-                    MapChoice::Rest => "Rest",
+                    MapChoice::Rest(RestType::Short) => "Short rest",
+                    MapChoice::Rest(RestType::Long) => "Rest",
                     MapChoice::Shop(..) => "Shop",
                     MapChoice::Fight(fight_id) => format!("{:?}", fight_id).leak(),
                     MapChoice::Chest(..) => "Chest",
                 };
                 node.texture = match choice {
-                    MapChoice::Rest => Some(rest_texture.clone()),
+                    MapChoice::Rest(..) => Some(rest_texture.clone()),
                     MapChoice::Fight(FightId::EliteOgre | FightId::EliteHuldra) => {
                         Some(fight_elite_texture.clone())
                     }
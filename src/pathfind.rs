@@ -17,6 +17,12 @@ use crate::{
 
 pub const CELLS_PER_ENTITY: u32 = 3;
 
+// Diagonal steps cost their true (Euclidean) length, so that movement matches weapon ranges
+const DIAGONAL_STEP_COST: f32 = std::f32::consts::SQRT_2;
+
+// How far from a character an obstacle can be, while still giving them cover
+const COVER_MAX_DISTANCE: i32 = 3;
+
 #[derive(Debug, Copy, Clone)]
 pub enum Occupation {
     Character(CharacterId),
@@ -42,9 +48,14 @@ pub struct Target {
     proximity_squared: f32,
 }
 
+#[derive(Debug)]
 pub struct PathfindGrid {
     dimensions: (u32, u32),
     occupied: RefCell<HashMap<Position, Occupation>>,
+    // Cells that can be walked through, but at double the movement cost (mud, rubble etc)
+    difficult: RefCell<HashSet<Position>>,
+    // Height of the ground; cells that aren't listed are at 0
+    elevation: RefCell<HashMap<Position, i32>>,
     cache_key: Cell<CacheKey>,
     cached_exploration_chart: RefCell<IndexMap<Position, ChartNode>>,
     cached_unexplored: RefCell<Vec<ChartNode>>,
@@ -71,6 +82,8 @@ impl PathfindGrid {
         Self {
             dimensions,
             occupied: Default::default(),
+            difficult: Default::default(),
+            elevation: Default::default(),
             cache_key: Default::default(),
             cached_exploration_chart: Default::default(),
             cached_unexplored: Default::default(),
@@ -122,6 +135,44 @@ impl PathfindGrid {
         *self.cached_exploration_chart.borrow_mut() = Default::default();
     }
 
+    pub fn set_difficult(&self, pos: Position, difficult: bool) {
+        let mut difficult_cells = self.difficult.borrow_mut();
+        if difficult {
+            difficult_cells.insert(pos);
+        } else {
+            difficult_cells.remove(&pos);
+        }
+
+        // Movement costs changed, so we can't re-use any previous exploration
+        self.cache_key.set(Default::default());
+        *self.cached_exploration_chart.borrow_mut() = Default::default();
+    }
+
+    pub fn is_difficult(&self, pos: Position) -> bool {
+        self.difficult.borrow().contains(&pos)
+    }
+
+    pub fn difficult_positions(&self) -> Ref<'_, HashSet<Position>> {
+        self.difficult.borrow()
+    }
+
+    pub fn set_elevation(&self, pos: Position, elevation: i32) {
+        let mut elevations = self.elevation.borrow_mut();
+        if elevation == 0 {
+            elevations.remove(&pos);
+        } else {
+            elevations.insert(pos, elevation);
+        }
+    }
+
+    pub fn elevation(&self, pos: Position) -> i32 {
+        self.elevation.borrow().get(&pos).copied().unwrap_or(0)
+    }
+
+    pub fn elevations(&self) -> Ref<'_, HashMap<Position, i32>> {
+        self.elevation.borrow()
+    }
+
     pub fn dimensions(&self) -> (u32, u32) {
         self.dimensions
     }
@@ -183,7 +234,9 @@ impl PathfindGrid {
 
         for (pos, chart_node) in chart.iter() {
             let sq_distance = sq_distance_between(*pos, target);
-            if sq_distance <= proximity_squared && !self.obstructed_line_of_sight(*pos, target) {
+            if sq_distance <= proximity_squared
+                && !self.obstructed_line_of_sight_for(Some(character_id), *pos, target)
+            {
                 //if distance_between(*pos, target) <= proximity {
                 //println!("Build path from chart... start={:?}, pos={:?}", start, pos);
                 let path = build_path_from_chart(&chart, start, *pos);
@@ -328,7 +381,7 @@ impl PathfindGrid {
             let (x, y) = chart_node.position;
 
             let dist = chart_node.distance_from_start;
-            let diagonal: f32 = 2f32.sqrt();
+            let diagonal = DIAGONAL_STEP_COST;
             let mut neighbors = [
                 ((x - 1, y - 1), dist + diagonal),
                 ((x - 1, y), dist + 1.0),
@@ -356,6 +409,12 @@ impl PathfindGrid {
             // The neighbors are (potentially) sorted, with the best one first; iterate in reverse
             // order since we push them onto a stack, and therefore want to push the best one last.
             for (neighbor_pos, neighbor_dist) in neighbors.into_iter().rev() {
+                // Entering difficult terrain costs twice as much
+                let neighbor_dist = if self.is_difficult(neighbor_pos) {
+                    dist + (neighbor_dist - dist) * 2.0
+                } else {
+                    neighbor_dist
+                };
                 let within_grid = (0..self.dimensions.0 as i32).contains(&neighbor_pos.0)
                     && (0..self.dimensions.1 as i32).contains(&neighbor_pos.1);
                 if within_grid && self.is_free(Some(character_id), neighbor_pos) {
@@ -404,8 +463,11 @@ impl PathfindGrid {
                         "Checking if unobstructed line between {:?} and {:?} ...",
                         chart_node.position, target_pos
                     );
-                    let obstructed_line_of_sight =
-                        self.obstructed_line_of_sight(chart_node.position, target_pos);
+                    let obstructed_line_of_sight = self.obstructed_line_of_sight_for(
+                        Some(character_id),
+                        chart_node.position,
+                        target_pos,
+                    );
                     if !obstructed_line_of_sight {
                         println!(
                             "Found a path to proximity ({:?}), pos={:?}. Chart size={}",
@@ -444,14 +506,60 @@ impl PathfindGrid {
     }
 
     pub fn obstructed_line_of_sight(&self, from: Position, to: Position) -> bool {
+        let viewer = match self.occupied.borrow().get(&from) {
+            Some(Occupation::Character(id)) => Some(*id),
+            _ => None,
+        };
+        self.obstructed_line_of_sight_for(viewer, from, to)
+    }
+
+    /// Like [Self::obstructed_line_of_sight], but the viewer may be somewhere other than `from` (e.g. when
+    /// considering a position to move to), so its own cells are never counted as blocking the line.
+    pub fn obstructed_line_of_sight_for(
+        &self,
+        viewer: Option<CharacterId>,
+        from: Position,
+        to: Position,
+    ) -> bool {
+        let occupied = self.occupied.borrow();
+        let destination = match occupied.get(&to) {
+            Some(Occupation::Character(id)) => Some(*id),
+            _ => None,
+        };
+
+        // Characters standing in the way block the line, unless it's a melee situation
+        let characters_block = sq_distance_between(from, to) > CENTER_MELEE_RANGE_SQUARED;
+
+        line_visitor(from, to, |x, y| match occupied.get(&(x, y)) {
+            Some(Occupation::Terrain(TerrainType::Tall)) => true,
+            Some(Occupation::Character(id)) => {
+                characters_block && Some(*id) != viewer && Some(*id) != destination
+            }
+            _ => false,
+        })
+    }
+
+    /// Whether the character at `to` is right behind an obstacle, as seen from `from`
+    pub fn has_cover(&self, from: Position, to: Position) -> bool {
+        let occupied = self.occupied.borrow();
         line_visitor(from, to, |x, y| {
-            matches!(
-                self.occupied.borrow().get(&(x, y)),
-                Some(Occupation::Terrain(TerrainType::Tall))
-            )
+            // The target itself takes up the cells within distance 1
+            let distance_to_target = (x - to.0).abs().max((y - to.1).abs());
+            (2..=COVER_MAX_DISTANCE).contains(&distance_to_target)
+                && matches!(occupied.get(&(x, y)), Some(Occupation::Terrain(..)))
         })
     }
 
+    /// Within the grid and not blocked by terrain (regardless of any characters standing there)
+    pub fn is_open_ground(&self, pos: Position) -> bool {
+        (0..self.dimensions.0 as i32).contains(&pos.0)
+            && (0..self.dimensions.1 as i32).contains(&pos.1)
+            && !matches!(
+                self.occupied.borrow().get(&pos),
+                Some(Occupation::Terrain { .. })
+            )
+    }
+
     pub fn is_free(&self, ignore_character: Option<CharacterId>, pos: Position) -> bool {
         // A character takes up 9 cells in a square. Check that each cell is free
         for x in pos.0 - 1..=pos.0 + 1 {
@@ -589,3 +697,30 @@ pub fn build_path_from_chart(
         positions,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn difficult_terrain_doubles_the_cost_of_entering_it() {
+        // Just tall enough for a character (which takes up 3x3 cells), so there's no way around
+        let grid = PathfindGrid::new((12, 3));
+        let plain = grid.find_shortest_path_to(0, (1, 1), (7, 1)).unwrap();
+        assert_eq!(plain.total_distance, 6.0);
+
+        for y in 0..3 {
+            grid.set_difficult((3, y), true);
+            grid.set_difficult((4, y), true);
+        }
+        let muddy = grid.find_shortest_path_to(0, (1, 1), (7, 1)).unwrap();
+        assert_eq!(muddy.total_distance, 8.0);
+    }
+
+    #[test]
+    fn diagonal_steps_cost_their_euclidean_length() {
+        let grid = PathfindGrid::new((10, 10));
+        let path = grid.find_shortest_path_to(0, (1, 1), (4, 4)).unwrap();
+        assert!((path.total_distance - 3.0 * 2f32.sqrt()).abs() < 0.01);
+    }
+}
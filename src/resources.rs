@@ -7,6 +7,7 @@ use macroquad::{
 
 use crate::{
     core::CoreGame,
+    d20::RandomDice,
     game_ui::UserInterface,
     game_ui_connection::GameUserInterfaceConnection,
     init_fight_map::GameInitState,
@@ -93,7 +94,7 @@ pub fn init_core_game(
     init_state: GameInitState,
 ) -> CoreGame {
     let mut game_ui = GameUserInterfaceConnection::uninitialized();
-    let core_game = CoreGame::new(game_ui.clone(), &init_state);
+    let core_game = CoreGame::new(game_ui.clone(), &init_state, Box::new(RandomDice));
     let gfx_user_interface = UserInterface::new(
         &core_game,
         resources,
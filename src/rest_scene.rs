@@ -14,7 +14,7 @@ use macroquad::{
 
 use crate::{
     base_ui::draw_text_rounded,
-    core::Character,
+    core::{Character, RestType},
     non_combat_ui::NonCombatPartyUi,
     sounds::SoundPlayer,
     textures::{EquipmentIconId, IconId, PortraitId},
@@ -22,14 +22,14 @@ use crate::{
 
 pub async fn run_rest_loop(
     mut player_characters: Vec<Rc<Character>>,
+    rest_type: RestType,
     font: Font,
     equipment_icons: &HashMap<EquipmentIconId, Texture2D>,
     icons: HashMap<IconId, Texture2D>,
     portrait_textures: &HashMap<PortraitId, Texture2D>,
 ) -> Vec<Rc<Character>> {
     for character in &mut player_characters {
-        character.health.gain(character.health.max() / 2);
-        character.mana.set_to_max();
+        character.rest(rest_type);
     }
 
     let characters: Vec<Rc<Character>> = player_characters;
@@ -57,11 +57,16 @@ pub async fn run_rest_loop(
             clear_background(BLACK);
             ui.draw_and_handle_input();
 
-            let text = "You regained 50% health and 100% mana";
+            let (health_fraction, mana_fraction) = rest_type.recovery();
+            let text = format!(
+                "You regained {}% health and {}% mana",
+                (health_fraction * 100.0) as u32,
+                (mana_fraction * 100.0) as u32
+            );
             let font_size = 32;
-            let text_dim = measure_text(text, Some(&font), font_size, 1.0);
+            let text_dim = measure_text(&text, Some(&font), font_size, 1.0);
             draw_text_rounded(
-                text,
+                &text,
                 screen_w / 2.0 - text_dim.width / 2.0,
                 150.0 + (text_dim.height) / 2.0,
                 TextParams {
@@ -0,0 +1,568 @@
+use std::{cell::Cell, fmt::Debug, fs, rc::Rc};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    core::{
+        Ability, AbilityEnhancement, ArmorPiece, Arrow, ArrowStack, AttackEnhancement, Attributes,
+        BaseAction, Character, CharacterKind, Consumable, EquipmentEntry, HandType,
+        OnAttackedReaction, OnHitReaction, Party, PlayerId, Position, Shield, Weapon, Wear,
+    },
+    data::*,
+    textures::{PortraitId, SpriteId},
+};
+
+// The static data is referred to by the names of the constants in data.rs, since the structs
+// themselves are full of &'static str and can't be deserialized.
+
+const WEAPONS: &[(&str, Weapon)] = &[
+    ("BAD_DAGGER", BAD_DAGGER),
+    ("DAGGER", DAGGER),
+    ("BAD_SWORD", BAD_SWORD),
+    ("ENSLAVED_SWORD", ENSLAVED_SWORD),
+    ("ENSLAVED_RAPIER", ENSLAVED_RAPIER),
+    ("SWORD", SWORD),
+    ("BAD_RAPIER", BAD_RAPIER),
+    ("RAPIER", RAPIER),
+    ("VAMPIRIC_SWORD", VAMPIRIC_SWORD),
+    ("BAD_WAR_HAMMER", BAD_WAR_HAMMER),
+    ("WAR_HAMMER", WAR_HAMMER),
+    ("BONE_CRUSHER", BONE_CRUSHER),
+    ("BERSERKER_AXE", BERSERKER_AXE),
+    ("THROWING_AXE", THROWING_AXE),
+    ("SPEAR", SPEAR),
+    ("BAD_BOW", BAD_BOW),
+    ("CHEAT_BOW", CHEAT_BOW),
+    ("BOW", BOW),
+    ("ELUSIVE_BOW", ELUSIVE_BOW),
+    ("LONGBOW", LONGBOW),
+    ("CROSSBOW", CROSSBOW),
+];
+
+const SHIELDS: &[(&str, Shield)] = &[
+    ("BAD_SMALL_SHIELD", BAD_SMALL_SHIELD),
+    ("SMALL_SHIELD", SMALL_SHIELD),
+    ("MEDIUM_SHIELD", MEDIUM_SHIELD),
+];
+
+const ARMOR_PIECES: &[(&str, ArmorPiece)] = &[
+    ("SHIRT", SHIRT),
+    ("ROBE", ROBE),
+    ("LEATHER_ARMOR", LEATHER_ARMOR),
+    ("CHAIN_MAIL", CHAIN_MAIL),
+    ("GOOD_CHAIN_MAIL", GOOD_CHAIN_MAIL),
+    ("LIGHT_CHAIN_MAIL", LIGHT_CHAIN_MAIL),
+];
+
+const CONSUMABLES: &[(&str, Consumable)] = &[
+    ("HEALTH_POTION", HEALTH_POTION),
+    ("MANA_POTION", MANA_POTION),
+    ("ADRENALIN_POTION", ADRENALIN_POTION),
+    ("ENERGY_POTION", ENERGY_POTION),
+    ("ARCANE_POTION", ARCANE_POTION),
+    ("ALCHEMISTS_FIRE", ALCHEMISTS_FIRE),
+];
+
+const ARROWS: &[(&str, Arrow)] = &[
+    ("PENETRATING_ARROWS", PENETRATING_ARROWS),
+    ("BARBED_ARROWS", BARBED_ARROWS),
+    ("POISON_ARROWS", POISON_ARROWS),
+    ("COLD_ARROWS", COLD_ARROWS),
+    ("EXPLODING_ARROWS", EXPLODING_ARROWS),
+];
+
+const ABILITIES: &[(&str, Ability)] = &[
+    ("SHIELD_BASH", SHIELD_BASH),
+    ("TRIP", TRIP),
+    ("ENTANGLE", ENTANGLE),
+    ("TAUNT", TAUNT),
+    ("SWEEP_ATTACK", SWEEP_ATTACK),
+    ("FLURRY", FLURRY),
+    ("LUNGE_ATTACK", LUNGE_ATTACK),
+    ("BRACE", BRACE),
+    ("SET_WEAPON", SET_WEAPON),
+    ("OVERWATCH", OVERWATCH),
+    ("AIM", AIM),
+    ("HIDE", HIDE),
+    ("FRENZY", FRENZY),
+    ("SCREAM", SCREAM),
+    ("SHACKLED_MIND", SHACKLED_MIND),
+    ("FREEZE", FREEZE),
+    ("PETRIFY", PETRIFY),
+    ("CONFUSE", CONFUSE),
+    ("WALL_OF_FIRE", WALL_OF_FIRE),
+    ("MIND_BLAST", MIND_BLAST),
+    ("SOUL_REND", SOUL_REND),
+    ("MIND_CRUSH", MIND_CRUSH),
+    ("RAISE_SKELETON", RAISE_SKELETON),
+    ("MANA_BURN", MANA_BURN),
+    ("DISPEL_MAGIC", DISPEL_MAGIC),
+    ("CLEANSE", CLEANSE),
+    ("GUST", GUST),
+    ("INFLICT_WOUNDS", INFLICT_WOUNDS),
+    ("HEAL", HEAL),
+    ("REGENERATE", REGENERATE),
+    ("HASTE", HASTE),
+    ("COMMAND_STRIKE", COMMAND_STRIKE),
+    ("THORNS", THORNS),
+    ("BLINK", BLINK),
+    ("SPIKE_TRAP", SPIKE_TRAP),
+    ("RESCUE", RESCUE),
+    ("INSPIRE", INSPIRE),
+    ("HEALING_NOVA", HEALING_NOVA),
+    ("SOOTHING_AURA", SOOTHING_AURA),
+    ("SELF_HEAL", SELF_HEAL),
+    ("SECOND_WIND", SECOND_WIND),
+    ("HEALING_RAIN", HEALING_RAIN),
+    ("PIERCING_SHOT", PIERCING_SHOT),
+    ("FIREBALL", FIREBALL),
+    ("KILL", KILL),
+    ("SEARING_LIGHT", SEARING_LIGHT),
+];
+
+const ATTACK_ENHANCEMENTS: &[(&str, AttackEnhancement)] = &[
+    ("STABBING", STABBING),
+    ("SLASHING", SLASHING),
+    ("DISARM", DISARM),
+    ("QUICK", QUICK),
+    ("SMITE", SMITE),
+    ("OVERWHELMING", OVERWHELMING),
+    ("CAREFUL_AIM", CAREFUL_AIM),
+    ("LONGER_REACH", LONGER_REACH),
+    ("EMPOWER", EMPOWER),
+    ("CRIPPLING_SHOT", CRIPPLING_SHOT),
+    ("CALLED_SHOT_LEGS", CALLED_SHOT_LEGS),
+    ("CALLED_SHOT_ARMS", CALLED_SHOT_ARMS),
+];
+
+const ABILITY_ENHANCEMENTS: &[(&str, AbilityEnhancement)] = &[
+    ("SHIELD_BASH_KNOCKBACK", SHIELD_BASH_KNOCKBACK),
+    ("SWEEP_ATTACK_PRECISE", SWEEP_ATTACK_PRECISE),
+    ("LUNGE_ATTACK_HEAVY_IMPACT", LUNGE_ATTACK_HEAVY_IMPACT),
+    ("LUNGE_ATTACK_REACH", LUNGE_ATTACK_REACH),
+    ("SCREAM_SHRIEK", SCREAM_SHRIEK),
+    (
+        "INFLICT_WOUNDS_NECROTIC_INFLUENCE",
+        INFLICT_WOUNDS_NECROTIC_INFLUENCE,
+    ),
+    ("HEAL_ENERGIZE", HEAL_ENERGIZE),
+    ("FIREBALL_MASSIVE", FIREBALL_MASSIVE),
+    ("FIREBALL_INFERNO", FIREBALL_INFERNO),
+    ("FIREBALL_CAREFUL", FIREBALL_CAREFUL),
+    ("SEARING_LIGHT_BURN", SEARING_LIGHT_BURN),
+    ("SEARING_LIGHT_SPLASH", SEARING_LIGHT_SPLASH),
+];
+
+const ATTACKED_REACTIONS: &[(&str, OnAttackedReaction)] = &[
+    ("PARRY", PARRY),
+    ("BLOCK", BLOCK),
+    ("SIDE_STEP", SIDE_STEP),
+    ("RIPOSTE", RIPOSTE),
+];
+
+const ON_HIT_REACTIONS: &[(&str, OnHitReaction)] =
+    &[("RAGE", RAGE), ("SPELL_REFLECT", SPELL_REFLECT)];
+
+const PASSIVE_SKILLS: &[(&str, PassiveSkill)] = &[
+    ("HardenedSkin", PassiveSkill::HardenedSkin),
+    ("WeaponProficiency", PassiveSkill::WeaponProficiency),
+    ("CriticalCharge", PassiveSkill::CriticalCharge),
+    ("Reaper", PassiveSkill::Reaper),
+    ("BloodRage", PassiveSkill::BloodRage),
+    ("ThrillOfBattle", PassiveSkill::ThrillOfBattle),
+    ("Honorless", PassiveSkill::Honorless),
+    ("Vigilant", PassiveSkill::Vigilant),
+    ("Cleave", PassiveSkill::Cleave),
+    ("Guardian", PassiveSkill::Guardian),
+    ("Berserker", PassiveSkill::Berserker),
+    ("Momentum", PassiveSkill::Momentum),
+    ("Counterspell", PassiveSkill::Counterspell),
+    ("Quickdraw", PassiveSkill::Quickdraw),
+];
+
+const PLAYERS: &[(&str, PlayerId)] = &[
+    ("Bob", PlayerId::Bob),
+    ("Alice", PlayerId::Alice),
+    ("Clara", PlayerId::Clara),
+];
+
+fn key_of<T: PartialEq + Debug>(table: &[(&'static str, T)], value: &T) -> String {
+    match table.iter().find(|(_key, v)| v == value) {
+        Some((key, _)) => key.to_string(),
+        None => panic!(
+            "Can't save {:?}; it's missing from the save file tables",
+            value
+        ),
+    }
+}
+
+fn value_of<T: Copy>(table: &[(&'static str, T)], key: &str) -> Result<T, String> {
+    match table.iter().find(|(k, _value)| *k == key) {
+        Some((_, value)) => Ok(*value),
+        None => Err(format!("Unknown key in save file: {}", key)),
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct SerializableParty {
+    money: u32,
+    stash: Vec<Option<SerializableEntry>>,
+    characters: Vec<SerializableCharacter>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+struct SerializableCharacter {
+    player: String,
+    position: Position,
+    // strength, agility, intellect, spirit
+    attributes: [u32; 4],
+    health: u32,
+    mana: u32,
+    level: u32,
+    experience: u32,
+    main_hand: Option<SerializableEntry>,
+    off_hand: Option<SerializableEntry>,
+    // How much of the weapons' durability has been used up
+    main_hand_wear: u32,
+    off_hand_wear: u32,
+    armor: Option<String>,
+    arrows: Option<(String, u32)>,
+    inventory: Vec<Option<SerializableEntry>>,
+    // Wear of the weapons in the inventory (by slot)
+    #[serde(default)]
+    inventory_wear: Vec<u32>,
+    abilities: Vec<String>,
+    attack_enhancements: Vec<String>,
+    ability_enhancements: Vec<String>,
+    attacked_reactions: Vec<String>,
+    on_hit_reactions: Vec<String>,
+    passive_skills: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+enum SerializableEntry {
+    Weapon(String),
+    Shield(String),
+    Armor(String),
+    Arrows(String, u32),
+    Consumable(String),
+}
+
+impl SerializableEntry {
+    fn from_entry(entry: EquipmentEntry) -> Self {
+        match entry {
+            EquipmentEntry::Weapon(weapon) => Self::Weapon(key_of(WEAPONS, &weapon)),
+            EquipmentEntry::Shield(shield) => Self::Shield(key_of(SHIELDS, &shield)),
+            EquipmentEntry::Armor(armor) => Self::Armor(key_of(ARMOR_PIECES, &armor)),
+            EquipmentEntry::Arrows(stack) => {
+                Self::Arrows(key_of(ARROWS, &stack.arrow), stack.quantity)
+            }
+            EquipmentEntry::Consumable(consumable) => {
+                Self::Consumable(key_of(CONSUMABLES, &consumable))
+            }
+        }
+    }
+
+    fn to_entry(&self) -> Result<EquipmentEntry, String> {
+        let entry = match self {
+            Self::Weapon(key) => EquipmentEntry::Weapon(value_of(WEAPONS, key)?),
+            Self::Shield(key) => EquipmentEntry::Shield(value_of(SHIELDS, key)?),
+            Self::Armor(key) => EquipmentEntry::Armor(value_of(ARMOR_PIECES, key)?),
+            Self::Arrows(key, quantity) => {
+                EquipmentEntry::Arrows(ArrowStack::new(value_of(ARROWS, key)?, *quantity))
+            }
+            Self::Consumable(key) => EquipmentEntry::Consumable(value_of(CONSUMABLES, key)?),
+        };
+        Ok(entry)
+    }
+}
+
+fn hand_entry(character: &Character, hand: HandType) -> Option<SerializableEntry> {
+    let entry = match hand {
+        HandType::MainHand => character.weapon(hand).map(EquipmentEntry::Weapon),
+        HandType::OffHand => character
+            .weapon(hand)
+            .map(EquipmentEntry::Weapon)
+            .or(character.shield().map(EquipmentEntry::Shield)),
+    };
+    entry.map(SerializableEntry::from_entry)
+}
+
+impl SerializableCharacter {
+    fn from_character(character: &Character) -> Self {
+        let attr = &character.base_attributes;
+        let abilities = character
+            .known_actions
+            .borrow()
+            .iter()
+            .filter_map(|action| match action {
+                // Stand up is only known while in a fight, after having been knocked prone
+                BaseAction::UseAbility(ability) if *ability != STAND_UP => {
+                    Some(key_of(ABILITIES, ability))
+                }
+                _ => None,
+            })
+            .collect();
+
+        Self {
+            player: key_of(PLAYERS, &character.player_id()),
+            position: character.pos(),
+            attributes: [
+                attr.strength.get(),
+                attr.agility.get(),
+                attr.intellect.get(),
+                attr.spirit.get(),
+            ],
+            health: character.health.current(),
+            mana: character.mana.current(),
+            level: character.level(),
+            experience: character.experience(),
+            main_hand: hand_entry(character, HandType::MainHand),
+            off_hand: hand_entry(character, HandType::OffHand),
+            main_hand_wear: character.weapon_wear(HandType::MainHand),
+            off_hand_wear: character.weapon_wear(HandType::OffHand),
+            armor: character
+                .armor_piece
+                .get()
+                .map(|armor| key_of(ARMOR_PIECES, &armor)),
+            arrows: character
+                .arrows
+                .get()
+                .map(|stack| (key_of(ARROWS, &stack.arrow), stack.quantity)),
+            inventory: character
+                .inventory
+                .iter()
+                .map(|slot| slot.get().map(SerializableEntry::from_entry))
+                .collect(),
+            inventory_wear: character
+                .inventory
+                .iter()
+                .map(|slot| match slot.get() {
+                    Some(EquipmentEntry::Weapon(weapon)) => weapon.wear.0,
+                    _ => 0,
+                })
+                .collect(),
+            abilities,
+            attack_enhancements: character
+                .known_attack_enhancements
+                .borrow()
+                .iter()
+                .map(|e| key_of(ATTACK_ENHANCEMENTS, e))
+                .collect(),
+            ability_enhancements: character
+                .known_ability_enhancements
+                .borrow()
+                .iter()
+                .map(|e| key_of(ABILITY_ENHANCEMENTS, e))
+                .collect(),
+            attacked_reactions: character
+                .known_attacked_reactions
+                .borrow()
+                .iter()
+                .map(|r| key_of(ATTACKED_REACTIONS, r))
+                .collect(),
+            on_hit_reactions: character
+                .known_on_hit_reactions
+                .borrow()
+                .iter()
+                .map(|r| key_of(ON_HIT_REACTIONS, r))
+                .collect(),
+            passive_skills: character
+                .learned_passive_skills()
+                .iter()
+                .map(|skill| key_of(PASSIVE_SKILLS, skill))
+                .collect(),
+        }
+    }
+
+    fn to_character(&self, party: &Rc<Party>) -> Result<Character, String> {
+        let player_id = value_of(PLAYERS, &self.player)?;
+        let (name, portrait, sprite) = match player_id {
+            PlayerId::Bob => ("Bob", PortraitId::Bob, SpriteId::Bob),
+            PlayerId::Alice => ("Alice", PortraitId::Alice, SpriteId::Alice),
+            PlayerId::Clara => ("Clara", PortraitId::Clara, SpriteId::Clara),
+        };
+        let [str, agi, intel, spi] = self.attributes;
+        let character = Character::new(
+            CharacterKind::Player(Rc::clone(party), player_id),
+            name,
+            portrait,
+            sprite,
+            Attributes::new(str, agi, intel, spi),
+            self.position,
+        );
+
+        // Learned before equipping, so that the equipment can't be rejected for lack of capacity etc
+        for key in &self.abilities {
+            character.learn_ability(value_of(ABILITIES, key)?);
+        }
+        for key in &self.attack_enhancements {
+            character.learn_attack_enhancement(value_of(ATTACK_ENHANCEMENTS, key)?);
+        }
+        for key in &self.ability_enhancements {
+            character.learn_ability_enhancement(value_of(ABILITY_ENHANCEMENTS, key)?);
+        }
+        for key in &self.attacked_reactions {
+            character.learn_attacked_reaction(value_of(ATTACKED_REACTIONS, key)?);
+        }
+        for key in &self.on_hit_reactions {
+            character.learn_on_hit_reaction(value_of(ON_HIT_REACTIONS, key)?);
+        }
+        for key in &self.passive_skills {
+            character.learn_passive(value_of(PASSIVE_SKILLS, key)?);
+        }
+
+        // Equipping the weapons through set_weapon() restores the attack actions' AP costs
+        if let Some(entry) = &self.main_hand {
+            let EquipmentEntry::Weapon(weapon) = entry.to_entry()? else {
+                return Err(format!("Main hand must hold a weapon: {:?}", entry));
+            };
+            character.set_weapon(HandType::MainHand, weapon);
+            character.set_weapon_wear(HandType::MainHand, self.main_hand_wear);
+        }
+        if let Some(entry) = &self.off_hand {
+            match entry.to_entry()? {
+                EquipmentEntry::Weapon(weapon) => {
+                    character.set_weapon(HandType::OffHand, weapon);
+                    character.set_weapon_wear(HandType::OffHand, self.off_hand_wear);
+                }
+                EquipmentEntry::Shield(shield) => character.set_shield(shield),
+                _ => {
+                    return Err(format!(
+                        "Off hand must hold a weapon or shield: {:?}",
+                        entry
+                    ))
+                }
+            }
+        }
+        let armor = match &self.armor {
+            Some(key) => Some(value_of(ARMOR_PIECES, key)?),
+            None => None,
+        };
+        character.armor_piece.set(armor);
+        let arrows = match &self.arrows {
+            Some((key, quantity)) => Some(ArrowStack::new(value_of(ARROWS, key)?, *quantity)),
+            None => None,
+        };
+        character.arrows.set(arrows);
+        for (i, (slot, entry)) in character.inventory.iter().zip(&self.inventory).enumerate() {
+            let mut entry = entry.as_ref().map(|entry| entry.to_entry()).transpose()?;
+            if let Some(EquipmentEntry::Weapon(weapon)) = &mut entry {
+                weapon.wear = Wear(self.inventory_wear.get(i).copied().unwrap_or(0));
+            }
+            slot.set(entry);
+        }
+
+        character.restore_progress(self.level, self.experience);
+        character
+            .health
+            .lose(character.health.max().saturating_sub(self.health));
+        character
+            .mana
+            .lose(character.mana.max().saturating_sub(self.mana));
+
+        Ok(character)
+    }
+}
+
+pub fn save_party(
+    filepath: &str,
+    characters: &[Rc<Character>],
+    party: &Party,
+) -> Result<(), String> {
+    let save = SerializableParty {
+        money: party.money.get(),
+        stash: party
+            .stash
+            .iter()
+            .map(|slot| slot.get().map(SerializableEntry::from_entry))
+            .collect(),
+        characters: characters
+            .iter()
+            .map(|character| SerializableCharacter::from_character(character))
+            .collect(),
+    };
+    let json_str = serde_json::to_string_pretty(&save).unwrap();
+    fs::write(filepath, json_str).map_err(|e| format!("Failed to write {}: {}", filepath, e))
+}
+
+pub fn load_party(filepath: &str) -> Result<(Rc<Party>, Vec<Character>), String> {
+    let json =
+        fs::read_to_string(filepath).map_err(|e| format!("Failed to read {}: {}", filepath, e))?;
+    let save = serde_json::from_str::<SerializableParty>(&json)
+        .map_err(|e| format!("Failed to parse {}: {}", filepath, e))?;
+
+    let party = Rc::new(Party {
+        money: Cell::new(save.money),
+        stash: Default::default(),
+    });
+    for (slot, entry) in party.stash.iter().zip(&save.stash) {
+        slot.set(entry.as_ref().map(|entry| entry.to_entry()).transpose()?);
+    }
+
+    let characters = save
+        .characters
+        .iter()
+        .map(|character| character.to_character(&party))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok((party, characters))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::map_data::make_low_level_party;
+
+    #[test]
+    fn party_survives_a_save_and_load_round_trip() {
+        let (party, characters) = make_low_level_party();
+        party.money.set(13);
+        let characters: Vec<Rc<Character>> = characters.into_iter().map(Rc::new).collect();
+
+        let path = std::env::temp_dir().join("rpg_save_file_round_trip.json");
+        let path = path.to_str().unwrap();
+        save_party(path, &characters, &party).unwrap();
+        let (loaded_party, loaded_characters) = load_party(path).unwrap();
+        fs::remove_file(path).unwrap();
+
+        assert_eq!(13, loaded_party.money.get());
+        assert_eq!(characters.len(), loaded_characters.len());
+        for (original, loaded) in characters.iter().zip(&loaded_characters) {
+            assert_eq!(original.name, loaded.name);
+            assert_eq!(
+                original.base_attributes.strength.get(),
+                loaded.base_attributes.strength.get()
+            );
+            assert_eq!(
+                original.base_attributes.spirit.get(),
+                loaded.base_attributes.spirit.get()
+            );
+            assert_eq!(
+                original.weapon(HandType::MainHand).map(|w| w.name),
+                loaded.weapon(HandType::MainHand).map(|w| w.name)
+            );
+            assert_eq!(
+                original.shield().map(|s| s.name),
+                loaded.shield().map(|s| s.name)
+            );
+            assert_eq!(original.known_abilities(), loaded.known_abilities());
+            assert_eq!(
+                *original.known_actions.borrow(),
+                *loaded.known_actions.borrow()
+            );
+        }
+    }
+
+    #[test]
+    fn unknown_key_is_an_error() {
+        let path = std::env::temp_dir().join("rpg_save_file_unknown_key.json");
+        let path = path.to_str().unwrap();
+        let json = r#"{"money": 0, "stash": [{"Weapon": "EXCALIBUR"}], "characters": []}"#;
+        fs::write(path, json).unwrap();
+        let result = load_party(path);
+        fs::remove_file(path).unwrap();
+
+        assert!(result.is_err());
+    }
+}
@@ -17,9 +17,10 @@ use crate::{
     base_ui::{draw_text_rounded, Drawable, TextLine},
     core::{ArrowStack, Character, EquipmentEntry, Party},
     data::{
-        ADRENALIN_POTION, ARCANE_POTION, BARBED_ARROWS, BOW, CHAIN_MAIL, COLD_ARROWS, DAGGER,
-        ENERGY_POTION, EXPLODING_ARROWS, HEALTH_POTION, LEATHER_ARMOR, MANA_POTION, MEDIUM_SHIELD,
-        PENETRATING_ARROWS, RAPIER, SMALL_SHIELD, SWORD, WAR_HAMMER,
+        ADRENALIN_POTION, ALCHEMISTS_FIRE, ARCANE_POTION, BARBED_ARROWS, BERSERKER_AXE, BOW,
+        CHAIN_MAIL, COLD_ARROWS, CROSSBOW, DAGGER, ENERGY_POTION, EXPLODING_ARROWS, HEALTH_POTION,
+        LEATHER_ARMOR, LONGBOW, MANA_POTION, MEDIUM_SHIELD, PENETRATING_ARROWS, POISON_ARROWS,
+        RAPIER, SMALL_SHIELD, SPEAR, SWORD, THROWING_AXE, VAMPIRIC_SWORD, WAR_HAMMER,
     },
     equipment_ui::equipment_tooltip,
     non_combat_ui::NonCombatPartyUi,
@@ -230,7 +231,13 @@ pub fn generate_shop_contents() -> Vec<ShopEntry> {
         (EquipmentEntry::Weapon(DAGGER), 3),
         (EquipmentEntry::Weapon(SWORD), 8),
         (EquipmentEntry::Weapon(RAPIER), 8),
+        (EquipmentEntry::Weapon(VAMPIRIC_SWORD), 16),
+        (EquipmentEntry::Weapon(SPEAR), 7),
+        (EquipmentEntry::Weapon(BERSERKER_AXE), 14),
+        (EquipmentEntry::Weapon(THROWING_AXE), 6),
         (EquipmentEntry::Weapon(BOW), 11),
+        (EquipmentEntry::Weapon(LONGBOW), 13),
+        (EquipmentEntry::Weapon(CROSSBOW), 12),
         (EquipmentEntry::Armor(LEATHER_ARMOR), 4),
         (EquipmentEntry::Armor(CHAIN_MAIL), 12),
         (EquipmentEntry::Shield(SMALL_SHIELD), 5),
@@ -240,11 +247,13 @@ pub fn generate_shop_contents() -> Vec<ShopEntry> {
         (EquipmentEntry::Consumable(ADRENALIN_POTION), 6),
         (EquipmentEntry::Consumable(ENERGY_POTION), 6),
         (EquipmentEntry::Consumable(ARCANE_POTION), 4),
+        (EquipmentEntry::Consumable(ALCHEMISTS_FIRE), 5),
         (
             EquipmentEntry::Arrows(ArrowStack::new(PENETRATING_ARROWS, 3)),
             4,
         ),
         (EquipmentEntry::Arrows(ArrowStack::new(BARBED_ARROWS, 3)), 4),
+        (EquipmentEntry::Arrows(ArrowStack::new(POISON_ARROWS, 3)), 4),
         (EquipmentEntry::Arrows(ArrowStack::new(COLD_ARROWS, 3)), 4),
         (
             EquipmentEntry::Arrows(ArrowStack::new(EXPLODING_ARROWS, 3)),
@@ -192,6 +192,7 @@ pub async fn run_editor() {
         PassiveSkill::ThrillOfBattle,
         PassiveSkill::Honorless,
         PassiveSkill::Vigilant,
+        PassiveSkill::Cleave,
     ];
 
     let mut rows = vec![];
@@ -145,6 +145,21 @@ pub fn build_character_stats_table(font: &Font, character: Rc<Character>) -> Cha
         ),
     );
 
+    let ap_row = Element::Text(
+        TextLine::new(
+            format!("AP per turn: {}", character.ap_per_turn()),
+            16,
+            WHITE,
+            Some(font.clone()),
+        )
+        .with_padding(0.0, 7.0)
+        .with_tooltip(
+            font.clone(),
+            "Action points",
+            vec!["Regained at the end of each of your turns.".to_string()],
+        ),
+    );
+
     let defense_header: Element = Element::Text(TextLine::new(
         "|<shield>| Defenses",
         16,
@@ -223,6 +238,7 @@ pub fn build_character_stats_table(font: &Font, character: Rc<Character>) -> Cha
             Element::Empty(0.0, 8.0),
             movement_row,
             spell_mod_row,
+            ap_row,
             Element::Empty(0.0, 8.0),
             defense_header_row,
             Element::Empty(0.0, 0.0),
@@ -143,6 +143,7 @@ impl TargetUi {
                 },
             );
             action_points_row.current_ap = char.action_points.current();
+            action_points_row.max_ap = char.action_points.max();
             let mut health_bar = ResourceBar::horizontal(char.health.max(), COL_RED, (80.0, 10.0));
             health_bar.current = char.health.current();
 
@@ -110,8 +110,6 @@ pub async fn run_transition_loop(
 
             let mut recovered = vec![];
 
-            char.conditions.borrow_mut().clear();
-
             let health_gain = char
                 .health
                 .gain(((char.health.max() - char.health.current()) as f32 * 0.1).ceil() as u32);
@@ -126,8 +124,6 @@ pub async fn run_transition_loop(
                 recovered.push(format!("{} |<mana>|", mana_gain));
             }
 
-            char.stamina.set_to_max();
-
             if !recovered.is_empty() {
                 restoration_texts.insert(
                     char.player_id(),
@@ -29,9 +29,13 @@ use crate::{
         OnHitReaction, Party, WeaponType,
     },
     data::{
-        PassiveSkill, BRACE, CRIPPLING_SHOT, FIREBALL, HEAL, HEALING_NOVA, HEALING_RAIN,
-        LUNGE_ATTACK, MIND_BLAST, OVERWHELMING, QUICK, RAGE, SCREAM, SHACKLED_MIND, SIDE_STEP,
-        SMITE, SWEEP_ATTACK,
+        PassiveSkill, AIM, BLINK, BRACE, CALLED_SHOT_ARMS, CALLED_SHOT_LEGS, CLEANSE,
+        COMMAND_STRIKE, CONFUSE, CRIPPLING_SHOT, DISARM, DISPEL_MAGIC, ENTANGLE, FIREBALL, FLURRY,
+        FREEZE, FRENZY, GUST, HEAL, HEALING_NOVA, HEALING_RAIN, HIDE, LUNGE_ATTACK, MANA_BURN,
+        MIND_BLAST, MIND_CRUSH, OVERWATCH, OVERWHELMING, PETRIFY, QUICK, RAGE, RAISE_SKELETON,
+        REGENERATE, RESCUE, RIPOSTE, SCREAM, SECOND_WIND, SET_WEAPON, SHACKLED_MIND, SIDE_STEP,
+        SMITE, SOOTHING_AURA, SOUL_REND, SPELL_REFLECT, SPIKE_TRAP, SWEEP_ATTACK, TAUNT, THORNS,
+        TRIP, WALL_OF_FIRE,
     },
     non_combat_ui::{NonCombatCharacterUi, PortraitRow},
     sounds::SoundPlayer,
@@ -71,7 +75,9 @@ enum AttributeName {
 struct RewardSelectionUi {
     bottom_panel: NonCombatCharacterUi,
     attribute_selection_card: Element,
-    selected_attribute: Option<AttributeName>,
+    // One per victory, plus one more when the character has gained a level
+    attribute_points: usize,
+    selected_attributes: Vec<AttributeName>,
     str_element: Rc<RefCell<TextLine>>,
     agi_element: Rc<RefCell<TextLine>>,
     int_element: Rc<RefCell<TextLine>>,
@@ -92,6 +98,7 @@ impl RewardSelectionUi {
         icons: HashMap<IconId, Texture2D>,
         portrait_textures: &HashMap<PortraitId, Texture2D>,
         rewards: Vec<(ButtonAction, Option<&'static str>)>,
+        leveled_up: bool,
         next_button_id: &mut u32,
         sound_player: SoundPlayer,
     ) -> Self {
@@ -166,8 +173,28 @@ impl RewardSelectionUi {
             min_width: Some(card_w),
             align: Align::Center,
             children: vec![
-                //Element::Text(TextLine::new("1 stat increase", 32, header_color, None)),
-                Element::Empty(0.0, 0.0),
+                Element::Text(TextLine::new(
+                    if leveled_up {
+                        format!("Reached level {}! 2 stat increases", character.level())
+                    } else {
+                        "1 stat increase".to_string()
+                    },
+                    28,
+                    if leveled_up { ORANGE } else { WHITE },
+                    Some(font.clone()),
+                )),
+                Element::Text(TextLine::new(
+                    format!(
+                        "Level {}  ({} / {} XP)",
+                        character.level(),
+                        character.experience(),
+                        character.experience_for_next_level()
+                    ),
+                    18,
+                    LIGHTGRAY,
+                    Some(font.clone()),
+                )),
+                Element::Empty(0.0, 10.0),
                 Element::RcRefCell(str_element.clone()),
                 Element::RcRefCell(agi_element.clone()),
                 Element::RcRefCell(int_element.clone()),
@@ -179,7 +206,8 @@ impl RewardSelectionUi {
         Self {
             bottom_panel,
             attribute_selection_card,
-            selected_attribute: None,
+            attribute_points: if leveled_up { 2 } else { 1 },
+            selected_attributes: vec![],
             str_element,
             agi_element,
             int_element,
@@ -198,7 +226,29 @@ impl RewardSelectionUi {
     }
 
     fn has_remaining_rewards(&self) -> bool {
-        self.selected_btn.is_none() || self.selected_attribute.is_none()
+        self.selected_btn.is_none() || self.selected_attributes.len() < self.attribute_points
+    }
+
+    fn change_attribute(&self, attribute: AttributeName, amount: i32, color: Color) {
+        let element = match attribute {
+            AttributeName::Str => {
+                self.character.add_to_strength(amount);
+                &self.str_element
+            }
+            AttributeName::Agi => {
+                self.character.add_to_agility(amount);
+                &self.agi_element
+            }
+            AttributeName::Int => {
+                self.character.add_to_intellect(amount);
+                &self.int_element
+            }
+            AttributeName::Spi => {
+                self.character.add_to_spirit(amount);
+                &self.spi_element
+            }
+        };
+        element.borrow_mut().set_color(color);
     }
 
     fn draw(&mut self) {
@@ -212,9 +262,10 @@ impl RewardSelectionUi {
         let card_w = 480.0;
         let card_color = Color::new(0.1, 0.1, 0.1, 1.00);
 
+        let mut hovered_attribute = None;
+
         self.attribute_selection_card.draw(x_mid - card_w / 2.0, y);
 
-        let mut hovered_attribute = None;
         if self.str_element.borrow().has_been_hovered.take().is_some() {
             hovered_attribute = Some(AttributeName::Str);
         }
@@ -230,53 +281,15 @@ impl RewardSelectionUi {
 
         if is_mouse_button_pressed(MouseButton::Left) {
             if let Some(new_attribute) = hovered_attribute {
-                let mut did_change = true;
-                if let Some(old_attribute) = self.selected_attribute {
-                    did_change = new_attribute != old_attribute;
-                    if did_change {
-                        match old_attribute {
-                            AttributeName::Str => {
-                                self.character.add_to_strength(-1);
-                                self.str_element.borrow_mut().set_color(WHITE);
-                            }
-                            AttributeName::Agi => {
-                                self.character.add_to_agility(-1);
-                                self.agi_element.borrow_mut().set_color(WHITE);
-                            }
-                            AttributeName::Int => {
-                                self.character.add_to_intellect(-1);
-                                self.int_element.borrow_mut().set_color(WHITE);
-                            }
-                            AttributeName::Spi => {
-                                self.character.add_to_spirit(-1);
-                                self.spi_element.borrow_mut().set_color(WHITE);
-                            }
-                        }
-                    }
-                }
-
-                if did_change {
-                    match new_attribute {
-                        AttributeName::Str => {
-                            self.character.add_to_strength(1);
-                            self.str_element.borrow_mut().set_color(ORANGE);
-                        }
-                        AttributeName::Agi => {
-                            self.character.add_to_agility(1);
-                            self.agi_element.borrow_mut().set_color(ORANGE);
-                        }
-                        AttributeName::Int => {
-                            self.character.add_to_intellect(1);
-                            self.int_element.borrow_mut().set_color(ORANGE);
-                        }
-                        AttributeName::Spi => {
-                            self.character.add_to_spirit(1);
-                            self.spi_element.borrow_mut().set_color(ORANGE);
-                        }
+                if !self.selected_attributes.contains(&new_attribute) {
+                    // Once all points are spent, picking another attribute moves the oldest point
+                    if self.selected_attributes.len() == self.attribute_points {
+                        let old_attribute = self.selected_attributes.remove(0);
+                        self.change_attribute(old_attribute, -1, WHITE);
                     }
+                    self.change_attribute(new_attribute, 1, ORANGE);
                     self.bottom_panel.on_character_stats_changed();
-                    //bottom_panel2.draw_and_handle_input();
-                    self.selected_attribute = Some(new_attribute);
+                    self.selected_attributes.push(new_attribute);
                 }
             }
         }
@@ -409,7 +422,15 @@ pub async fn run_victory_loop(
         let mut transition_countdown = None;
 
         let mut candidate_rewards = vec![];
-        for enhancement in vec![QUICK, SMITE, OVERWHELMING, CRIPPLING_SHOT] {
+        for enhancement in vec![
+            QUICK,
+            SMITE,
+            OVERWHELMING,
+            CRIPPLING_SHOT,
+            DISARM,
+            CALLED_SHOT_LEGS,
+            CALLED_SHOT_ARMS,
+        ] {
             let label = match enhancement.weapon_requirement {
                 Some(WeaponType::Melee) => "Melee attack",
                 Some(WeaponType::Ranged) => "Ranged attack",
@@ -421,13 +442,41 @@ pub async fn run_victory_loop(
             FIREBALL,
             SWEEP_ATTACK,
             LUNGE_ATTACK,
+            FLURRY,
+            FRENZY,
             BRACE,
+            SET_WEAPON,
+            TAUNT,
+            OVERWATCH,
+            AIM,
+            SECOND_WIND,
             SCREAM,
             SHACKLED_MIND,
+            FREEZE,
+            PETRIFY,
+            CONFUSE,
+            WALL_OF_FIRE,
             MIND_BLAST,
+            MIND_CRUSH,
+            SOUL_REND,
+            MANA_BURN,
+            DISPEL_MAGIC,
+            CLEANSE,
+            THORNS,
+            COMMAND_STRIKE,
+            BLINK,
+            SPIKE_TRAP,
+            GUST,
+            TRIP,
+            ENTANGLE,
+            RAISE_SKELETON,
+            HIDE,
             HEAL,
             HEALING_NOVA,
             HEALING_RAIN,
+            REGENERATE,
+            RESCUE,
+            SOOTHING_AURA,
         ] {
             candidate_rewards.push((ButtonAction::Action(BaseAction::UseAbility(ability)), None));
         }
@@ -443,15 +492,13 @@ pub async fn run_victory_loop(
                 }
             }
         }
-        {
-            let reaction = SIDE_STEP;
+        for reaction in [SIDE_STEP, RIPOSTE] {
             candidate_rewards.push((
                 ButtonAction::OnAttackedReaction(reaction),
                 Some("On attacked"),
             ));
         }
-        {
-            let reaction = RAGE;
+        for reaction in [RAGE, SPELL_REFLECT] {
             candidate_rewards.push((ButtonAction::OnHitReaction(reaction), Some("On hit")));
         }
         for passive in [
@@ -463,6 +510,12 @@ pub async fn run_victory_loop(
             PassiveSkill::ThrillOfBattle,
             PassiveSkill::Honorless,
             PassiveSkill::Vigilant,
+            PassiveSkill::Cleave,
+            PassiveSkill::Guardian,
+            PassiveSkill::Berserker,
+            PassiveSkill::Momentum,
+            PassiveSkill::Counterspell,
+            PassiveSkill::Quickdraw,
         ] {
             candidate_rewards.push((ButtonAction::Passive(passive), Some("Passive")));
         }
@@ -490,9 +543,16 @@ pub async fn run_victory_loop(
             .map(|ch| ch.listen_to_changed_equipment())
             .collect();
 
+        // Experience from the fight is turned into (at most) one level per victory
+        let leveled_up: Vec<bool> = characters
+            .iter()
+            .map(|char| !char.is_summon() && char.try_level_up())
+            .collect();
+
         let mut reward_selection_uis: Vec<RewardSelectionUi> = characters
             .iter()
-            .map(|char| {
+            .zip(leveled_up)
+            .map(|(char, leveled_up)| {
                 RewardSelectionUi::new(
                     Rc::clone(char),
                     font.clone(),
@@ -500,6 +560,7 @@ pub async fn run_victory_loop(
                     icons.clone(),
                     portrait_textures,
                     rewards.clone(),
+                    leveled_up,
                     &mut next_button_id,
                     sound_player.clone(),
                 )
@@ -663,10 +724,9 @@ fn can_learn(character: &Character, learning: Learning) -> bool {
             character.knows_ability(enhancement.ability_id)
                 && !character.knows_ability_enhancement(enhancement)
         }
-        Learning::Passive(passive_skill) => !character
-            .known_passive_skills
-            .borrow()
-            .contains(&passive_skill),
+        Learning::Passive(passive_skill) => {
+            !character.learned_passive_skills().contains(&passive_skill)
+        }
     }
 }
 
@@ -695,6 +755,11 @@ fn action_to_reward_choice(btn_action: ButtonAction) -> Learning {
         ButtonAction::AttackEnhancement(enhancement) => Learning::AttackEnhancement(enhancement),
         ButtonAction::AbilityEnhancement(enhancement) => Learning::AbilityEnhancement(enhancement),
         ButtonAction::Passive(skill) => Learning::Passive(skill),
-        ButtonAction::OpportunityAttack | ButtonAction::Proceed => unreachable!(),
+        ButtonAction::MovementEnhancement(..)
+        | ButtonAction::OpportunityAttack
+        | ButtonAction::OverwatchShot
+        | ButtonAction::Intercept
+        | ButtonAction::Counterspell
+        | ButtonAction::Proceed => unreachable!(),
     }
 }